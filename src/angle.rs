@@ -0,0 +1,75 @@
+use crate::Qreal;
+
+/// An angle measured in radians.
+///
+/// Rotations and phase shifts, like [`Qureg::rotate_x()`] or
+/// [`Qureg::phase_shift()`], accept any value that can be converted into
+/// `Radians`.  This includes a plain [`Qreal`], which is then interpreted
+/// as an angle in radians, and an angle in [`Degrees`], which is converted
+/// automatically.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+/// qureg.init_zero_state();
+///
+/// // rotate by PI/2 around the X-axis
+/// qureg.rotate_x(0, Degrees(90.0)).unwrap();
+/// let prob = qureg.calc_prob_of_outcome(0, 1).unwrap();
+/// assert!((prob - 0.5).abs() < EPSILON);
+///
+/// // the same as: qureg.rotate_x(0, PI / 2.)
+/// qureg.rotate_x(0, Radians(PI / 2.)).unwrap();
+/// let prob = qureg.calc_prob_of_outcome(0, 1).unwrap();
+/// assert!((prob - 1.).abs() < EPSILON);
+/// ```
+///
+/// [`Qureg::rotate_x()`]: crate::Qureg::rotate_x()
+/// [`Qureg::phase_shift()`]: crate::Qureg::phase_shift()
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Radians(pub Qreal);
+
+/// An angle measured in degrees.
+///
+/// See [`Radians`] for more information.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let angle = Radians::from(Degrees(180.));
+/// assert!((angle.0 - PI).abs() < EPSILON);
+///
+/// let angle = Degrees::from(Radians(PI / 2.));
+/// assert!((angle.0 - 90.).abs() < EPSILON);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Degrees(pub Qreal);
+
+impl From<Qreal> for Radians {
+    fn from(value: Qreal) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Radians> for Qreal {
+    fn from(value: Radians) -> Self {
+        value.0
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(value: Degrees) -> Self {
+        Self(value.0.to_radians())
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(value: Radians) -> Self {
+        Self(value.0.to_degrees())
+    }
+}
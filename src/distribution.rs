@@ -0,0 +1,116 @@
+use crate::Qreal;
+
+/// Probability distribution of measurement outcomes of a sub-register.
+///
+/// The outcome `k` is the integer whose `j`-th bit is the value of the qubit
+/// `qubits()[j]`, i.e. the qubits are treated as of *increasing*
+/// significance.
+///
+/// This is returned by [`Qureg::marginal()`].
+///
+/// [`Qureg::marginal()`]: crate::Qureg::marginal()
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    qubits: Vec<i32>,
+    probs:  Vec<Qreal>,
+}
+
+impl Distribution {
+    pub(crate) fn new(
+        qubits: Vec<i32>,
+        probs: Vec<Qreal>,
+    ) -> Self {
+        debug_assert_eq!(probs.len(), 1 << qubits.len());
+        Self {
+            qubits,
+            probs,
+        }
+    }
+
+    /// Labels of the qubits the distribution is over.
+    #[must_use]
+    pub fn qubits(&self) -> &[i32] {
+        &self.qubits
+    }
+
+    /// Probabilities of all outcomes.
+    ///
+    /// The slice has length `1 << qubits().len()`.
+    #[must_use]
+    pub fn probs(&self) -> &[Qreal] {
+        &self.probs
+    }
+
+    /// Probability of the given `outcome`.
+    ///
+    /// Returns `None` if `outcome` is not smaller than `1 << qubits().len()`.
+    #[must_use]
+    pub fn prob(
+        &self,
+        outcome: u64,
+    ) -> Option<Qreal> {
+        usize::try_from(outcome)
+            .ok()
+            .and_then(|i| self.probs.get(i))
+            .copied()
+    }
+
+    /// The most likely outcome together with its probability.
+    ///
+    /// If there are many outcomes with the same maximal probability, the
+    /// smallest of them is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(6).unwrap();
+    ///
+    /// let dist = qureg.marginal(&[1, 2]).unwrap();
+    /// let (outcome, prob) = dist.most_likely();
+    /// assert_eq!(outcome, 3);
+    /// assert!((prob - 1.).abs() < EPSILON);
+    /// ```
+    #[must_use]
+    pub fn most_likely(&self) -> (u64, Qreal) {
+        self.probs.iter().enumerate().fold(
+            (0, self.probs[0]),
+            |(outcome, max), (i, &p)| {
+                if p > max {
+                    (i as u64, p)
+                } else {
+                    (outcome, max)
+                }
+            },
+        )
+    }
+
+    /// Shannon entropy of the distribution, in bits.
+    ///
+    /// Outcomes with zero probability do not contribute to the entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let dist = qureg.marginal(&[0, 1]).unwrap();
+    /// assert!((dist.entropy() - 2.).abs() < 4. * EPSILON);
+    /// ```
+    #[must_use]
+    pub fn entropy(&self) -> Qreal {
+        -self
+            .probs
+            .iter()
+            .filter(|&&p| p > 0.)
+            .map(|&p| p * p.log2())
+            .sum::<Qreal>()
+    }
+}
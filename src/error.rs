@@ -45,6 +45,19 @@ pub enum QuestError {
     NulError(std::ffi::NulError),
     IntoStringError(std::ffi::IntoStringError),
     ArrayLengthError,
+    /// The size of the requested register overflows the platform's address
+    /// space.
+    ///
+    /// This guards only against the overflow.  A register that is merely too
+    /// large for the available memory is still passed to `QuEST`, which exits
+    /// the process if the allocation fails.
+    AllocationError,
+    /// A qubit index is not representable as `i32`, or is outside of the
+    /// range of qubits of the register.
+    QubitIndexError,
+    /// Reading or writing amplitudes failed with an I/O error of the given
+    /// kind.
+    IoError(std::io::ErrorKind),
 }
 
 /// Report error in a `QuEST` API call.
@@ -13,6 +13,7 @@ use std::ffi::{
     c_int,
     c_longlong,
     c_ulong,
+    c_void,
 };
 
 use crate::{
@@ -32,13 +33,13 @@ pub enum phaseGateType {
 #[derive(Debug, Clone, Copy)]
 pub struct QASMLogger {
     /// generated QASM string
-    buffer:     SendPtr<c_char>,
+    buffer:        SendPtr<c_char>,
     /// maximum number of chars before overflow
-    bufferSize: c_int,
+    bufferSize:    c_int,
     /// number of chars currently in buffer
-    bufferFill: c_int,
+    bufferFill:    c_int,
     /// whether gates are being added to buffer
-    isLogging:  c_int,
+    pub isLogging: c_int,
 }
 
 #[repr(C)]
@@ -87,7 +88,7 @@ pub struct ComplexMatrixN {
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Vector {
     pub x: qreal,
     pub y: qreal,
@@ -141,17 +142,21 @@ pub struct DiagonalOp {
     pub deviceOperator:   ComplexArray,
 }
 
+/// Raw `QuEST` register, as defined in `QuEST.h`.
+///
+/// Re-exported as `RawQureg`.  See `Qureg::from_raw()` and
+/// `Qureg::into_raw()`.
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct Qureg {
     pub isDensityMatrix:      c_int,
     pub numQubitsRepresented: c_int,
     numQubitsInStateVec:      c_int,
-    numAmpsPerChunk:          c_longlong,
+    pub numAmpsPerChunk:      c_longlong,
     pub numAmpsTotal:         c_longlong,
-    chunkId:                  c_int,
+    pub chunkId:              c_int,
 
-    numChunks: c_int,
+    pub numChunks: c_int,
 
     stateVec:     ComplexArray,
     pairStateVec: ComplexArray,
@@ -160,7 +165,7 @@ pub struct Qureg {
     firstLevelReduction:  SendPtr<qreal>,
     secondLevelReduction: SendPtr<qreal>,
 
-    qasmLog: SendPtr<QASMLogger>,
+    pub(crate) qasmLog: SendPtr<QASMLogger>,
 }
 
 #[repr(C)]
@@ -172,6 +177,37 @@ pub struct QuESTEnv {
     numSeeds: c_int,
 }
 
+impl QuESTEnv {
+    /// Copy of the environment without the array of seeds.
+    pub(crate) fn without_seeds(self) -> Self {
+        Self {
+            seeds: SendPtr(std::ptr::null_mut()),
+            numSeeds: 0,
+            ..self
+        }
+    }
+
+    /// Free the array of seeds allocated by `QuEST`, but leave the rest of the
+    /// environment intact.
+    ///
+    /// # Safety
+    ///
+    /// The seeds must have been allocated by `QuEST` and not freed before.
+    pub(crate) unsafe fn free_seeds(&mut self) {
+        unsafe {
+            free(self.seeds.get().cast());
+        }
+        *self = self.without_seeds();
+    }
+}
+
+// Functions from the C standard library
+extern "C" {
+    fn free(ptr: *mut c_void);
+
+    pub fn atexit(func: extern "C" fn()) -> c_int;
+}
+
 #[link(name = "QuEST")]
 extern "C" {
 
@@ -306,6 +342,12 @@ extern "C" {
         imags: *const qreal,
     );
 
+    pub fn initStateFromSingleFile(
+        qureg: *mut Qureg,
+        filename: *const c_char,
+        env: QuESTEnv,
+    ) -> c_int;
+
     pub fn setAmps(
         qureg: Qureg,
         startInd: c_longlong,
@@ -921,7 +963,7 @@ extern "C" {
         u: ComplexMatrixN,
     );
 
-    fn bindArraysToStackComplexMatrixN(
+    pub fn bindArraysToStackComplexMatrixN(
         numQubits: c_int,
         re: *mut *mut qreal,
         im: *mut *mut qreal,
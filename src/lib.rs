@@ -5,22 +5,35 @@
 
 use error::catch_quest_exception;
 
+mod angle;
+mod distribution;
 mod error;
 mod ffi;
 mod matrices;
 mod numbers;
 mod operators;
+pub mod prelude;
+mod probability;
+mod qasm;
 mod questenv;
 mod qureg;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 #[cfg(test)]
 mod tests;
 
+pub use angle::{
+    Degrees,
+    Radians,
+};
+pub use distribution::Distribution;
 pub use error::QuestError;
 pub use ffi::{
     bitEncoding as BitEncoding,
     pauliOpType as PauliOpType,
     phaseFunc as PhaseFunc,
     phaseGateType as PhaseGateType,
+    Qureg as RawQureg,
 };
 pub use matrices::{
     init_complex_matrix_from_slice,
@@ -28,6 +41,8 @@ pub use matrices::{
     ComplexMatrix2,
     ComplexMatrix4,
     ComplexMatrixN,
+    ParametricGate,
+    StackComplexMatrixN,
     Vector,
 };
 pub use numbers::{
@@ -50,7 +65,11 @@ pub use operators::{
     sync_diagonal_op,
     DiagonalOp,
     PauliHamil,
+    PauliHamilBuilder,
+    TrotterConfig,
 };
+pub use probability::Probability;
+pub use qasm::run_qasm;
 pub use questenv::QuestEnv;
 pub use qureg::{
     apply_pauli_hamil,
@@ -61,11 +80,18 @@ pub use qureg::{
     // create_density_qureg,
     // create_qureg,
     set_weighted_qureg,
+    tensor,
     Qureg,
+    QuregKind,
 };
 
 /// Print the Hamiltonian `hamil` to screen.
+///
+/// Does nothing if reporting is disabled with [`QuestEnv::set_reporting()`].
 pub fn report_pauli_hamil(hamil: &PauliHamil) -> Result<(), QuestError> {
+    if !questenv::reporting_enabled() {
+        return Ok(());
+    }
     catch_quest_exception(|| unsafe {
         ffi::reportPauliHamil(hamil.0);
     })
@@ -1,8 +1,21 @@
+use std::{
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{
+        Add,
+        Deref,
+        Mul,
+        Neg,
+        Sub,
+    },
+};
+
 use num::Complex;
 
 use crate::{
     error::catch_quest_exception,
     ffi,
+    Qcomplex,
     Qreal,
     QuestError,
 };
@@ -21,6 +34,104 @@ impl ComplexMatrix2 {
             imag,
         })
     }
+
+    /// Create a new matrix from its complex elements.
+    ///
+    /// The elements are given row by row, i.e. `m[row][col]`.  This is
+    /// equivalent to [`new()`] with the real and imaginary parts of `m`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let (one, i) = (Qcomplex::new(1., 0.), Qcomplex::new(0., 1.));
+    /// let zero = Qcomplex::new(0., 0.);
+    ///
+    /// // Pauli Y
+    /// let mtr = ComplexMatrix2::from_complex([[zero, -i], [i, zero]]);
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.unitary(0, &mtr).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`new()`]: crate::ComplexMatrix2::new()
+    #[must_use]
+    pub fn from_complex(m: [[Qcomplex; 2]; 2]) -> Self {
+        Self::new(
+            m.map(|row| row.map(|z| z.re)),
+            m.map(|row| row.map(|z| z.im)),
+        )
+    }
+}
+
+/// A single-qubit gate depending on a real parameter.
+///
+/// The gate stores a function mapping the parameter to a [`ComplexMatrix2`],
+/// so that the parameter-to-matrix logic is defined once and reused, e.g.
+/// in parameter sweeps.  The matrix is built on the stack for each
+/// parameter, without any allocation.  Apply the gate with
+/// [`Qureg::apply_parametric()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// // Rz(theta) = diag(exp(-i theta/2), exp(i theta/2))
+/// let rz = ParametricGate::new(|theta| {
+///     let zero = Qcomplex::new(0., 0.);
+///     ComplexMatrix2::from_complex([
+///         [Qcomplex::from_polar(1., -theta / 2.), zero],
+///         [zero, Qcomplex::from_polar(1., theta / 2.)],
+///     ])
+/// });
+///
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+/// qureg.init_zero_state();
+/// qureg.unitary(0, &rz.matrix(PI)).unwrap();
+///
+/// let amp = qureg.get_imag_amp(0).unwrap();
+/// assert!((amp + 1.).abs() < EPSILON);
+/// ```
+///
+/// [`Qureg::apply_parametric()`]: crate::Qureg::apply_parametric()
+pub struct ParametricGate {
+    func: Box<dyn Fn(Qreal) -> ComplexMatrix2>,
+}
+
+impl ParametricGate {
+    /// Create a new gate from a function mapping the parameter to a matrix.
+    #[must_use]
+    pub fn new(func: impl Fn(Qreal) -> ComplexMatrix2 + 'static) -> Self {
+        Self {
+            func: Box::new(func),
+        }
+    }
+
+    /// Compute the matrix of the gate for the given parameter.
+    #[must_use]
+    pub fn matrix(
+        &self,
+        param: Qreal,
+    ) -> ComplexMatrix2 {
+        (self.func)(param)
+    }
+}
+
+impl std::fmt::Debug for ParametricGate {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("ParametricGate").finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
@@ -37,6 +148,45 @@ impl ComplexMatrix4 {
             imag,
         })
     }
+
+    /// Create a new matrix from its complex elements.
+    ///
+    /// The elements are given row by row, i.e. `m[row][col]`.  This is
+    /// equivalent to [`new()`] with the real and imaginary parts of `m`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let one = Qcomplex::new(1., 0.);
+    /// let zero = Qcomplex::new(0., 0.);
+    ///
+    /// // SWAP
+    /// let mtr = ComplexMatrix4::from_complex([
+    ///     [one, zero, zero, zero],
+    ///     [zero, zero, one, zero],
+    ///     [zero, one, zero, zero],
+    ///     [zero, zero, zero, one],
+    /// ]);
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(1).unwrap();
+    /// qureg.two_qubit_unitary(0, 1, &mtr).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(2).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`new()`]: crate::ComplexMatrix4::new()
+    #[must_use]
+    pub fn from_complex(m: [[Qcomplex; 4]; 4]) -> Self {
+        Self::new(
+            m.map(|row| row.map(|z| z.re)),
+            m.map(|row| row.map(|z| z.im)),
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -81,6 +231,85 @@ impl ComplexMatrixN {
         self.0.numQubits
     }
 
+    /// Get the dimension of the matrix.
+    ///
+    /// This is the number of rows (and columns) of the matrix, equal to
+    /// `1 << num_qubits()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let mtr = ComplexMatrixN::try_new(3).unwrap();
+    ///
+    /// assert_eq!(mtr.dim(), 8);
+    /// ```
+    #[must_use]
+    pub fn dim(&self) -> usize {
+        1 << self.0.numQubits
+    }
+
+    /// Create a new matrix from its rows.
+    ///
+    /// The number of rows must be a power of two, `2^N` with `N >= 1`, and
+    /// every row must have the same length, equal to the number of rows.
+    /// The resulting matrix acts on `N` qubits.
+    ///
+    /// This complements [`init_complex_matrix_n()`], which takes the real
+    /// and imaginary parts of the matrix elements as separate arrays.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if the number of rows is not a power of two larger than 1
+    ///   - if any of the rows has a length different from the number of rows
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the matrix cannot be allocated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let (one, i) = (Qcomplex::new(1., 0.), Qcomplex::new(0., 1.));
+    /// let zero = Qcomplex::new(0., 0.);
+    ///
+    /// let mtr =
+    ///     ComplexMatrixN::from_rows([vec![one, zero], vec![zero, i]]).unwrap();
+    ///
+    /// assert_eq!(mtr.num_qubits(), 1);
+    /// assert_eq!(mtr.row_imag_as_slice(1), &[0., 1.]);
+    ///
+    /// ComplexMatrixN::from_rows([vec![one, zero], vec![zero]]).unwrap_err();
+    /// ```
+    ///
+    /// [`init_complex_matrix_n()`]: crate::init_complex_matrix_n()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn from_rows(
+        rows: impl IntoIterator<Item = Vec<Qcomplex>>
+    ) -> Result<Self, QuestError> {
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        let dim = rows.len();
+        if dim < 2
+            || !dim.is_power_of_two()
+            || rows.iter().any(|row| row.len() != dim)
+        {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_qubits = i32::try_from(dim.trailing_zeros())
+            .map_err(|_| QuestError::ArrayLengthError)?;
+        let mut mtr = Self::try_new(num_qubits)?;
+        for (i, row) in rows.iter().enumerate() {
+            for (x, elem) in mtr.row_real_as_mut_slice(i).iter_mut().zip(row) {
+                *x = elem.re;
+            }
+            for (x, elem) in mtr.row_imag_as_mut_slice(i).iter_mut().zip(row) {
+                *x = elem.im;
+            }
+        }
+        Ok(mtr)
+    }
+
     /// Get the real part of the `i`th row of the matrix as shared slice.
     ///
     /// # Examples
@@ -285,12 +514,179 @@ impl Drop for ComplexMatrixN {
     }
 }
 
+/// A square complex matrix of any size, backed by arrays owned by the caller.
+///
+/// Unlike [`ComplexMatrixN`], which allocates its elements on the heap, this
+/// matrix only borrows the real and imaginary parts of its elements from two
+/// `DIM x DIM` arrays, e.g. living on the stack.  This avoids a heap
+/// allocation every time a different small matrix is applied in a hot loop.
+/// The rows are bound to the matrix with `QuEST`'s
+/// `bindArraysToStackComplexMatrixN()`.
+///
+/// Call [`as_matrix()`] to use it wherever a [`ComplexMatrixN`] is expected.
+///
+/// # Lifetimes
+///
+/// `QuEST` sees the matrix as an array of pointers to the rows of `re` and
+/// `im`.  These pointers are valid only as long as the arrays are neither
+/// moved nor dropped.  This is enforced by borrowing the arrays for the
+/// lifetime `'a` of the matrix, and the [`ComplexMatrixN`] returned by
+/// [`as_matrix()`] for no longer than the matrix itself.  The returned
+/// [`ComplexMatrixN`] is never passed to `destroyComplexMatrixN()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+///
+/// let re = [[0., 1.], [1., 0.]];
+/// let im = [[0., 0.], [0., 0.]];
+/// let mtr = StackComplexMatrixN::new(&re, &im).unwrap();
+/// assert_eq!(mtr.num_qubits(), 1);
+///
+/// qureg.multi_qubit_unitary(&[1], &mtr.as_matrix()).unwrap();
+///
+/// let amp = qureg.get_real_amp(2).unwrap();
+/// assert!((amp - 1.).abs() < EPSILON);
+/// ```
+///
+/// [`as_matrix()`]: crate::StackComplexMatrixN::as_matrix()
 #[derive(Debug)]
+pub struct StackComplexMatrixN<'a, const DIM: usize> {
+    num_qubits: i32,
+    re_rows:    [*mut Qreal; DIM],
+    im_rows:    [*mut Qreal; DIM],
+    _marker:    PhantomData<&'a [[Qreal; DIM]; DIM]>,
+}
+
+impl<'a, const DIM: usize> StackComplexMatrixN<'a, DIM> {
+    /// Bind the real and imaginary parts of the matrix elements to a new
+    /// matrix.
+    ///
+    /// The element in row `i` and column `j` is `re[i][j] + i im[i][j]`.
+    /// The dimension `DIM` must be a power of two, `2^N` with `N >= 1`.  The
+    /// resulting matrix acts on `N` qubits.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `DIM` is not a power of two larger than 1
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let re = [[0.; 4]; 4];
+    /// let im = [[0.; 4]; 4];
+    /// let mtr = StackComplexMatrixN::new(&re, &im).unwrap();
+    /// assert_eq!(mtr.num_qubits(), 2);
+    ///
+    /// let re = [[0.; 3]; 3];
+    /// let im = [[0.; 3]; 3];
+    /// StackComplexMatrixN::new(&re, &im).unwrap_err();
+    /// ```
+    ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn new(
+        re: &'a [[Qreal; DIM]; DIM],
+        im: &'a [[Qreal; DIM]; DIM],
+    ) -> Result<Self, QuestError> {
+        if DIM < 2 || !DIM.is_power_of_two() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_qubits = i32::try_from(DIM.trailing_zeros())
+            .map_err(|_| QuestError::ArrayLengthError)?;
+        let mut re_rows = [std::ptr::null_mut(); DIM];
+        let mut im_rows = [std::ptr::null_mut(); DIM];
+        let re_storage = re_rows.as_mut_ptr();
+        let im_storage = im_rows.as_mut_ptr();
+        // QuEST only reads the matrix elements through the row pointers.
+        catch_quest_exception(|| unsafe {
+            ffi::bindArraysToStackComplexMatrixN(
+                num_qubits,
+                re.as_ptr().cast_mut().cast(),
+                im.as_ptr().cast_mut().cast(),
+                re_storage,
+                im_storage,
+            );
+        })?;
+        Ok(Self {
+            num_qubits,
+            re_rows,
+            im_rows,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Get number of qubits this operators acts on.
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.num_qubits
+    }
+
+    /// Borrow the matrix as a [`ComplexMatrixN`].
+    ///
+    /// No memory is allocated or copied.  The returned value can be passed
+    /// to any function taking a `&ComplexMatrixN`, e.g.
+    /// [`Qureg::multi_qubit_unitary()`].
+    ///
+    /// [`Qureg::multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
+    #[must_use]
+    pub fn as_matrix(&self) -> impl Deref<Target = ComplexMatrixN> + '_ {
+        BorrowedComplexMatrixN {
+            mtr:     ManuallyDrop::new(ComplexMatrixN(ffi::ComplexMatrixN {
+                numQubits: self.num_qubits,
+                real:      self.re_rows.as_ptr().cast_mut(),
+                imag:      self.im_rows.as_ptr().cast_mut(),
+            })),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A [`ComplexMatrixN`] that does not own its elements.
+struct BorrowedComplexMatrixN<'b> {
+    mtr:     ManuallyDrop<ComplexMatrixN>,
+    _marker: PhantomData<&'b ()>,
+}
+
+impl<'b> Deref for BorrowedComplexMatrixN<'b> {
+    type Target = ComplexMatrixN;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mtr
+    }
+}
+
+/// A vector in three-dimensional real space.
+///
+/// Used e.g. as the rotation axis in [`Qureg::rotate_around_axis()`], or
+/// returned as the Bloch vector by [`Qureg::bloch_vector()`].  Vectors can be
+/// added, subtracted, negated and multiplied by a scalar.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let x = Vector::new(1., 0., 0.);
+/// let y = Vector::new(0., 1., 0.);
+///
+/// assert_eq!(x.cross(&y), Vector::new(0., 0., 1.));
+/// assert_eq!(x.dot(&y), 0.);
+/// assert_eq!(2. * x - y, Vector::new(2., -1., 0.));
+/// ```
+///
+/// [`Qureg::rotate_around_axis()`]: crate::Qureg::rotate_around_axis()
+/// [`Qureg::bloch_vector()`]: crate::Qureg::bloch_vector()
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector(pub(crate) ffi::Vector);
 
 impl Vector {
     #[must_use]
-    pub fn new(
+    pub const fn new(
         x: Qreal,
         y: Qreal,
         z: Qreal,
@@ -301,6 +697,128 @@ impl Vector {
             z,
         })
     }
+
+    /// The `x` component of the vector.
+    #[must_use]
+    pub const fn x(&self) -> Qreal {
+        self.0.x
+    }
+
+    /// The `y` component of the vector.
+    #[must_use]
+    pub const fn y(&self) -> Qreal {
+        self.0.y
+    }
+
+    /// The `z` component of the vector.
+    #[must_use]
+    pub const fn z(&self) -> Qreal {
+        self.0.z
+    }
+
+    /// Dot product of two vectors.
+    #[must_use]
+    pub const fn dot(
+        &self,
+        other: &Self,
+    ) -> Qreal {
+        self.0.x * other.0.x + self.0.y * other.0.y + self.0.z * other.0.z
+    }
+
+    /// Cross product of two vectors.
+    #[must_use]
+    pub const fn cross(
+        &self,
+        other: &Self,
+    ) -> Self {
+        Self::new(
+            self.0.y * other.0.z - self.0.z * other.0.y,
+            self.0.z * other.0.x - self.0.x * other.0.z,
+            self.0.x * other.0.y - self.0.y * other.0.x,
+        )
+    }
+
+    /// Euclidean norm of the vector.
+    #[must_use]
+    pub fn norm(&self) -> Qreal {
+        self.dot(self).sqrt()
+    }
+
+    /// The unit vector in the same direction.
+    ///
+    /// Returns `None` for the zero vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let v = Vector::new(3., 0., 4.).normalized().unwrap();
+    /// assert!((v.norm() - 1.).abs() < EPSILON);
+    /// assert!((v.x() - 0.6).abs() < EPSILON);
+    ///
+    /// assert!(Vector::new(0., 0., 0.).normalized().is_none());
+    /// ```
+    #[must_use]
+    pub fn normalized(&self) -> Option<Self> {
+        let norm = self.norm();
+        if norm > 0. {
+            Some(*self * norm.recip())
+        } else {
+            None
+        }
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self::new(self.0.x + rhs.0.x, self.0.y + rhs.0.y, self.0.z + rhs.0.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self::new(self.0.x - rhs.0.x, self.0.y - rhs.0.y, self.0.z - rhs.0.z)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.0.x, -self.0.y, -self.0.z)
+    }
+}
+
+impl Mul<Qreal> for Vector {
+    type Output = Self;
+
+    fn mul(
+        self,
+        rhs: Qreal,
+    ) -> Self::Output {
+        Self::new(self.0.x * rhs, self.0.y * rhs, self.0.z * rhs)
+    }
+}
+
+impl Mul<Vector> for Qreal {
+    type Output = Vector;
+
+    fn mul(
+        self,
+        rhs: Vector,
+    ) -> Self::Output {
+        rhs * self
+    }
 }
 
 /// Initialises a `ComplexMatrixN` instance to have the passed
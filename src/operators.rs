@@ -1,4 +1,7 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    fmt,
+};
 
 use crate::{
     error::catch_quest_exception,
@@ -13,7 +16,6 @@ use crate::{
     Qureg,
 };
 
-#[derive(Debug)]
 pub struct PauliHamil(pub(crate) ffi::PauliHamil);
 
 impl PauliHamil {
@@ -69,7 +71,187 @@ impl Drop for PauliHamil {
     }
 }
 
+/// Print the size of the Hamiltonian, but not its terms.
+impl fmt::Debug for PauliHamil {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("PauliHamil")
+            .field("num_qubits", &self.0.numQubits)
+            .field("num_sum_terms", &self.0.numSumTerms)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Incremental builder of a [`PauliHamil`].
+///
+/// Terms of the Hamiltonian are added one by one, each as a real coefficient
+/// and a string of Pauli operators: `I`, `X`, `Y` or `Z` (lowercase letters
+/// are also accepted).  The `k`-th character of the string is the operator
+/// acting on the qubit with index `k`, i.e. the leftmost character
+/// corresponds to qubit `0`.  This is the same order as in
+/// [`init_pauli_hamil()`] and in the files read by
+/// [`PauliHamil::try_new_from_file()`].
+///
+/// The terms are validated as they are added, and the first error is
+/// reported by [`build()`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let hamil = PauliHamilBuilder::new(3)
+///     .add_term(0.5, "XZI")
+///     .add_term(-1.2, "IYY")
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// [`build()`]: crate::PauliHamilBuilder::build()
+/// [`init_pauli_hamil()`]: crate::init_pauli_hamil()
+/// [`PauliHamil::try_new_from_file()`]: crate::PauliHamil::try_new_from_file()
 #[derive(Debug)]
+pub struct PauliHamilBuilder {
+    num_qubits: i32,
+    coeffs:     Vec<Qreal>,
+    codes:      Vec<PauliOpType>,
+    error:      Option<QuestError>,
+}
+
+impl PauliHamilBuilder {
+    /// Start building a Hamiltonian acting on `num_qubits` qubits.
+    #[must_use]
+    pub fn new(num_qubits: i32) -> Self {
+        Self {
+            num_qubits,
+            coeffs: Vec::new(),
+            codes: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Add a term to the Hamiltonian.
+    ///
+    /// # Parameters
+    ///
+    /// - `coeff`: the real coefficient of the term
+    /// - `paulis`: a string of Pauli operators, one for each qubit, with the
+    ///   leftmost character corresponding to qubit `0`
+    ///
+    /// If `paulis` is invalid, the term is skipped and the error is returned
+    /// later by [`build()`].
+    ///
+    /// [`build()`]: crate::PauliHamilBuilder::build()
+    #[must_use]
+    pub fn add_term(
+        mut self,
+        coeff: Qreal,
+        paulis: &str,
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        match parse_pauli_string(paulis, self.num_qubits) {
+            Ok(codes) => {
+                self.coeffs.push(coeff);
+                self.codes.extend(codes);
+            }
+            Err(err) => self.error = Some(err),
+        }
+        self
+    }
+
+    /// Allocate and initialize the Hamiltonian.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any of the Pauli strings passed to [`add_term()`] contains an
+    ///     invalid character, or its length is not `num_qubits`
+    ///   - if `num_qubits` is not positive
+    ///   - if no terms have been added
+    ///
+    /// [`add_term()`]: crate::PauliHamilBuilder::add_term()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn build(self) -> Result<PauliHamil, QuestError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let num_sum_terms = i32::try_from(self.coeffs.len())
+            .map_err(|_| QuestError::ArrayLengthError)?;
+        let mut hamil = PauliHamil::try_new(self.num_qubits, num_sum_terms)?;
+        init_pauli_hamil(&mut hamil, &self.coeffs, &self.codes)?;
+        Ok(hamil)
+    }
+}
+
+/// Parse a string of Pauli operators of length `num_qubits`.
+fn parse_pauli_string(
+    paulis: &str,
+    num_qubits: i32,
+) -> Result<Vec<PauliOpType>, QuestError> {
+    let err = |err_msg: String| QuestError::InvalidQuESTInputError {
+        err_msg,
+        err_func: "PauliHamilBuilder::add_term".to_owned(),
+    };
+    let codes = paulis
+        .chars()
+        .map(|c| match c {
+            'I' | 'i' => Ok(PauliOpType::PAULI_I),
+            'X' | 'x' => Ok(PauliOpType::PAULI_X),
+            'Y' | 'y' => Ok(PauliOpType::PAULI_Y),
+            'Z' | 'z' => Ok(PauliOpType::PAULI_Z),
+            _ => Err(err(format!(
+                "Invalid Pauli operator '{c}' in \"{paulis}\". Must be one \
+                 of: I, X, Y, Z."
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if i32::try_from(codes.len()) != Ok(num_qubits) {
+        return Err(err(format!(
+            "Invalid Pauli string \"{paulis}\": expected {num_qubits} \
+             operators, found {}.",
+            codes.len()
+        )));
+    }
+    Ok(codes)
+}
+
+/// Parameters of the Suzuki-Trotter decomposition used by
+/// [`Qureg::evolve()`].
+///
+/// The default configuration is a single repetition of the first-order
+/// decomposition.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let config = TrotterConfig {
+///     order: 2,
+///     ..Default::default()
+/// };
+/// assert_eq!(config.reps, 1);
+/// ```
+///
+/// [`Qureg::evolve()`]: crate::Qureg::evolve()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrotterConfig {
+    /// Order of the decomposition: `1`, or a positive even number
+    pub order: u32,
+    /// Number of repetitions of the decomposition: at least `1`
+    pub reps:  u32,
+}
+
+impl Default for TrotterConfig {
+    fn default() -> Self {
+        Self {
+            order: 1, reps: 1
+        }
+    }
+}
+
 pub struct DiagonalOp<'a> {
     pub(crate) env: &'a QuestEnv,
     pub(crate) op:  ffi::DiagonalOp,
@@ -104,6 +286,22 @@ impl<'a> DiagonalOp<'a> {
             })?,
         })
     }
+
+    /// Return the number of qubits the operator acts upon.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let op = DiagonalOp::try_new(3, env).unwrap();
+    ///
+    /// assert_eq!(op.num_qubits(), 3);
+    /// ```
+    #[must_use]
+    pub fn num_qubits(&self) -> i32 {
+        self.op.numQubits
+    }
 }
 
 impl<'a> Drop for DiagonalOp<'a> {
@@ -115,6 +313,20 @@ impl<'a> Drop for DiagonalOp<'a> {
     }
 }
 
+/// Print the size and distribution of the operator, but not its elements.
+impl<'a> fmt::Debug for DiagonalOp<'a> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("DiagonalOp")
+            .field("num_qubits", &self.op.numQubits)
+            .field("num_elems_per_chunk", &self.op.numElemsPerChunk)
+            .field("num_chunks", &self.op.numChunks)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Initialize [`PauliHamil`](crate::PauliHamil) instance with the given term
 /// coefficients
 ///
@@ -296,6 +508,27 @@ pub fn set_diagonal_op_elems(
 
 /// Apply a diagonal operator to the entire `qureg`.
 ///
+/// If `qureg` is a state-vector `|psi>`, this results in `|psi> -> D |psi>`.
+///
+/// If `qureg` is a density matrix `rho`, this results in `rho -> D rho`, i.e.
+/// the operator is applied by *left-multiplication only*, and not as `D rho
+/// D^dagger`.  The element `rho_{r,c}` is multiplied by `d_r`, so the
+/// off-diagonal elements pick up the phase of `d_r` alone, not the relative
+/// phase `d_r conj(d_c)`.  In particular, the result need not be a valid
+/// density matrix, even if `op` is unitary.  This matches the behaviour of
+/// [`Qureg::apply_matrix_n()`], and `op` need not be unitary.
+///
+/// To apply a *unitary* diagonal operator to a density matrix as `D rho
+/// D^dagger`, use instead [`Qureg::multi_qubit_unitary()`] with the diagonal
+/// matrix, or, if the phases are given by a function of the basis state
+/// index, [`Qureg::apply_phase_func()`] and related methods.
+///
+/// # Errors
+///
+/// - [`InvalidQuESTInputError`],
+///   - if `op` and `qureg` have a different number of qubits
+///   - if `op` and `qureg` are not distributed in the same way
+///
 /// # Examples
 ///
 /// ```rust
@@ -311,6 +544,10 @@ pub fn set_diagonal_op_elems(
 ///
 /// See [QuEST API] for more information.
 ///
+/// [`Qureg::apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+/// [`Qureg::multi_qubit_unitary()`]: crate::Qureg::multi_qubit_unitary()
+/// [`Qureg::apply_phase_func()`]: crate::Qureg::apply_phase_func()
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn apply_diagonal_op(
@@ -0,0 +1,49 @@
+//! The `quest_bind` prelude.
+//!
+//! Re-exports the types and free functions used most often, so that they
+//! can be imported with a single glob:
+//!
+//! ```rust
+//! use quest_bind::prelude::*;
+//!
+//! let env = &QuestEnv::new();
+//! let mut qureg = Qureg::try_new(2, env).unwrap();
+//! qureg.init_plus_state();
+//!
+//! let other = &mut Qureg::try_new(2, env).unwrap();
+//! other.init_zero_state();
+//!
+//! let prod: Qcomplex = calc_inner_product(&qureg, other).unwrap();
+//! assert!((prod.re - 0.5).abs() < EPSILON);
+//! ```
+//!
+//! Everything in this module is also available at the crate root.
+
+pub use crate::{
+    apply_pauli_hamil,
+    apply_pauli_sum,
+    calc_density_inner_product,
+    calc_hilbert_schmidt_distance,
+    calc_inner_product,
+    set_weighted_qureg,
+    tensor,
+    ComplexMatrix2,
+    ComplexMatrix4,
+    ComplexMatrixN,
+    Degrees,
+    DiagonalOp,
+    ParametricGate,
+    PauliHamil,
+    PauliHamilBuilder,
+    PauliOpType,
+    Qcomplex,
+    Qreal,
+    QuestEnv,
+    QuestError,
+    Qureg,
+    QuregKind,
+    Radians,
+    StackComplexMatrixN,
+    Vector,
+    EPSILON,
+};
@@ -0,0 +1,102 @@
+use std::ops::Deref;
+
+use crate::{
+    Qreal,
+    QuestError,
+};
+
+/// A probability, i.e. a real number in `[0, 1]`.
+///
+/// A `Probability` can only be constructed with a value that has been
+/// checked to lie in `[0, 1]`, which makes it useful to validate
+/// user-supplied inputs early.  Methods like [`Qureg::mix_depolarising()`]
+/// accept as probabilities either a `Probability` or a plain [`Qreal`].  Note
+/// that specific methods may further restrict the range of admissible
+/// probabilities.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let prob = Probability::new(0.25).unwrap();
+/// assert_eq!(*prob, 0.25);
+///
+/// Probability::new(1.5).unwrap_err();
+///
+/// let env = QuestEnv::new();
+/// let mut qureg = Qureg::try_new_density(2, &env)
+///     .expect("cannot allocate memory for Qureg");
+/// qureg.init_zero_state();
+///
+/// qureg.mix_depolarising(0, prob).unwrap();
+/// qureg.mix_depolarising(0, 0.25).unwrap();
+/// ```
+///
+/// [`Qureg::mix_depolarising()`]: crate::Qureg::mix_depolarising()
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Probability(Qreal);
+
+impl Probability {
+    /// Create a new probability.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `prob` is not in `[0, 1]` (this includes `NaN`)
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn new(prob: Qreal) -> Result<Self, QuestError> {
+        if (0. ..=1.).contains(&prob) {
+            Ok(Self(prob))
+        } else {
+            Err(QuestError::InvalidQuESTInputError {
+                err_msg:  "Probabilities must be in [0, 1].".to_owned(),
+                err_func: "Probability::new".to_owned(),
+            })
+        }
+    }
+}
+
+impl Deref for Probability {
+    type Target = Qreal;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Probability> for Qreal {
+    fn from(value: Probability) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Qreal> for Probability {
+    type Error = QuestError;
+
+    fn try_from(value: Qreal) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+/// A probability passed to the `mix_*()` methods of [`Qureg`].
+///
+/// This is either a plain [`Qreal`] or a [`Probability`].  Having exactly one
+/// conversion from a float type lets the compiler infer the type of float
+/// literals, like `0.5`, also when `Qreal` is `f32`.
+///
+/// [`Qureg`]: crate::Qureg
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ProbArg(pub(crate) Qreal);
+
+impl From<Qreal> for ProbArg {
+    fn from(value: Qreal) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Probability> for ProbArg {
+    fn from(value: Probability) -> Self {
+        Self(value.0)
+    }
+}
@@ -0,0 +1,624 @@
+//! Run circuits written in `OpenQASM` 2.0.
+//!
+//! Only a subset of the language is supported: register declarations, the
+//! standard gates that have a direct counterpart among the methods of
+//! [`Qureg`], `measure` and `reset`.  Custom gate definitions, classical
+//! control (`if`) and `opaque` declarations are rejected.
+
+use crate::{
+    Qreal,
+    QuestError,
+    Qureg,
+    PI,
+};
+
+/// Parse a program written in `OpenQASM` 2.0 and apply it to `qureg`.
+///
+/// The whole program is parsed and validated before any operation is
+/// applied, so if the program is rejected, `qureg` is left intact.
+///
+/// Quantum registers declared with `qreg` are laid out one after another,
+/// starting from qubit `0` of `qureg`.  Measurement outcomes are discarded:
+/// classical registers are only checked to have been declared.
+///
+/// The following statements are supported:
+///
+/// - `OPENQASM 2.0;`, `include "...";` (ignored), `barrier` (ignored)
+/// - `qreg`, `creg`
+/// - single-qubit gates: `id`, `x`, `y`, `z`, `h`, `s`, `sdg`, `t`, `tdg`,
+///   `rx(θ)`, `ry(θ)`, `rz(θ)`, `u1(λ)`, `p(λ)`, `u2(φ,λ)`, `u3(θ,φ,λ)`,
+///   `U(θ,φ,λ)`
+/// - two-qubit gates: `cx`, `cy`, `cz`, `swap`, `crx(θ)`, `cry(θ)`, `crz(θ)`,
+///   `cu1(λ)`, `cp(λ)`
+/// - three-qubit gates: `ccx`, `cswap`
+/// - `measure`, `reset`
+///
+/// Gate names are case-insensitive, so that the QASM recorded by `QuEST`
+/// (e.g. `Rz(0.5) q[0];` or `U(0.1,0.2,0.3) q[0];`) can be replayed, as long
+/// as it contains only the gates listed above.  Gate parameters can be
+/// arithmetic expressions involving numbers, `pi`, `+`, `-`, `*`, `/` and
+/// parentheses.  Single-qubit gates, `measure` and `reset` can be applied to a
+/// whole register at once, e.g. `h q;`.
+///
+/// # Parameters
+///
+/// - `src`: the source code of the program
+/// - `qureg`: the register to apply the program to
+///
+/// # Errors
+///
+/// - [`InvalidQuESTInputError`],
+///   - if the program contains a syntax error or an unsupported instruction
+///   - if the declared quantum registers need more qubits than `qureg` has
+///   - if an instruction refers to an undeclared register, or a qubit outside
+///     of its register
+///   - if the same qubit is passed twice to a multi-qubit gate
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+/// qureg.init_zero_state();
+///
+/// let src = r#"
+///     OPENQASM 2.0;
+///     include "qelib1.inc";
+///     qreg q[2];
+///     x q[0];
+///     cx q[0],q[1];
+/// "#;
+/// run_qasm(src, &mut qureg).unwrap();
+///
+/// let amp = qureg.get_real_amp(3).unwrap();
+/// assert!((amp - 1.).abs() < EPSILON);
+/// ```
+///
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+pub fn run_qasm(
+    src: &str,
+    qureg: &mut Qureg<'_>,
+) -> Result<(), QuestError> {
+    let ops = Program::new(qureg.num_qubits()).parse(src)?;
+    ops.into_iter().try_for_each(|op| op.apply(qureg))
+}
+
+fn qasm_error(err_msg: String) -> QuestError {
+    QuestError::InvalidQuESTInputError {
+        err_msg,
+        err_func: "run_qasm".to_owned(),
+    }
+}
+
+/// A single operation on the register.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    X(i32),
+    Y(i32),
+    Z(i32),
+    H(i32),
+    S(i32),
+    Sdg(i32),
+    T(i32),
+    Tdg(i32),
+    Rx(i32, Qreal),
+    Ry(i32, Qreal),
+    Rz(i32, Qreal),
+    Phase(i32, Qreal),
+    U3(i32, Qreal, Qreal, Qreal),
+    Cx(i32, i32),
+    Cy(i32, i32),
+    Cz(i32, i32),
+    Swap(i32, i32),
+    Crx(i32, i32, Qreal),
+    Cry(i32, i32, Qreal),
+    Crz(i32, i32, Qreal),
+    Cphase(i32, i32, Qreal),
+    Ccx(i32, i32, i32),
+    Cswap(i32, i32, i32),
+    Measure(i32),
+    Reset(i32),
+}
+
+impl Op {
+    fn new(
+        name: &str,
+        params: &[Qreal],
+        qubits: &[i32],
+    ) -> Option<Self> {
+        Some(match (name, params, qubits) {
+            ("x", [], &[q]) => Self::X(q),
+            ("y", [], &[q]) => Self::Y(q),
+            ("z", [], &[q]) => Self::Z(q),
+            ("h", [], &[q]) => Self::H(q),
+            ("s", [], &[q]) => Self::S(q),
+            ("sdg", [], &[q]) => Self::Sdg(q),
+            ("t", [], &[q]) => Self::T(q),
+            ("tdg", [], &[q]) => Self::Tdg(q),
+            ("rx", &[a], &[q]) => Self::Rx(q, a),
+            ("ry", &[a], &[q]) => Self::Ry(q, a),
+            ("rz", &[a], &[q]) => Self::Rz(q, a),
+            ("u1" | "p", &[a], &[q]) => Self::Phase(q, a),
+            ("u2", &[phi, lambda], &[q]) => Self::U3(q, PI / 2., phi, lambda),
+            ("u3" | "u", &[theta, phi, lambda], &[q]) => {
+                Self::U3(q, theta, phi, lambda)
+            }
+            ("cx", [], &[c, t]) => Self::Cx(c, t),
+            ("cy", [], &[c, t]) => Self::Cy(c, t),
+            ("cz", [], &[c, t]) => Self::Cz(c, t),
+            ("swap", [], &[q1, q2]) => Self::Swap(q1, q2),
+            ("crx", &[a], &[c, t]) => Self::Crx(c, t, a),
+            ("cry", &[a], &[c, t]) => Self::Cry(c, t, a),
+            ("crz", &[a], &[c, t]) => Self::Crz(c, t, a),
+            ("cu1" | "cp", &[a], &[c, t]) => Self::Cphase(c, t, a),
+            ("ccx", [], &[c1, c2, t]) => Self::Ccx(c1, c2, t),
+            ("cswap", [], &[c, q1, q2]) => Self::Cswap(c, q1, q2),
+            _ => return None,
+        })
+    }
+
+    fn apply(
+        self,
+        qureg: &mut Qureg<'_>,
+    ) -> Result<(), QuestError> {
+        match self {
+            Self::X(q) => qureg.pauli_x(q),
+            Self::Y(q) => qureg.pauli_y(q),
+            Self::Z(q) => qureg.pauli_z(q),
+            Self::H(q) => qureg.hadamard(q),
+            Self::S(q) => qureg.s_gate(q),
+            Self::Sdg(q) => qureg.sdg_gate(q),
+            Self::T(q) => qureg.t_gate(q),
+            Self::Tdg(q) => qureg.tdg_gate(q),
+            Self::Rx(q, a) => qureg.rotate_x(q, a),
+            Self::Ry(q, a) => qureg.rotate_y(q, a),
+            Self::Rz(q, a) => qureg.rotate_z(q, a),
+            Self::Phase(q, a) => qureg.phase_shift(q, a),
+            Self::U3(q, theta, phi, lambda) => qureg.u3(q, theta, phi, lambda),
+            Self::Cx(c, t) => qureg.controlled_not(c, t),
+            Self::Cy(c, t) => qureg.controlled_pauli_y(c, t),
+            Self::Cz(c, t) => qureg.controlled_phase_flip(c, t),
+            Self::Swap(q1, q2) => qureg.swap_gate(q1, q2),
+            Self::Crx(c, t, a) => qureg.controlled_rotate_x(c, t, a),
+            Self::Cry(c, t, a) => qureg.controlled_rotate_y(c, t, a),
+            Self::Crz(c, t, a) => qureg.controlled_rotate_z(c, t, a),
+            Self::Cphase(c, t, a) => qureg.controlled_phase_shift(c, t, a),
+            Self::Ccx(c1, c2, t) => qureg.toffoli(c1, c2, t),
+            Self::Cswap(c, q1, q2) => qureg.controlled_swap(c, q1, q2),
+            Self::Measure(q) => qureg.measure(q).map(|_| ()),
+            Self::Reset(q) => qureg.reset_qubit(q),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Register {
+    name:   String,
+    offset: i32,
+    size:   i32,
+}
+
+/// Registers declared so far by the program.
+#[derive(Debug)]
+struct Program {
+    num_qubits: i32,
+    qregs:      Vec<Register>,
+    cregs:      Vec<Register>,
+}
+
+impl Program {
+    fn new(num_qubits: i32) -> Self {
+        Self {
+            num_qubits,
+            qregs: Vec::new(),
+            cregs: Vec::new(),
+        }
+    }
+
+    fn parse(
+        mut self,
+        src: &str,
+    ) -> Result<Vec<Op>, QuestError> {
+        let src = src
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut stmts = src.split(';').map(str::trim).collect::<Vec<_>>();
+        if let Some(last) = stmts.pop() {
+            if !last.is_empty() {
+                return Err(qasm_error(format!(
+                    "Missing `;` at the end of: `{last}`"
+                )));
+            }
+        }
+
+        let mut ops = Vec::new();
+        for stmt in stmts.into_iter().filter(|s| !s.is_empty()) {
+            self.parse_statement(stmt, &mut ops)?;
+        }
+        Ok(ops)
+    }
+
+    fn parse_statement(
+        &mut self,
+        stmt: &str,
+        ops: &mut Vec<Op>,
+    ) -> Result<(), QuestError> {
+        let name_len = stmt
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(stmt.len());
+        let (name, rest) = stmt.split_at(name_len);
+        let rest = rest.trim();
+
+        match name {
+            "OPENQASM" => {
+                if rest != "2.0" {
+                    return Err(qasm_error(format!(
+                        "Unsupported OpenQASM version: `{rest}`. Only version \
+                         2.0 is supported."
+                    )));
+                }
+            }
+            "include" | "barrier" => (),
+            "gate" | "opaque" | "if" => {
+                return Err(qasm_error(format!(
+                    "Unsupported instruction: `{stmt}`"
+                )));
+            }
+            "qreg" => {
+                let (name, size) = parse_declaration(stmt, rest)?;
+                let offset = self.qregs.last().map_or(0, |r| r.offset + r.size);
+                if offset
+                    .checked_add(size)
+                    .filter(|&end| end <= self.num_qubits)
+                    .is_none()
+                {
+                    return Err(qasm_error(format!(
+                        "Not enough qubits in the register for: `{stmt}`"
+                    )));
+                }
+                declare(&mut self.qregs, name, offset, size)?;
+            }
+            "creg" => {
+                let (name, size) = parse_declaration(stmt, rest)?;
+                declare(&mut self.cregs, name, 0, size)?;
+            }
+            "measure" => {
+                let (qubits, bits) =
+                    rest.split_once("->").ok_or_else(|| {
+                        qasm_error(format!("Invalid measurement: `{stmt}`"))
+                    })?;
+                resolve(&self.cregs, bits)?;
+                ops.extend(resolve(&self.qregs, qubits)?.map(Op::Measure));
+            }
+            "reset" => {
+                ops.extend(resolve(&self.qregs, rest)?.map(Op::Reset));
+            }
+            _ => self.parse_gate(stmt, &name.to_lowercase(), rest, ops)?,
+        }
+        Ok(())
+    }
+
+    fn parse_gate(
+        &self,
+        stmt: &str,
+        name: &str,
+        rest: &str,
+        ops: &mut Vec<Op>,
+    ) -> Result<(), QuestError> {
+        let (params, args) = match rest.strip_prefix('(') {
+            Some(rest) => split_at_closing_paren(rest).ok_or_else(|| {
+                qasm_error(format!("Missing `)` in: `{stmt}`"))
+            })?,
+            None => ("", rest),
+        };
+        let params = params
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                eval_expr(p).ok_or_else(|| {
+                    qasm_error(format!("Invalid parameter expression: `{p}`"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let args = args
+            .split(',')
+            .map(|arg| resolve(&self.qregs, arg).map(Iterator::collect))
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+
+        let unsupported =
+            || qasm_error(format!("Unsupported instruction: `{stmt}`"));
+
+        // Apply single-qubit gates to every qubit of the register
+        if let [qubits] = args.as_slice() {
+            if name == "id" && params.is_empty() {
+                return Ok(());
+            }
+            for &q in qubits {
+                ops.push(Op::new(name, &params, &[q]).ok_or_else(unsupported)?);
+            }
+            return Ok(());
+        }
+
+        let qubits = args
+            .iter()
+            .map(|qubits| match qubits.as_slice() {
+                &[q] => Ok(q),
+                _ => Err(qasm_error(format!(
+                    "Register arguments are supported only for single-qubit \
+                     instructions: `{stmt}`"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if (1..qubits.len()).any(|i| qubits[..i].contains(&qubits[i])) {
+            return Err(qasm_error(format!(
+                "Qubit arguments must be unique: `{stmt}`"
+            )));
+        }
+        ops.push(Op::new(name, &params, &qubits).ok_or_else(unsupported)?);
+        Ok(())
+    }
+}
+
+/// Split `s` at the `)` that closes an already opened `(`.
+fn split_at_closing_paren(s: &str) -> Option<(&str, &str)> {
+    let mut depth = 0_usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some((&s[..i], &s[i + 1..])),
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Parse the declaration `name[size]`.
+fn parse_declaration<'a>(
+    stmt: &str,
+    decl: &'a str,
+) -> Result<(&'a str, i32), QuestError> {
+    let invalid = || qasm_error(format!("Invalid declaration: `{stmt}`"));
+    let (name, size) = decl
+        .strip_suffix(']')
+        .and_then(|d| d.split_once('['))
+        .ok_or_else(invalid)?;
+    let size = size.trim().parse::<i32>().map_err(|_| invalid())?;
+    if size < 1 {
+        return Err(invalid());
+    }
+    Ok((name.trim(), size))
+}
+
+fn declare(
+    registers: &mut Vec<Register>,
+    name: &str,
+    offset: i32,
+    size: i32,
+) -> Result<(), QuestError> {
+    if registers.iter().any(|r| r.name == name) {
+        return Err(qasm_error(format!("Register already declared: `{name}`")));
+    }
+    registers.push(Register {
+        name: name.to_owned(),
+        offset,
+        size,
+    });
+    Ok(())
+}
+
+/// Resolve the argument `name[index]`, or `name`, into a range of (qu)bits.
+fn resolve(
+    registers: &[Register],
+    arg: &str,
+) -> Result<std::ops::Range<i32>, QuestError> {
+    let arg = arg.trim();
+    let (name, index) = match arg.strip_suffix(']') {
+        Some(a) => {
+            let (name, index) = a.split_once('[').ok_or_else(|| {
+                qasm_error(format!("Invalid argument: `{arg}`"))
+            })?;
+            (name.trim(), Some(index.trim()))
+        }
+        None => (arg, None),
+    };
+    let reg = registers
+        .iter()
+        .find(|r| r.name == name)
+        .ok_or_else(|| qasm_error(format!("Undeclared register: `{name}`")))?;
+
+    match index {
+        None => Ok(reg.offset..reg.offset + reg.size),
+        Some(index) => match index.parse::<i32>() {
+            Ok(i) if (0..reg.size).contains(&i) => {
+                Ok(reg.offset + i..reg.offset + i + 1)
+            }
+            _ => Err(qasm_error(format!("Index out of range: `{arg}`"))),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(Qreal),
+    Pi,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = i + 1;
+                let mut prev = c;
+                while let Some(&(j, d)) = chars.peek() {
+                    let exp_sign =
+                        (d == '+' || d == '-') && (prev == 'e' || prev == 'E');
+                    if !(d.is_ascii_digit() || ".eE".contains(d) || exp_sign) {
+                        break;
+                    }
+                    end = j + d.len_utf8();
+                    prev = d;
+                    chars.next();
+                }
+                Token::Num(expr[i..end].parse().ok()?)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut end = i + 1;
+                while let Some(&(j, d)) = chars.peek() {
+                    if !d.is_ascii_alphanumeric() {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                match &expr[i..end] {
+                    "pi" => Token::Pi,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// Evaluate an arithmetic expression of numbers and `pi`.
+fn eval_expr(expr: &str) -> Option<Qreal> {
+    let tokens = tokenize(expr)?;
+    let mut pos = 0;
+    let value = eval_sum(&tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(value)
+}
+
+fn eval_sum(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Option<Qreal> {
+    let mut value = eval_product(tokens, pos)?;
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::Plus) => {
+                *pos += 1;
+                value += eval_product(tokens, pos)?;
+            }
+            Some(Token::Minus) => {
+                *pos += 1;
+                value -= eval_product(tokens, pos)?;
+            }
+            _ => return Some(value),
+        }
+    }
+}
+
+fn eval_product(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Option<Qreal> {
+    let mut value = eval_factor(tokens, pos)?;
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::Star) => {
+                *pos += 1;
+                value *= eval_factor(tokens, pos)?;
+            }
+            Some(Token::Slash) => {
+                *pos += 1;
+                value /= eval_factor(tokens, pos)?;
+            }
+            _ => return Some(value),
+        }
+    }
+}
+
+fn eval_factor(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Option<Qreal> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    match token {
+        Token::Num(x) => Some(x),
+        Token::Pi => Some(PI),
+        Token::Plus => eval_factor(tokens, pos),
+        Token::Minus => eval_factor(tokens, pos).map(|x| -x),
+        Token::LParen => {
+            let value = eval_sum(tokens, pos)?;
+            (tokens.get(*pos) == Some(&Token::RParen)).then(|| {
+                *pos += 1;
+                value
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EPSILON;
+
+    #[test]
+    fn eval_expr_01() {
+        let cases: &[(&str, Qreal)] = &[
+            ("0", 0.),
+            ("1.5", 1.5),
+            (".5", 0.5),
+            ("1e-1", 0.1),
+            ("2E+1", 20.),
+            ("pi", PI),
+            ("-pi/2", -PI / 2.),
+            ("pi / 4 + 1", PI / 4. + 1.),
+            ("-(1 - 3) * 2", 4.),
+            ("2*-pi", -2. * PI),
+            ("((1))", 1.),
+        ];
+        for &(expr, expected) in cases {
+            let value = eval_expr(expr).unwrap();
+            assert!((value - expected).abs() < 4. * EPSILON, "{expr}");
+        }
+    }
+
+    #[test]
+    fn split_at_closing_paren_01() {
+        assert_eq!(split_at_closing_paren("pi) q[0]"), Some(("pi", " q[0]")));
+        assert_eq!(
+            split_at_closing_paren("-(pi/2)) q[0]"),
+            Some(("-(pi/2)", " q[0]"))
+        );
+        assert_eq!(
+            split_at_closing_paren("(1), (2)) q"),
+            Some(("(1), (2)", " q"))
+        );
+        assert_eq!(split_at_closing_paren("(pi) q[0]"), None);
+        assert_eq!(split_at_closing_paren("pi q[0]"), None);
+    }
+
+    #[test]
+    fn eval_expr_02() {
+        for expr in ["", "pie", "1 +", "(1", "1)", "2 3", "tau", "1..2", "*2"] {
+            assert!(eval_expr(expr).is_none(), "{expr}");
+        }
+    }
+}
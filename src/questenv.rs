@@ -1,21 +1,91 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Mutex,
+        PoisonError,
+    },
+};
 
 use crate::{
     error::catch_quest_exception,
     ffi,
+    seed_quest,
     QuestError,
+    Qureg,
 };
 
 /// Information about the `QuEST` environment.
 ///
 /// In practice, this holds info about MPI ranks and helps to hide MPI
 /// initialization code.
+///
+/// In distributed mode, all instances of `QuestEnv`, whether alive at the
+/// same time or created one after another, share one MPI world.  MPI is
+/// initialized when the first environment is created and finalized only once,
+/// at process exit.
 #[derive(Debug)]
 pub struct QuestEnv(pub(crate) ffi::QuESTEnv);
 
+/// Copy of the first distributed environment created, without its seeds.
+///
+/// New environments are created from this template, so that MPI is
+/// initialized only once.  The template is destroyed at process exit, which
+/// finalizes MPI.
+static MPI_ENV_TEMPLATE: Mutex<Option<ffi::QuESTEnv>> = Mutex::new(None);
+
+/// Whether the `report_*` functions print to standard output.
+static REPORTING: AtomicBool = AtomicBool::new(true);
+
+/// Check if reporting to standard output is enabled.
+///
+/// See [`QuestEnv::set_reporting()`].
+pub(crate) fn reporting_enabled() -> bool {
+    REPORTING.load(Ordering::Relaxed)
+}
+
+extern "C" fn finalize_mpi_env() {
+    let template = MPI_ENV_TEMPLATE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    if let Some(env) = template {
+        unsafe {
+            ffi::destroyQuESTEnv(env);
+        }
+    }
+}
+
 impl QuestEnv {
     /// Create a new environment.
     ///
+    /// `QuEST` v3.5.0 doesn't offer any parameters to customize the
+    /// environment.  Whether it runs locally, distributed or on a GPU is
+    /// decided at compile time by the feature flags of `quest_bind` (see
+    /// README).  Depending on the mode, this function has the following side
+    /// effects:
+    ///
+    /// - *local* (the default): a single rank is used and there is no
+    ///   distribution.  OpenMP threads are spawned as needed, if OpenMP is
+    ///   available.
+    /// - *distributed* (feature `"mpi"`): when the first environment is
+    ///   created, MPI is initialized with `MPI_Init()`, unless it has already
+    ///   been initialized by the application, in which case `QuEST` prints an
+    ///   error message and reuses the existing MPI world.  The environment
+    ///   spans all ranks in `MPI_COMM_WORLD`, whose number must be a power of
+    ///   2. Subsequent environments share the same MPI world.  MPI is
+    ///   finalized at process exit, and not when the environment is dropped.
+    ///   This function must be called collectively by all ranks.
+    /// - *GPU-accelerated* (feature `"gpu"`): `QuEST` checks if a CUDA-capable
+    ///   device is available and aborts the process if there is none.
+    ///
+    /// In all cases, the random number generator used for measurements is
+    /// seeded with default seeds, based on the current time and the process
+    /// id.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -25,35 +95,181 @@ impl QuestEnv {
     /// ```
     #[must_use]
     pub fn new() -> Self {
+        if cfg!(feature = "mpi") {
+            return Self::new_distributed();
+        }
         Self(unsafe { ffi::createQuESTEnv() })
     }
 
+    fn new_distributed() -> Self {
+        let mut template = MPI_ENV_TEMPLATE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(mut env) = *template {
+            unsafe {
+                ffi::seedQuESTDefault(&mut env);
+            }
+            return Self(env);
+        }
+
+        let env = unsafe { ffi::createQuESTEnv() };
+        *template = Some(env.without_seeds());
+        let status = unsafe { ffi::atexit(finalize_mpi_env) };
+        assert_eq!(status, 0, "cannot register MPI finalization at exit");
+        Self(env)
+    }
+
+    /// Create a new environment with the random number generator seeded with
+    /// `seeds`.
+    ///
+    /// This is the same as calling [`QuestEnv::new()`] followed by
+    /// [`seed_quest()`], and is meant as a single entry point to reproducible
+    /// simulations: given the same seeds, the same sequence of calls to
+    /// random functions like [`measure()`] or [`measure_with_stats()`]
+    /// yields the same outcomes.
+    ///
+    /// Note that `QuEST` v3.5.0 keeps one random number generator per process,
+    /// which is shared by all environments and registers.  Creating or
+    /// seeding another environment reseeds the generator for all of them,
+    /// and measurements performed concurrently from other threads change the
+    /// sequence of random numbers.  In distributed mode, the seeds of the
+    /// master node are used by every node.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic, if `seeds` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let measure_all = |seeds: &[u64]| {
+    ///     let env = QuestEnv::new_seeded(seeds);
+    ///     let mut qureg =
+    ///         Qureg::try_new(8, &env).expect("cannot allocate memory for Qureg");
+    ///     qureg.init_plus_state();
+    ///     (0..8)
+    ///         .map(|i| qureg.measure(i).unwrap())
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// assert_eq!(measure_all(&[1, 2, 3]), measure_all(&[1, 2, 3]));
+    /// ```
+    ///
+    /// [`QuestEnv::new()`]: crate::QuestEnv::new()
+    /// [`seed_quest()`]: crate::seed_quest()
+    /// [`measure()`]: crate::Qureg::measure()
+    /// [`measure_with_stats()`]: crate::Qureg::measure_with_stats()
+    #[must_use]
+    pub fn new_seeded(seeds: &[u64]) -> Self {
+        assert!(!seeds.is_empty(), "seeds must not be empty");
+        let mut env = Self::new();
+        seed_quest(&mut env, seeds);
+        env
+    }
+
     /// Sync environment in distributed mode.
     ///
     /// Guarantees that all code up to the given point has been executed on all
     /// nodes (if running in distributed mode).
     ///
-    ///  # Examples
+    /// This is a collective barrier across all MPI ranks: every rank must call
+    /// it, or the program will deadlock.  Use it as a synchronization point
+    /// when mixing `quest_bind` with custom MPI code, e.g. before reducing
+    /// amplitudes stored locally on each node.  To also combine a success
+    /// status across the ranks, see [`Qureg::sync_quest_success()`].
+    ///
+    /// In local and GPU-accelerated modes, this function waits for all
+    /// pending GPU work to finish (if any), and otherwise does nothing.
+    ///
+    /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// env.sync();
     /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`Qureg::sync_quest_success()`]: crate::Qureg::sync_quest_success()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn sync(&self) {
         unsafe {
             ffi::syncQuESTEnv(self.0);
         }
     }
 
+    /// Enable or disable printing reports to standard output.
+    ///
+    /// `QuEST` v3.5.0 has no verbosity setting, and its report functions
+    /// always print to standard output.  This switch is global: it affects
+    /// all environments and registers in the process.  When reporting is
+    /// disabled:
+    ///
+    /// - [`report_quest_env()`] and [`Qureg::report_qureg_params()`] send their
+    ///   information to the [log](https://crates.io/crates/log) crate at the
+    ///   `info` level instead,
+    /// - [`Qureg::report_state_to_screen()`] and [`report_pauli_hamil()`] do
+    ///   nothing.
+    ///
+    /// [`Qureg::report_state()`] writes to files and is not affected.
+    /// Messages that `QuEST` prints on its own, e.g. when MPI has already been
+    /// initialized, cannot be suppressed.
+    ///
+    /// Reporting is enabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    ///
+    /// env.set_reporting(false);
+    /// assert!(!env.is_reporting());
+    /// env.report_quest_env(); // prints nothing
+    ///
+    /// env.set_reporting(true);
+    /// ```
+    ///
+    /// [`report_quest_env()`]: crate::QuestEnv::report_quest_env()
+    /// [`Qureg::report_qureg_params()`]: crate::Qureg::report_qureg_params()
+    /// [`Qureg::report_state_to_screen()`]: crate::Qureg::report_state_to_screen()
+    /// [`Qureg::report_state()`]: crate::Qureg::report_state()
+    /// [`report_pauli_hamil()`]: crate::report_pauli_hamil()
+    pub fn set_reporting(
+        &self,
+        enabled: bool,
+    ) {
+        REPORTING.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check if printing reports to standard output is enabled.
+    ///
+    /// See [`set_reporting()`] for more information.
+    ///
+    /// [`set_reporting()`]: crate::QuestEnv::set_reporting()
+    #[must_use]
+    pub fn is_reporting(&self) -> bool {
+        reporting_enabled()
+    }
+
     /// Report information about the `QuEST` environment.
     ///
-    /// The information if printed to standard output.
+    /// The information if printed to standard output, or logged if reporting
+    /// is disabled with [`set_reporting()`].
     ///
     /// See [QuEST API][quest-api] for more information.
     ///
+    /// [`set_reporting()`]: crate::QuestEnv::set_reporting()
     /// [quest-api]: https://quest-kit.github.io/QuEST/modules.html
     pub fn report_quest_env(&self) {
+        if !reporting_enabled() {
+            if let Ok(env_str) = self.get_environment_string() {
+                log::info!("QuEST environment: {env_str}");
+            }
+            return;
+        }
         catch_quest_exception(|| unsafe {
             ffi::reportQuESTEnv(self.0);
         })
@@ -94,6 +310,99 @@ impl QuestEnv {
         })
         .expect("get_environment_string should always succeed")
     }
+
+    /// Get the number of threads available to `QuEST`.
+    ///
+    /// In multithreaded mode, `QuEST` uses OpenMP and this is the maximal
+    /// number of OpenMP threads observed by `QuEST`.  It can be controlled by
+    /// setting the environment variable `OMP_NUM_THREADS` before the program
+    /// starts.  If OpenMP is not enabled (e.g. in GPU-accelerated mode), this
+    /// function returns `1`.
+    ///
+    /// The value is read from the string returned by
+    /// [`get_environment_string()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let num_threads = env.num_threads();
+    ///
+    /// assert!(num_threads >= 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the environment string reported by
+    /// `QuEST` is malformed.
+    ///
+    /// [`get_environment_string()`]: crate::QuestEnv::get_environment_string()
+    #[must_use]
+    pub fn num_threads(&self) -> i32 {
+        self.get_environment_string()
+            .expect("get_environment_string should always succeed")
+            .split_whitespace()
+            .find_map(|s| s.strip_prefix("threads="))
+            .and_then(|s| s.parse().ok())
+            .expect("environment string should contain the number of threads")
+    }
+
+    /// Create a new state-vector in this environment.
+    ///
+    /// This is the same as [`Qureg::try_new()`], called as a method of the
+    /// environment, e.g. `env.make_qureg(5)`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Qureg::try_new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg = env.make_qureg(2).unwrap();
+    ///
+    /// assert_eq!(qureg.num_qubits(), 2);
+    /// assert!(!qureg.is_density_matrix());
+    /// ```
+    ///
+    /// [`Qureg::try_new()`]: crate::Qureg::try_new()
+    pub fn make_qureg(
+        &self,
+        num_qubits: i32,
+    ) -> Result<Qureg<'_>, QuestError> {
+        Qureg::try_new(num_qubits, self)
+    }
+
+    /// Create a new density matrix in this environment.
+    ///
+    /// This is the same as [`Qureg::try_new_density()`], called as a method
+    /// of the environment, e.g. `env.make_density_qureg(5)`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Qureg::try_new_density()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg = env.make_density_qureg(2).unwrap();
+    ///
+    /// assert_eq!(qureg.num_qubits(), 2);
+    /// assert!(qureg.is_density_matrix());
+    /// ```
+    ///
+    /// [`Qureg::try_new_density()`]: crate::Qureg::try_new_density()
+    pub fn make_density_qureg(
+        &self,
+        num_qubits: i32,
+    ) -> Result<Qureg<'_>, QuestError> {
+        Qureg::try_new_density(num_qubits, self)
+    }
 }
 
 impl Default for QuestEnv {
@@ -104,6 +413,13 @@ impl Default for QuestEnv {
 
 impl Drop for QuestEnv {
     fn drop(&mut self) {
+        if cfg!(feature = "mpi") {
+            // MPI is finalized at process exit, see: `QuestEnv::new()`
+            unsafe {
+                self.0.free_seeds();
+            }
+            return;
+        }
         catch_quest_exception(|| unsafe { ffi::destroyQuESTEnv(self.0) })
             .expect("dropping QuestEnv should always succeed");
     }
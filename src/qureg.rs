@@ -1,12 +1,21 @@
-use std::ffi::CString;
+use std::{
+    ffi::CString,
+    fmt,
+    panic::UnwindSafe,
+};
 
 use super::{
+    apply_diagonal_op,
     catch_quest_exception,
     ffi,
+    init_diagonal_op,
     BitEncoding,
     ComplexMatrix2,
     ComplexMatrix4,
     ComplexMatrixN,
+    DiagonalOp,
+    Distribution,
+    ParametricGate,
     PauliHamil,
     PauliOpType,
     PhaseFunc,
@@ -14,10 +23,50 @@ use super::{
     Qreal,
     QuestEnv,
     QuestError,
+    Radians,
+    TrotterConfig,
     Vector,
+    EPSILON,
+    PI,
+};
+use crate::{
+    probability::ProbArg,
+    questenv::reporting_enabled,
 };
 
-#[derive(Debug)]
+/// The kind of quantum register: a state-vector or a density matrix.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let qureg = Qureg::try_new_density(2, &env)
+///     .expect("cannot allocate memory for Qureg");
+///
+/// match qureg.kind() {
+///     QuregKind::StateVector => unreachable!(),
+///     QuregKind::DensityMatrix => println!("{}", qureg.kind()),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuregKind {
+    StateVector,
+    DensityMatrix,
+}
+
+impl fmt::Display for QuregKind {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::StateVector => write!(f, "StateVector"),
+            Self::DensityMatrix => write!(f, "DensityMatrix"),
+        }
+    }
+}
+
 pub struct Qureg<'a> {
     pub(crate) env: &'a QuestEnv,
     pub(crate) reg: ffi::Qureg,
@@ -39,14 +88,24 @@ impl<'a> Qureg<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`QuestError::InvalidQuESTInputError`](crate::QuestError::InvalidQuESTInputError)
-    /// on failure.  This is an exception thrown by `QuEST`.
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `num_qubits` is not positive
+    /// - [`AllocationError`],
+    ///   - if the number of amplitudes, `2^num_qubits`, or the size of the
+    ///     register in bytes overflows the platform's address space
+    ///
+    /// Only this overflow is checked.  A register that fits in the address
+    /// space, but not in the available memory, is still passed to `QuEST`,
+    /// which exits the process if the allocation fails.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`AllocationError`]: crate::QuestError::AllocationError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn try_new(
         num_qubits: i32,
         env: &'a QuestEnv,
     ) -> Result<Self, QuestError> {
+        validate_alloc_size(num_qubits, QuregKind::StateVector, "try_new")?;
         Ok(Self {
             env,
             reg: catch_quest_exception(|| unsafe {
@@ -70,14 +129,28 @@ impl<'a> Qureg<'a> {
     ///
     /// # Errors
     ///
-    /// Returns [`QuestError::InvalidQuESTInputError`](crate::QuestError::InvalidQuESTInputError)
-    /// on failure.  This is an exception thrown by `QuEST`.
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `num_qubits` is not positive
+    /// - [`AllocationError`],
+    ///   - if the number of amplitudes, `2^(2 * num_qubits)`, or the size of
+    ///     the register in bytes overflows the platform's address space
+    ///
+    /// Only this overflow is checked.  A register that fits in the address
+    /// space, but not in the available memory, is still passed to `QuEST`,
+    /// which exits the process if the allocation fails.
     ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`AllocationError`]: crate::QuestError::AllocationError
     /// [Quest API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn try_new_density(
         num_qubits: i32,
         env: &'a QuestEnv,
     ) -> Result<Self, QuestError> {
+        validate_alloc_size(
+            num_qubits,
+            QuregKind::DensityMatrix,
+            "try_new_density",
+        )?;
         Ok(Self {
             env,
             reg: catch_quest_exception(|| unsafe {
@@ -86,11 +159,352 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Creates a density matrix in the pure state given by a state-vector.
+    ///
+    /// Allocates a density matrix with the same number of qubits as `pure`,
+    /// and initializes it to `|pure><pure|`.  See also
+    /// [`init_pure_state()`].
+    ///
+    /// The decoherence channels, like [`mix_dephasing()`] or
+    /// [`mix_damping()`], and the Kraus maps act only on density matrices.
+    /// This is the intended way to apply noise to a state prepared as a
+    /// state-vector: promote it first, then mix the returned density matrix.
+    /// [`to_density_matrix()`] is a method form of this constructor.
+    ///
+    /// # Parameters
+    ///
+    /// - `pure`: a state-vector containing the pure state
+    /// - `env`: the environment of the new register
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `pure` is a density matrix
+    /// - [`AllocationError`],
+    ///   - if the size of the density matrix overflows the address space
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut pure =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// pure.init_plus_state();
+    ///
+    /// let qureg = Qureg::density_from_pure(&pure, &env).unwrap();
+    ///
+    /// assert!(qureg.is_density_matrix());
+    /// assert_eq!(qureg.num_qubits(), 2);
+    /// assert!((qureg.calc_purity().unwrap() - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`init_pure_state()`]: crate::Qureg::init_pure_state()
+    /// [`mix_dephasing()`]: crate::Qureg::mix_dephasing()
+    /// [`mix_damping()`]: crate::Qureg::mix_damping()
+    /// [`to_density_matrix()`]: crate::Qureg::to_density_matrix()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`AllocationError`]: crate::QuestError::AllocationError
+    pub fn density_from_pure(
+        pure: &Qureg<'_>,
+        env: &'a QuestEnv,
+    ) -> Result<Self, QuestError> {
+        validate_kind(pure, QuregKind::StateVector, "density_from_pure")?;
+        let mut qureg = Self::try_new_density(pure.num_qubits(), env)?;
+        qureg.init_pure_state(pure)?;
+        Ok(qureg)
+    }
+
+    /// Promote a state-vector to a density matrix.
+    ///
+    /// This is a method form of [`density_from_pure()`], which is the
+    /// canonical constructor: `qureg.to_density_matrix(env)` is equivalent to
+    /// `Qureg::density_from_pure(&qureg, env)`.  The state-vector itself is
+    /// not modified.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`density_from_pure()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let mut rho = qureg.to_density_matrix(&env).unwrap();
+    /// rho.mix_dephasing(0, 0.5).unwrap();
+    ///
+    /// // the coherences are gone
+    /// let amp = rho.get_density_amp(0, 1).unwrap();
+    /// assert!(amp.norm() < EPSILON);
+    /// ```
+    ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
+    pub fn to_density_matrix<'b>(
+        &self,
+        env: &'b QuestEnv,
+    ) -> Result<Qureg<'b>, QuestError> {
+        Qureg::density_from_pure(self, env)
+    }
+
+    /// Creates a state-vector with the amplitudes read from a file.
+    ///
+    /// The number of qubits of the new register is determined from the
+    /// number of amplitudes in the file, which must be a power of 2.  The
+    /// amplitudes are then loaded with [`init_state_from_file()`], where the
+    /// file format is described.
+    ///
+    /// # Parameters
+    ///
+    /// - `filename`: path to the file
+    /// - `env`: a reference to the [`QuestEnv`] runtime environment
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the file cannot be read
+    ///   - if the number of amplitudes in the file is not a power of 2
+    /// - [`NulError`],
+    ///   - if `filename` contains a nul byte
+    /// - [`AllocationError`],
+    ///   - if the size of the register overflows the address space
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg = Qureg::from_quest_file("state.csv", &env).unwrap();
+    /// ```
+    ///
+    /// [`init_state_from_file()`]: crate::Qureg::init_state_from_file()
+    /// [`QuestEnv`]: crate::QuestEnv
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`NulError`]: crate::QuestError::NulError
+    /// [`AllocationError`]: crate::QuestError::AllocationError
+    pub fn from_quest_file(
+        filename: &str,
+        env: &'a QuestEnv,
+    ) -> Result<Self, QuestError> {
+        let contents = std::fs::read_to_string(filename).map_err(|_| {
+            invalid_input_error(
+                &format!("Could not open file ({filename})."),
+                "from_quest_file",
+            )
+        })?;
+        let num_amps = contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .count();
+        if !num_amps.is_power_of_two() || num_amps < 2 {
+            return Err(invalid_input_error(
+                "The number of amplitudes in the file must be a power of 2, \
+                 and at least 2.",
+                "from_quest_file",
+            ));
+        }
+        let num_qubits = checked_len(num_amps.trailing_zeros() as usize)?;
+        let mut qureg = Self::try_new(num_qubits, env)?;
+        qureg.init_state_from_file(filename)?;
+        Ok(qureg)
+    }
+
+    /// Creates a state-vector with the amplitudes read from `r` in a binary
+    /// format.
+    ///
+    /// This is the counterpart of [`write_amps_chunked()`], where the binary
+    /// format is described.  The amplitudes are read and loaded into the new
+    /// register with [`set_amps()`] in chunks, so that the whole state never
+    /// needs to reside in a single host buffer.  All `16 * 2^num_qubits`
+    /// bytes must be present in `r`, and nothing more.
+    ///
+    /// In distributed mode, every node must read the same data.  This
+    /// function must be called collectively by all nodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `r`: the reader to read the amplitudes from
+    /// - `num_qubits`: number of qubits in the new register
+    /// - `env`: a reference to the [`QuestEnv`] runtime environment
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `r` contains fewer or more than `16 * 2^num_qubits` bytes
+    /// - [`IoError`],
+    ///   - if reading from `r` fails
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `num_qubits` is not positive
+    /// - [`AllocationError`],
+    ///   - if the size of the register overflows the address space
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let mut bytes = Vec::new();
+    /// qureg.write_amps_chunked(&mut bytes, 4).unwrap();
+    ///
+    /// let copy = Qureg::read_amps_binary(bytes.as_slice(), 3, &env).unwrap();
+    /// let amp = copy.get_real_amp(5).unwrap();
+    /// assert!((amp - 8_f64.sqrt().recip() as Qreal).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`write_amps_chunked()`]: crate::Qureg::write_amps_chunked()
+    /// [`set_amps()`]: crate::Qureg::set_amps()
+    /// [`QuestEnv`]: crate::QuestEnv
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`IoError`]: crate::QuestError::IoError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`AllocationError`]: crate::QuestError::AllocationError
+    pub fn read_amps_binary<R: std::io::Read>(
+        mut r: R,
+        num_qubits: i32,
+        env: &'a QuestEnv,
+    ) -> Result<Self, QuestError> {
+        const CHUNK: i64 = 1 << 14;
+
+        let read_err = |err: std::io::Error| match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => QuestError::ArrayLengthError,
+            kind => QuestError::IoError(kind),
+        };
+
+        let mut qureg = Self::try_new(num_qubits, env)?;
+        let num_amps = qureg.num_amps_total();
+        let chunk = CHUNK.min(num_amps);
+        let mut buf = vec![0_u8; chunk as usize * 16];
+        let mut reals = Vec::with_capacity(chunk as usize);
+        let mut imags = Vec::with_capacity(chunk as usize);
+        let mut start = 0;
+        while start < num_amps {
+            let len = (num_amps - start).min(chunk) as usize;
+            let bytes = &mut buf[..len * 16];
+            r.read_exact(bytes).map_err(read_err)?;
+            reals.clear();
+            imags.clear();
+            for pair in bytes.chunks_exact(16) {
+                let (re, im) = pair.split_at(8);
+                reals.push(f64::from_le_bytes(re.try_into().unwrap()) as Qreal);
+                imags.push(f64::from_le_bytes(im.try_into().unwrap()) as Qreal);
+            }
+            qureg.set_amps(start, &reals, &imags)?;
+            start += len as i64;
+        }
+
+        // The input must end exactly after the last amplitude.
+        if r.read(&mut [0_u8; 1]).map_err(read_err)? != 0 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        Ok(qureg)
+    }
+
+    /// Wrap a raw `QuEST` register.
+    ///
+    /// This is an escape hatch for interoperability with C code, or
+    /// hand-written FFI calls, that already hold a `QuEST` register.  The
+    /// returned `Qureg` takes ownership of `reg`, and destroys it with
+    /// `destroyQureg()` when dropped.  See [`into_raw()`] for the opposite
+    /// conversion.
+    ///
+    /// # Safety
+    ///
+    /// - `reg` must have been created by `createQureg()`,
+    ///   `createDensityQureg()` or `createCloneQureg()` of `libQuEST` v3.5.0,
+    ///   with the same precision as `quest_bind` (see the feature `"f32"`), and
+    ///   must not have been destroyed.
+    /// - `reg` must have been created in the same process and with an
+    ///   environment equivalent to `env`, i.e. in the same mode (local,
+    ///   distributed or GPU-accelerated) and, in distributed mode, in the same
+    ///   MPI world.
+    /// - Ownership of the register is transferred: no other code may destroy
+    ///   `reg`, nor access its amplitudes while the returned `Qureg` is alive,
+    ///   and `reg` must not be passed to `from_raw()` again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let reg: RawQureg = qureg.into_raw();
+    /// // ... pass `reg` to C code ...
+    /// let qureg = unsafe { Qureg::from_raw(reg, &env) };
+    ///
+    /// assert_eq!(qureg.num_qubits(), 2);
+    /// ```
+    ///
+    /// [`into_raw()`]: crate::Qureg::into_raw()
+    #[must_use]
+    pub unsafe fn from_raw(
+        reg: ffi::Qureg,
+        env: &'a QuestEnv,
+    ) -> Self {
+        Self {
+            env,
+            reg,
+        }
+    }
+
+    /// Release the raw `QuEST` register, without destroying it.
+    ///
+    /// The register is *leaked*: after this call, `quest_bind` no longer owns
+    /// the memory of the register, and the caller is responsible for
+    /// destroying it exactly once, either by passing it to `destroyQureg()`
+    /// of `libQuEST` together with the environment the register was created
+    /// in, or by wrapping it again with [`from_raw()`] and dropping the
+    /// result.  The environment must outlive the register.
+    ///
+    /// # Examples
+    ///
+    /// See [`from_raw()`].
+    ///
+    /// [`from_raw()`]: crate::Qureg::from_raw()
+    #[must_use]
+    pub fn into_raw(self) -> ffi::Qureg {
+        std::mem::ManuallyDrop::new(self).reg
+    }
+
     #[must_use]
     pub fn is_density_matrix(&self) -> bool {
         self.reg.isDensityMatrix != 0
     }
 
+    /// Returns the kind of the register.
+    ///
+    /// This is a typed counterpart of [`is_density_matrix()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// assert_eq!(qureg.kind(), QuregKind::StateVector);
+    /// ```
+    ///
+    /// [`is_density_matrix()`]: crate::Qureg::is_density_matrix()
+    #[must_use]
+    pub fn kind(&self) -> QuregKind {
+        if self.is_density_matrix() {
+            QuregKind::DensityMatrix
+        } else {
+            QuregKind::StateVector
+        }
+    }
+
     /// Print the current state vector of probability amplitudes to file.
     ///
     /// ## File format:
@@ -129,13 +543,20 @@ impl<'a> Qureg<'a> {
     /// qubits to standard out. For debugging purposes. Each rank should
     /// print output serially.  Only print output for systems <= 5 qubits.
     ///
+    /// Does nothing if reporting is disabled with
+    /// [`QuestEnv::set_reporting()`].
+    ///
     /// See [QuEST API] for more information.
     ///
+    /// [`QuestEnv::set_reporting()`]: crate::QuestEnv::set_reporting()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn report_state_to_screen(
         &self,
         report_rank: i32,
     ) {
+        if !reporting_enabled() {
+            return;
+        }
         catch_quest_exception(|| unsafe {
             ffi::reportStateToScreen(self.reg, self.env.0, report_rank);
         })
@@ -163,6 +584,45 @@ impl<'a> Qureg<'a> {
         self.reg.numQubitsRepresented
     }
 
+    /// Convert a `usize` into a qubit index of the register.
+    ///
+    /// All methods of `Qureg` take qubit indices as `i32`.  This function is
+    /// a checked alternative to casting `index as i32`, which would silently
+    /// truncate large values.
+    ///
+    /// # Errors
+    ///
+    /// - [`QubitIndexError`],
+    ///   - if `index` is outside [0, [`num_qubits()`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// for i in 0..3_usize {
+    ///     qureg.hadamard(qureg.qubit_index(i).unwrap()).unwrap();
+    /// }
+    ///
+    /// qureg.qubit_index(3).unwrap_err();
+    /// qureg.qubit_index(usize::MAX).unwrap_err();
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    pub fn qubit_index(
+        &self,
+        index: usize,
+    ) -> Result<i32, QuestError> {
+        i32::try_from(index)
+            .ok()
+            .filter(|&qubit| qubit < self.num_qubits())
+            .ok_or(QuestError::QubitIndexError)
+    }
+
     /// Return the total number of amplitudes in the register.
     ///
     /// - If `Qureg` is a state-vector, this is equal to: `2^N`, where `N` is
@@ -189,76 +649,182 @@ impl<'a> Qureg<'a> {
         self.reg.numAmpsTotal
     }
 
-    /// Report information about a set of qubits.
+    /// Return the number of rows (and columns) of a density matrix.
     ///
-    /// This function prints to stdout: number of qubits, number of probability
-    /// amplitudes.
+    /// - If `Qureg` is a density matrix, this is `Some(2^N)`, where `N` is the
+    ///   number of qubits in the register: [`num_qubits()`].  This is the bound
+    ///   of the `row` and `col` indices accepted by [`get_density_amp()`], and
+    ///   [`num_amps_total()`] is its square.
+    /// - If `Qureg` is a state-vector, this is `None`.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// assert_eq!(qureg.density_dim(), Some(8));
     ///
-    /// qureg.report_qureg_params();
+    /// let qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// assert_eq!(qureg.density_dim(), None);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    pub fn report_qureg_params(&self) {
-        catch_quest_exception(|| unsafe {
-            ffi::reportQuregParams(self.reg);
-        })
-        .expect("report_qureg_params should never fail");
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`get_density_amp()`]: crate::Qureg::get_density_amp()
+    #[must_use]
+    pub fn density_dim(&self) -> Option<i64> {
+        self.is_density_matrix().then(|| 1_i64 << self.num_qubits())
     }
 
-    /// Initializes a `Qureg` to have all-zero-amplitudes.
+    /// Range of global amplitude indices stored on the current node.
     ///
-    /// This is an unphysical state, useful for iteratively building a state
-    /// with functions like
-    /// [`set_weighted_qureg()`][api-set-weighted-qureg], and should
-    /// not be confused with [`init_zero_state()`][api-init-zero-state].
+    /// In distributed mode, the amplitudes of the register are split evenly
+    /// into consecutive chunks, one per node.  This returns the range of
+    /// indices (into the flattened array of [`num_amps_total()`] amplitudes)
+    /// of the chunk owned by the current node.  The ranges of all nodes
+    /// partition `0..num_amps_total()`.  In local mode, this is the whole
+    /// range.
+    ///
+    /// This is useful e.g. for writing correct distributed calls to
+    /// [`set_amps()`].
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
     ///
-    /// qureg.init_blank_state();
+    /// let range = qureg.local_index_range();
+    /// assert!(range.start >= 0 && range.end <= qureg.num_amps_total());
     ///
-    /// assert!(qureg.get_prob_amp(0).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
-    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
+    /// for index in range {
+    ///     let _ = qureg.get_amp(index).unwrap();
+    /// }
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [api-set-weighted-qureg]: crate::set_weighted_qureg()
-    /// [api-init-zero-state]: crate::Qureg::init_zero_state()
-    /// [api-qureg]: crate::Qureg
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn init_blank_state(&mut self) {
-        catch_quest_exception(|| unsafe {
-            ffi::initBlankState(self.reg);
-        })
-        .expect("init_blank_state should always succeed");
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`set_amps()`]: crate::Qureg::set_amps()
+    #[must_use]
+    pub fn local_index_range(&self) -> std::ops::Range<i64> {
+        chunk_index_range(&self.reg)
     }
 
-    /// Initialize `qureg` into the zero state.
+    /// Check if the amplitude at `global_index` is stored on the current node.
     ///
-    /// If `qureg` is a state-vector of `N` qubits, it is modified to state
-    /// `|0>^{\otimes N}`.  If `qureg` is a density matrix of `N` qubits, it is
-    /// modified to state `|0><0|^{\otimes N}`.
+    /// See [`local_index_range()`].
     ///
-    /// # Parameters
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let start = qureg.local_index_range().start;
+    /// assert!(qureg.owns_index(start));
+    /// assert!(!qureg.owns_index(-1));
+    /// assert!(!qureg.owns_index(8));
+    /// ```
+    ///
+    /// [`local_index_range()`]: crate::Qureg::local_index_range()
+    #[must_use]
+    pub fn owns_index(
+        &self,
+        global_index: i64,
+    ) -> bool {
+        self.local_index_range().contains(&global_index)
+    }
+
+    /// Report information about a set of qubits.
+    ///
+    /// This function prints to stdout: number of qubits, number of probability
+    /// amplitudes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.report_qureg_params();
+    /// ```
+    ///
+    /// If reporting is disabled with [`QuestEnv::set_reporting()`], the
+    /// parameters are logged instead.
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`QuestEnv::set_reporting()`]: crate::QuestEnv::set_reporting()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    pub fn report_qureg_params(&self) {
+        if !reporting_enabled() {
+            log::info!(
+                "Number of qubits is {}. Number of amps is {}. Number of amps \
+                 per rank is {}.",
+                self.num_qubits(),
+                self.num_amps_total(),
+                self.reg.numAmpsPerChunk
+            );
+            return;
+        }
+        catch_quest_exception(|| unsafe {
+            ffi::reportQuregParams(self.reg);
+        })
+        .expect("report_qureg_params should never fail");
+    }
+
+    /// Initializes a `Qureg` to have all-zero-amplitudes.
+    ///
+    /// This is an unphysical state, useful for iteratively building a state
+    /// with functions like
+    /// [`set_weighted_qureg()`][api-set-weighted-qureg], and should
+    /// not be confused with [`init_zero_state()`][api-init-zero-state].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_blank_state();
+    ///
+    /// assert!(qureg.get_prob_amp(0).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(1).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(2).unwrap().abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(3).unwrap().abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [api-set-weighted-qureg]: crate::set_weighted_qureg()
+    /// [api-init-zero-state]: crate::Qureg::init_zero_state()
+    /// [api-qureg]: crate::Qureg
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn init_blank_state(&mut self) {
+        catch_quest_exception(|| unsafe {
+            ffi::initBlankState(self.reg);
+        })
+        .expect("init_blank_state should always succeed");
+    }
+
+    /// Initialize `qureg` into the zero state.
+    ///
+    /// If `qureg` is a state-vector of `N` qubits, it is modified to state
+    /// `|0>^{\otimes N}`.  If `qureg` is a density matrix of `N` qubits, it is
+    /// modified to state `|0><0|^{\otimes N}`.
+    ///
+    /// # Parameters
     ///
     ///
     ///
@@ -290,6 +856,72 @@ impl<'a> Qureg<'a> {
         .expect("init_zero_state should always succeed");
     }
 
+    /// Return the register to the zero state, reusing its allocation.
+    ///
+    /// A state-vector is set to `|0...0>`, and a density matrix to
+    /// `|0...0><0...0|`.  This is the same as [`init_zero_state()`], and is
+    /// meant to make it clear that a register can be reused, e.g. between
+    /// the shots of a simulation.  There is no need to drop the register and
+    /// allocate a new one with [`try_new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// for _ in 0..3 {
+    ///     qureg.reset();
+    ///     qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    ///
+    ///     let outcome0 = qureg.measure(0).unwrap();
+    ///     let outcome1 = qureg.measure(1).unwrap();
+    ///     assert_eq!(outcome0, outcome1);
+    /// }
+    /// ```
+    ///
+    /// [`init_zero_state()`]: crate::Qureg::init_zero_state()
+    /// [`try_new()`]: crate::Qureg::try_new()
+    pub fn reset(&mut self) {
+        self.init_zero_state();
+    }
+
+    /// Return a density matrix to the state `|0...0><0...0|`, reusing its
+    /// allocation.
+    ///
+    /// This is the same as [`reset()`], but it additionally checks that the
+    /// register is a density matrix.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `self` is a state-vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// qureg.reset_density().unwrap();
+    ///
+    /// let amp = qureg.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`reset()`]: crate::Qureg::reset()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn reset_density(&mut self) -> Result<(), QuestError> {
+        validate_kind(self, QuregKind::DensityMatrix, "reset_density")?;
+        self.init_zero_state();
+        Ok(())
+    }
+
     /// Initialize `qureg` into the plus state.
     ///
     /// If `qureg` is a state-vector of `N` qubits, it is modified to state:
@@ -332,6 +964,54 @@ impl<'a> Qureg<'a> {
         .expect("init_plus_state should always succeed");
     }
 
+    /// Initialize a sub-register into the plus state.
+    ///
+    /// The qubits in `qubits` are put into the `|+>` state, and all the
+    /// remaining qubits into the `|0>` state.  This works both for
+    /// state-vectors and density matrices.  If `qubits` contains all qubits,
+    /// this is the same as [`init_plus_state()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: qubits to initialize into the `|+>` state
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `qubits` is outside [0, [`num_qubits()`])
+    ///   - if `qubits` contain any repetitions
+    ///
+    /// The state is not modified, if an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_plus_state_on(&[0, 2]).unwrap();
+    ///
+    /// assert!((qureg.get_prob_amp(0b101).unwrap() - 0.25).abs() < EPSILON);
+    /// assert!(qureg.get_prob_amp(0b010).unwrap().abs() < EPSILON);
+    /// ```
+    ///
+    /// [`init_plus_state()`]: crate::Qureg::init_plus_state()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn init_plus_state_on(
+        &mut self,
+        qubits: &[i32],
+    ) -> Result<(), QuestError> {
+        validate_unique_qubits(self, qubits, "init_plus_state_on")?;
+        self.init_zero_state();
+        for &qubit in qubits {
+            self.hadamard(qubit)?;
+        }
+        Ok(())
+    }
+
     /// Initialize `qureg` into a classical state.
     ///
     /// This state is also known as a "computational basis state" with index
@@ -394,6 +1074,116 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Initialize `qureg` into the W state over all qubits.
+    ///
+    /// The W state is the equal superposition of all the basis states with
+    /// exactly one qubit in the state `|1>`:
+    ///
+    /// ```text
+    /// |W> = (|0..001> + |0..010> + ... + |1..000>) / sqrt(N)
+    /// ```
+    ///
+    /// where `N` is [`num_qubits()`].  The state is prepared from `|0..001>`
+    /// by moving the excitation from each qubit `k` to the qubit `k+1` with
+    /// a [`controlled_rotate_y()`] followed by a [`controlled_not()`],
+    /// leaving behind the amplitude `1/sqrt(N)` at every step.  If `qureg`
+    /// is a density matrix, it is initialized into the pure state `|W><W|`.
+    ///
+    /// For a single qubit, this is the state `|1>`.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if [`num_qubits()`] is less than 1
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_w_state().unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(4).unwrap();
+    /// assert!((amp - 3_f64.sqrt().recip() as Qreal).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!(amp.abs() < EPSILON);
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`controlled_rotate_y()`]: crate::Qureg::controlled_rotate_y()
+    /// [`controlled_not()`]: crate::Qureg::controlled_not()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn init_w_state(&mut self) -> Result<(), QuestError> {
+        let num_qubits = self.num_qubits();
+        if num_qubits < 1 {
+            return Err(invalid_input_error(
+                "Invalid number of qubits. Must be >0.",
+                "init_w_state",
+            ));
+        }
+        self.init_zero_state();
+        self.pauli_x(0)?;
+        for k in 0..num_qubits - 1 {
+            // Keep the amplitude 1/sqrt(N-k) of the remaining weight at k.
+            let stay = ((num_qubits - k) as Qreal).sqrt().recip();
+            self.controlled_rotate_y(k, k + 1, 2. * stay.acos())?;
+            self.controlled_not(k + 1, k)?;
+        }
+        Ok(())
+    }
+
+    /// Initialize `qureg` into the GHZ state over all qubits.
+    ///
+    /// The GHZ state is the equal superposition of the two extreme basis
+    /// states:
+    ///
+    /// ```text
+    /// |GHZ> = (|0..000> + |1..111>) / sqrt(2)
+    /// ```
+    ///
+    /// The register is first initialized into the zero state, then a
+    /// [`hadamard()`] is applied to qubit `0`, followed by a chain of
+    /// [`controlled_not()`] gates from each qubit `k` to the qubit `k+1`.  If
+    /// `qureg` is a density matrix, it is initialized into the pure state
+    /// `|GHZ><GHZ|`.
+    ///
+    /// For a single qubit, this is the plus state.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error only if applying any of the gates
+    /// fails, which should not happen for a valid register.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_ghz_state().unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - 2_f64.sqrt().recip() as Qreal).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(7).unwrap();
+    /// assert!((amp - 2_f64.sqrt().recip() as Qreal).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`hadamard()`]: crate::Qureg::hadamard()
+    /// [`controlled_not()`]: crate::Qureg::controlled_not()
+    pub fn init_ghz_state(&mut self) -> Result<(), QuestError> {
+        self.init_zero_state();
+        self.hadamard(0)?;
+        for k in 1..self.num_qubits() {
+            self.controlled_not(k - 1, k)?;
+        }
+        Ok(())
+    }
+
     /// Initialize `qureg` into a pure state.
     ///
     /// - If `qureg` is a state-vector, this merely clones `pure` into `qureg`.
@@ -462,6 +1252,69 @@ impl<'a> Qureg<'a> {
         .expect("init_debug_state() should always succeed");
     }
 
+    /// Check if `qureg` is in the debug state.
+    ///
+    /// Verifies that the `n`th complex amplitude of `qureg` is equal to
+    ///
+    /// ```text
+    ///   2n/10 + i*(2n+1)/10,
+    /// ```
+    ///
+    /// as set by [`init_debug_state()`], up to a relative error of
+    /// [`EPSILON`].  For density matrices, `n` indexes the amplitudes of the
+    /// matrix flattened column-wise, as in [`get_flat_amp()`].
+    ///
+    /// This is useful to check that code moving amplitudes around, e.g.
+    /// between the host and the GPU, or between the nodes in distributed
+    /// mode, preserves the state.  In distributed mode, every amplitude is
+    /// broadcast to all the nodes, so the function must be called by all the
+    /// nodes.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any amplitude differs from the debug state.  The error message
+    ///     identifies the first mismatching amplitude.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_debug_state();
+    /// qureg.assert_debug_state().unwrap();
+    ///
+    /// qureg.pauli_x(0).unwrap();
+    /// qureg.assert_debug_state().unwrap_err();
+    /// ```
+    ///
+    /// [`init_debug_state()`]: crate::Qureg::init_debug_state()
+    /// [`get_flat_amp()`]: crate::Qureg::get_flat_amp()
+    /// [`EPSILON`]: crate::EPSILON
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn assert_debug_state(&self) -> Result<(), QuestError> {
+        for index in 0..self.num_amps_total() {
+            // computed the same way as in QuEST, in double precision
+            let re = (index as f64 * 2.) / 10.;
+            let im = (index as f64 * 2. + 1.) / 10.;
+            let expected = Qcomplex::new(re as Qreal, im as Qreal);
+            let amp = self.get_flat_amp(index)?;
+            if (amp - expected).norm() > EPSILON * expected.norm() {
+                return Err(invalid_input_error(
+                    &format!(
+                        "The amplitude at index {index} is {amp}, expected \
+                         {expected}."
+                    ),
+                    "assert_debug_state",
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Initialize `qureg` by specifying all amplitudes.
     ///
     /// For density matrices, it is assumed the amplitudes have been flattened
@@ -523,26 +1376,100 @@ impl<'a> Qureg<'a> {
         })
     }
 
-    /// Overwrites a contiguous subset of the amplitudes in a state-vector.
+    /// Initialize `qureg` with the amplitudes read from a file.
     ///
-    /// Only amplitudes with indices in `[start_ind,  start_ind + reals.len()]`
-    /// will be changed. The resulting `qureg` may not necessarily be in an
-    /// L2 normalized state.
+    /// Each line of the file should contain the real and imaginary
+    /// components of one amplitude, separated by a comma and a space, e.g.:
     ///
-    /// In distributed mode, this function assumes the subset `reals` and
-    /// `imags` exist (at least) on the node containing the ultimately
-    /// updated elements. For example, below is the correct way to modify
-    /// the full 8 elements of `qureg` when split between 2 nodes:
+    /// ```text
+    /// # this line is ignored
+    /// 0.5, 0.0
+    /// 0.5, 0.0
+    /// 0.0, 0.5
+    /// 0.0, -0.5
+    /// ```
     ///
-    /// ```rust
-    /// # use quest_bind::*;
-    /// let env = QuestEnv::new();
-    /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// Lines starting with `#` are ignored.  The `n`-th remaining line gives
+    /// the amplitude with index `n`.  For density matrices, the amplitudes
+    /// are read as the matrix flattened column-wise, as in
+    /// [`init_state_from_amps()`].  If the file contains fewer than
+    /// [`num_amps_total()`] amplitudes, the remaining ones are left
+    /// unchanged, and any further lines are ignored.  There is no checking
+    /// that the state is normalized.
     ///
-    /// let re = &mut [1., 2., 3., 4.];
-    /// let im = &mut [1., 2., 3., 4.];
-    /// qureg.set_amps(0, re, im);
+    /// Note that the header line `real, imag` written by [`report_state()`]
+    /// is not ignored, and must be removed or commented out with `#` before
+    /// reading the file back.
+    ///
+    /// In distributed mode, every node must be able to read the file, and
+    /// each node loads only the amplitudes it stores.
+    ///
+    /// See also [`from_quest_file()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `filename`: path to the file
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the file cannot be opened
+    /// - [`NulError`],
+    ///   - if `filename` contains a nul byte
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_state_from_file("state.csv").unwrap();
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`init_state_from_amps()`]: crate::Qureg::init_state_from_amps()
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`report_state()`]: crate::Qureg::report_state()
+    /// [`from_quest_file()`]: crate::Qureg::from_quest_file()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`NulError`]: crate::QuestError::NulError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    pub fn init_state_from_file(
+        &mut self,
+        filename: &str,
+    ) -> Result<(), QuestError> {
+        let filename_cstr =
+            CString::new(filename).map_err(QuestError::NulError)?;
+        let reg_ptr = std::ptr::addr_of_mut!(self.reg);
+        let env = self.env.0;
+        catch_quest_exception(|| unsafe {
+            ffi::initStateFromSingleFile(reg_ptr, filename_cstr.as_ptr(), env);
+        })
+    }
+
+    /// Overwrites a contiguous subset of the amplitudes in a state-vector.
+    ///
+    /// Only amplitudes with indices in `[start_ind,  start_ind + reals.len()]`
+    /// will be changed. The resulting `qureg` may not necessarily be in an
+    /// L2 normalized state.
+    ///
+    /// In distributed mode, this function assumes the subset `reals` and
+    /// `imags` exist (at least) on the node containing the ultimately
+    /// updated elements. For example, below is the correct way to modify
+    /// the full 8 elements of `qureg` when split between 2 nodes:
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let re = &mut [1., 2., 3., 4.];
+    /// let im = &mut [1., 2., 3., 4.];
+    /// qureg.set_amps(0, re, im);
     ///
     /// // modify re and im to the next set of elements
     /// for i in 0..4 {
@@ -697,6 +1624,108 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Complex-conjugate every amplitude of the register.
+    ///
+    /// For a state-vector `|psi>`, this results in `|psi^*>`, and for a
+    /// density matrix `rho`, in `rho^*`.  Together with
+    /// [`transpose_density()`], this can be used to check if a density matrix
+    /// is Hermitian, i.e. if `rho = (rho^*)^T`.
+    ///
+    /// The amplitudes are read with [`get_flat_amp()`] and written back with
+    /// [`set_amps()`] or [`set_density_amps()`], hence this function works in
+    /// every mode, but is not fast.  In distributed mode, it must be called
+    /// collectively by all nodes, and every node temporarily holds a copy of
+    /// the entire register.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_debug_state();
+    ///
+    /// qureg.conjugate();
+    ///
+    /// let amp = qureg.get_amp(1).unwrap();
+    /// assert!((amp.re - 0.2).abs() < EPSILON);
+    /// assert!((amp.im + 0.3).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`transpose_density()`]: crate::Qureg::transpose_density()
+    /// [`get_flat_amp()`]: crate::Qureg::get_flat_amp()
+    /// [`set_amps()`]: crate::Qureg::set_amps()
+    /// [`set_density_amps()`]: crate::Qureg::set_density_amps()
+    pub fn conjugate(&mut self) {
+        let (reals, imags): (Vec<_>, Vec<_>) = (0..self.num_amps_total())
+            .map(|index| {
+                let amp = self
+                    .get_flat_amp(index)
+                    .expect("conjugate should always succeed");
+                (amp.re, -amp.im)
+            })
+            .unzip();
+        if self.is_density_matrix() {
+            self.set_density_amps(0, 0, &reals, &imags)
+        } else {
+            self.set_amps(0, &reals, &imags)
+        }
+        .expect("conjugate should always succeed");
+    }
+
+    /// Transpose a density matrix, i.e. swap the elements `rho_ij` and
+    /// `rho_ji`.
+    ///
+    /// Together with [`conjugate()`], this can be used to check if a density
+    /// matrix is Hermitian, i.e. if `rho = (rho^*)^T`.
+    ///
+    /// Since the elements to swap are in general stored on different nodes,
+    /// the matrix is read with [`get_density_amp()`] and written back with
+    /// [`set_density_amps()`].  In distributed mode, this function must be
+    /// called collectively by all nodes, and every node temporarily holds a
+    /// copy of the entire matrix.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_debug_state();
+    /// let amp = qureg.get_density_amp(1, 2).unwrap();
+    ///
+    /// qureg.transpose_density().unwrap();
+    ///
+    /// assert_eq!(qureg.get_density_amp(2, 1).unwrap(), amp);
+    /// ```
+    ///
+    /// [`conjugate()`]: crate::Qureg::conjugate()
+    /// [`get_density_amp()`]: crate::Qureg::get_density_amp()
+    /// [`set_density_amps()`]: crate::Qureg::set_density_amps()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn transpose_density(&mut self) -> Result<(), QuestError> {
+        validate_kind(self, QuregKind::DensityMatrix, "transpose_density")?;
+        let dim = 1_i64 << self.num_qubits();
+        let mut reals = Vec::with_capacity(self.num_amps_total() as usize);
+        let mut imags = Vec::with_capacity(self.num_amps_total() as usize);
+        // amplitudes are stored column by column
+        for col in 0..dim {
+            for row in 0..dim {
+                let amp = self.get_density_amp(col, row)?;
+                reals.push(amp.re);
+                imags.push(amp.im);
+            }
+        }
+        self.set_density_amps(0, 0, &reals, &imags)
+    }
+
     /// Shift the phase of a single qubit by a given angle.
     ///
     /// This is equivalent to a Z-axis rotation of the Bloch-sphere up to a
@@ -742,9 +1771,10 @@ impl<'a> Qureg<'a> {
     pub fn phase_shift(
         &mut self,
         target_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("phase_shift", || unsafe {
             ffi::phaseShift(self.reg, target_qubit, angle);
         })
     }
@@ -799,9 +1829,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         id_qubit1: i32,
         id_qubit2: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("controlled_phase_shift", || unsafe {
             ffi::controlledPhaseShift(self.reg, id_qubit1, id_qubit2, angle);
         })
     }
@@ -846,10 +1877,11 @@ impl<'a> Qureg<'a> {
     pub fn multi_controlled_phase_shift(
         &mut self,
         control_qubits: &[i32],
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        let num_control_qubits = checked_len(control_qubits.len())?;
+        catch_gate("multi_controlled_phase_shift", || unsafe {
             ffi::multiControlledPhaseShift(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -859,6 +1891,218 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Apply a global phase factor, controlled by the passed qubits.
+    ///
+    /// A controlled global phase multiplies by `exp(i angle)` every amplitude
+    /// of the basis states, in which all `control_qubits` are in the `|1>`
+    /// state.  This is the phase a sub-circuit acquires when it is controlled
+    /// by `control_qubits`, e.g. in phase kickback constructions.  It effects
+    /// the diagonal unitary `diag(1, ..., 1, exp(i angle))` on the control
+    /// qubits, and is thus *exactly* equivalent to
+    /// [`multi_controlled_phase_shift()`] (and to [`phase_shift()`], if there
+    /// is only one control qubit).
+    ///
+    /// Note the distinction from a true global phase, i.e. one applied to the
+    /// whole register without any control qubits, which is physically
+    /// unobservable.  A controlled global phase changes the relative phase
+    /// between the branches of a superposition of the control qubits, and
+    /// can be observed.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: qubits that must all be in the `|1>` state for the
+    ///   phase to be applied
+    /// - `angle`: the phase in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `control_qubits` is empty or longer than [`num_qubits()`]
+    ///   - if any qubit index in `control_qubits` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if qubits in `control_qubits` are not unique
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// qureg.multi_controlled_global_phase(&[0, 1], PI).unwrap();
+    ///
+    /// // Only the amplitude of `|11>` changes its sign
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp + 0.5).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`multi_controlled_phase_shift()`]: crate::Qureg::multi_controlled_phase_shift()
+    /// [`phase_shift()`]: crate::Qureg::phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn multi_controlled_global_phase(
+        &mut self,
+        control_qubits: &[i32],
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        self.multi_controlled_phase_shift(control_qubits, angle)
+    }
+
+    /// Apply a multi-controlled phase shift, conditioned on the given state of
+    /// the control qubits.
+    ///
+    /// This is the same as [`multi_controlled_phase_shift()`], except that
+    /// the phase `exp(i angle)` is applied to the basis states in which the
+    /// `control_qubits` are in the state given by `control_state`, instead of
+    /// all being `|1>`.  The control qubits conditioned on `0` are flipped with
+    /// [`pauli_x()`] before and after applying
+    /// [`multi_controlled_phase_shift()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: the indices of the control qubits
+    /// - `control_state`: the bit values (`0` or `1`) of each control qubit,
+    ///   upon which to condition
+    /// - `angle`: amount by which to rotate the phase in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `control_qubits` and `control_state` have different lengths
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `control_qubits` is empty
+    ///   - if any qubit index in `control_qubits` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if qubits in `control_qubits` are not unique
+    ///   - if any element of `control_state` is not a bit (`0` or `1`)
+    ///
+    /// All inputs are validated before the state is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// qureg
+    ///     .multi_state_controlled_phase_shift(&[0, 1], &[1, 0], PI)
+    ///     .unwrap();
+    ///
+    /// // Only the amplitude of `|01>` changes its sign
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp + 0.5).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`multi_controlled_phase_shift()`]: crate::Qureg::multi_controlled_phase_shift()
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn multi_state_controlled_phase_shift(
+        &mut self,
+        control_qubits: &[i32],
+        control_state: &[i32],
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        let err_func = "multi_state_controlled_phase_shift";
+        if control_qubits.len() != control_state.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        if control_qubits.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of control qubits. Must be >0 and <=numQubits.",
+                err_func,
+            ));
+        }
+        validate_control_qubits(self, control_qubits, &[], err_func)?;
+        validate_control_state(control_state, err_func)?;
+
+        with_flipped_controls(self, control_qubits, control_state, |qureg| {
+            qureg.multi_controlled_phase_shift(control_qubits, angle)
+        })
+    }
+
+    /// Shift the phase of a single computational basis state.
+    ///
+    /// Multiplies the amplitude of the basis state `|index>` by
+    /// `exp(i angle)`, leaving all the other amplitudes unchanged.  This is
+    /// the diagonal unitary
+    ///
+    /// ```text
+    /// U = I + (exp(i angle) - 1) |index><index|
+    /// ```
+    ///
+    /// For density matrices, the unitary is applied as `U rho U^dagger`,
+    /// i.e. the elements in row `index` are multiplied by `exp(i angle)`, and
+    /// the elements in column `index` by `exp(-i angle)`, so the diagonal
+    /// element `(index, index)` is unchanged.
+    ///
+    /// This is [`multi_state_controlled_phase_shift()`] controlled on all
+    /// the qubits of the register, in the state given by the binary
+    /// representation of `index` (with qubit `0` the least significant bit).
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: index of the basis state, in `[0, 2^N)`
+    /// - `angle`: amount by which to shift the phase in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `index` is outside `[0, 2^N)`, where `N` is [`num_qubits()`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// // an oracle marking the state `|10>`
+    /// qureg.phase_on_basis_state(2, PI).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(2).unwrap();
+    /// assert!((amp + 0.5).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`multi_state_controlled_phase_shift()`]: crate::Qureg::multi_state_controlled_phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn phase_on_basis_state(
+        &mut self,
+        index: i64,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let num_qubits = self.num_qubits();
+        if index < 0 || index >= 1_i64 << num_qubits {
+            return Err(invalid_input_error(
+                "Invalid state index. Must be >=0 and <2^numQubits.",
+                "phase_on_basis_state",
+            ));
+        }
+        let qubits = (0..num_qubits).collect::<Vec<_>>();
+        let bits = qubits
+            .iter()
+            .map(|&qubit| i32::from((index >> qubit) & 1 == 1))
+            .collect::<Vec<_>>();
+        self.multi_state_controlled_phase_shift(&qubits, &bits, angle)
+    }
+
     /// Apply the (two-qubit) controlled phase flip gate.
     ///
     /// Also known as the controlled pauliZ gate. For each state, if both input
@@ -905,7 +2149,7 @@ impl<'a> Qureg<'a> {
         id_qubit1: i32,
         id_qubit2: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_phase_flip", || unsafe {
             ffi::controlledPhaseFlip(self.reg, id_qubit1, id_qubit2);
         })
     }
@@ -958,11 +2202,12 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubits: &[i32],
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let num_control_qubits = checked_len(control_qubits.len())?;
+        catch_gate("multi_controlled_phase_flip", || unsafe {
             ffi::multiControlledPhaseFlip(
                 self.reg,
                 control_qubits.as_ptr(),
-                control_qubits.len() as i32,
+                num_control_qubits,
             );
         })
     }
@@ -1012,7 +2257,7 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("s_gate", || unsafe {
             ffi::sGate(self.reg, target_qubit);
         })
     }
@@ -1063,23 +2308,31 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("t_gate", || unsafe {
             ffi::tGate(self.reg, target_qubit);
         })
     }
 
-    /// Overwrite the amplitudes of `target_qureg` with those from `copy_qureg`.
+    /// Apply the single-qubit S-dagger gate.
+    ///
+    /// This is the inverse of the S gate: a rotation of `-PI/2` around the
+    /// Z-axis on the Bloch sphere, or the unitary:
+    ///
+    /// ```text
+    ///   [ 1   0 ]
+    ///   [ 0  -i ]
+    /// ```
+    ///
+    /// This is equivalent to [`phase_shift()`] with the angle `-PI/2`.
     ///
     /// # Parameters
     ///
-    /// - `copy_qureg`: the `Qureg` to have its quantum state clone into `self`
+    /// - `target_qubit`: qubit to operate upon
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `self` is a state-vector while `copy_qureg` is a density matrix
-    ///     (and vice versa)
-    ///   - if `self` and `copy_qureg` have different dimensions
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -1087,30 +2340,377 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
-    /// let copy_qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.pauli_x(0).unwrap();
     ///
-    /// qureg.clone_qureg(&copy_qureg);
-    /// ```
+    /// qureg.sdg_gate(0).unwrap();
     ///
-    /// See [QuEST API] for more information.
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
     ///
+    /// [`phase_shift()`]: crate::Qureg::phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn clone_qureg(
+    pub fn sdg_gate(
         &mut self,
-        copy_qureg: &Qureg<'_>,
+        target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::cloneQureg(self.reg, copy_qureg.reg);
-        })
+        self.phase_shift(target_qubit, -PI / 2.)
     }
 
-    /// Performs a logical AND on all successCodes held by all processes.
+    /// Apply the single-qubit T-dagger gate.
     ///
-    /// If any one process has a zero `success_code`, all processes will return
+    /// This is the inverse of the T gate: a rotation of `-PI/4` around the
+    /// Z-axis on the Bloch sphere, or the unitary:
+    ///
+    /// ```text
+    ///   [ 1        0       ]
+    ///   [ 0  e^(-i PI / 4) ]
+    /// ```
+    ///
+    /// This is equivalent to [`phase_shift()`] with the angle `-PI/4`.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate upon
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.pauli_x(0).unwrap();
+    ///
+    /// qureg.tdg_gate(0).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(1).unwrap();
+    /// assert!((amp + SQRT_2 / 2.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`phase_shift()`]: crate::Qureg::phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn tdg_gate(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.phase_shift(target_qubit, -PI / 4.)
+    }
+
+    /// Apply the controlled S gate.
+    ///
+    /// The S gate is applied to `target_qubit` if `control_qubit` is in
+    /// the state `|1>`.  This is equivalent to [`controlled_phase_shift()`]
+    /// with the angle `PI/2`, i.e. the two-qubit unitary:
+    ///
+    /// ```text
+    ///  [ 1  0  0  0 ]
+    ///  [ 0  1  0  0 ]
+    ///  [ 0  0  1  0 ]
+    ///  [ 0  0  0  i ]
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubit`: the qubit which controls the application of the gate
+    /// - `target_qubit`: qubit to operate upon
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.controlled_s_gate(1, 0).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`controlled_phase_shift()`]: crate::Qureg::controlled_phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn controlled_s_gate(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.controlled_phase_shift(control_qubit, target_qubit, PI / 2.)
+    }
+
+    /// Apply the controlled T gate.
+    ///
+    /// The T gate is applied to `target_qubit` if `control_qubit` is in
+    /// the state `|1>`.  This is equivalent to [`controlled_phase_shift()`]
+    /// with the angle `PI/4`, i.e. the two-qubit unitary:
+    ///
+    /// ```text
+    ///  [ 1  0  0       0       ]
+    ///  [ 0  1  0       0       ]
+    ///  [ 0  0  1       0       ]
+    ///  [ 0  0  0  e^(i PI / 4) ]
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubit`: the qubit which controls the application of the gate
+    /// - `target_qubit`: qubit to operate upon
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if either `control_qubit` or `target_qubit` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.controlled_t_gate(1, 0).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp - SQRT_2 / 2.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`controlled_phase_shift()`]: crate::Qureg::controlled_phase_shift()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn controlled_t_gate(
+        &mut self,
+        control_qubit: i32,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        self.controlled_phase_shift(control_qubit, target_qubit, PI / 4.)
+    }
+
+    /// Overwrite the amplitudes of `target_qureg` with those from `copy_qureg`.
+    ///
+    /// # Parameters
+    ///
+    /// - `copy_qureg`: the `Qureg` to have its quantum state clone into `self`
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `self` is a state-vector while `copy_qureg` is a density matrix
+    ///     (and vice versa)
+    ///   - if `self` and `copy_qureg` have different dimensions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// let copy_qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.clone_qureg(&copy_qureg);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn clone_qureg(
+        &mut self,
+        copy_qureg: &Qureg<'_>,
+    ) -> Result<(), QuestError> {
+        catch_quest_exception(|| unsafe {
+            ffi::cloneQureg(self.reg, copy_qureg.reg);
+        })
+    }
+
+    /// Apply a closure to a copy of the register, and return the copy.
+    ///
+    /// Allocates a new register of the same kind and size as `self`, and in
+    /// the same [`QuestEnv`], clones the state of `self` into it, calls `f`
+    /// on the new register, and returns it.  `self` is not modified.  This is
+    /// useful to compare the state before and after a sequence of gates,
+    /// e.g. for gradient estimation.
+    ///
+    /// Note that every call allocates (and later frees) the memory for a
+    /// whole new register: `2^N` amplitudes for a state-vector, and
+    /// `2^(2N)` for a density matrix.  To repeatedly compute the state after
+    /// a gate, it is cheaper to allocate a workspace once and overwrite it
+    /// with [`clone_qureg()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: a closure to apply to the copy of `self`
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the new register cannot be created
+    /// - any error returned by `f`.  The copy is then dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    ///
+    /// let flipped = qureg.with_applied(|q| q.pauli_x(0)).unwrap();
+    ///
+    /// assert!((qureg.get_real_amp(0).unwrap() - 1.).abs() < EPSILON);
+    /// assert!((flipped.get_real_amp(1).unwrap() - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`QuestEnv`]: crate::QuestEnv
+    /// [`clone_qureg()`]: crate::Qureg::clone_qureg()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn with_applied(
+        &self,
+        f: impl FnOnce(&mut Qureg<'a>) -> Result<(), QuestError>,
+    ) -> Result<Qureg<'a>, QuestError> {
+        let mut qureg = self.try_clone()?;
+        f(&mut qureg)?;
+        Ok(qureg)
+    }
+
+    /// Allocate a new register in the same environment as `self`, and clone
+    /// the state of `self` into it.
+    fn try_clone(&self) -> Result<Qureg<'a>, QuestError> {
+        Ok(Self {
+            env: self.env,
+            reg: catch_quest_exception(|| unsafe {
+                ffi::createCloneQureg(self.reg, self.env.0)
+            })?,
+        })
+    }
+
+    /// Apply a block of linear operations to a state-vector, controlled on the
+    /// qubit `control`.  Allocates a whole extra register, and gives wrong
+    /// results for non-linear operations, like measurements, in the block.
+    ///
+    /// The closure `body` is applied to the register as if every operation
+    /// in it were controlled on `control` being in the state `|1>`.  Since
+    /// `QuEST` cannot control arbitrary code, the register is split into the
+    /// two branches: `self` is copied into a temporary register, projected
+    /// onto `control = 1`, and `body` is applied to the copy.  The original
+    /// register is projected onto `control = 0`, and the two branches are
+    /// summed with [`set_weighted_qureg()`].
+    ///
+    /// This is exact for every *linear* operation that does not act on
+    /// `control`, i.e. all gates, including multi-qubit and controlled ones,
+    /// as well as [`apply_matrix_n()`], [`apply_phase_func()`] and similar.
+    /// The following are not supported and give meaningless results, when
+    /// called inside `body`:
+    ///
+    /// - measurements, e.g. [`measure()`], and other operations depending on
+    ///   the norm of the state, like [`collapse_to_outcome()`]
+    /// - initializations, like [`init_zero_state()`], which overwrite the whole
+    ///   register
+    ///
+    /// If `body` moves any amplitude out of the subspace where `control` is
+    /// `1`, e.g. by flipping `control`, an error is returned.
+    ///
+    /// Only state-vectors are supported, since the controlled operation acts
+    /// on a density matrix from both sides.  Every call allocates a temporary
+    /// copy of the register: `2^N` amplitudes.
+    ///
+    /// # Parameters
+    ///
+    /// - `control`: the control qubit
+    /// - `body`: the operations to control.  They must not act on `control`.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `self` is a density matrix
+    ///   - if `control` is outside [0, [`num_qubits()`])
+    ///   - if `body` changes the state of `control`
+    ///   - if the temporary register cannot be created
+    /// - any error returned by `body`.  The register is then left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.hadamard(0).unwrap();
+    ///
+    /// // controlled preparation of a Bell state on qubits 1 and 2
+    /// qureg
+    ///     .controlled(0, |q| q.hadamard(1).and(q.controlled_not(1, 2)))
+    ///     .unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - SQRT_2.recip()).abs() < EPSILON);
+    /// let amp = qureg.get_real_amp(7).unwrap();
+    /// assert!((amp - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`set_weighted_qureg()`]: crate::set_weighted_qureg()
+    /// [`apply_matrix_n()`]: crate::Qureg::apply_matrix_n()
+    /// [`apply_phase_func()`]: crate::Qureg::apply_phase_func()
+    /// [`measure()`]: crate::Qureg::measure()
+    /// [`collapse_to_outcome()`]: crate::Qureg::collapse_to_outcome()
+    /// [`init_zero_state()`]: crate::Qureg::init_zero_state()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn controlled<R>(
+        &mut self,
+        control: i32,
+        body: impl FnOnce(&mut Qureg<'a>) -> Result<R, QuestError>,
+    ) -> Result<R, QuestError> {
+        validate_kind(self, QuregKind::StateVector, "controlled")?;
+        validate_unique_qubits(self, &[control], "controlled")?;
+
+        let mut branch = self.try_clone()?;
+        branch.apply_projector(control, 1)?;
+        let ret = body(&mut branch)?;
+        if branch.calc_prob_of_outcome(control, 0)? > EPSILON.sqrt() {
+            return Err(invalid_input_error(
+                "The controlled operations must not act on the control qubit.",
+                "controlled",
+            ));
+        }
+
+        self.apply_projector(control, 0)?;
+        let (one, zero) = (Qcomplex::new(1., 0.), Qcomplex::new(0., 0.));
+        set_weighted_qureg(one, &branch, zero, &branch, one, self)?;
+        Ok(ret)
+    }
+
+    /// Performs a logical AND on all successCodes held by all processes.
+    ///
+    /// If any one process has a zero `success_code`, all processes will return
     /// a zero success code.
     ///
     /// # Parameters
@@ -1131,13 +2731,40 @@ impl<'a> Qureg<'a> {
             .expect("sync_quest_success should always succeed")
     }
 
+    /// Check if `QuEST` runs in GPU-accelerated mode.
+    ///
+    /// Functions like [`copy_state_to_gpu()`] silently do nothing, unless
+    /// `quest_bind` was compiled with the feature `"gpu"` enabled (and the
+    /// feature `"mpi"` disabled).  This function can be used to make sure the
+    /// state is really kept in GPU memory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// if qureg.is_gpu_accelerated() {
+    ///     qureg.copy_state_from_gpu();
+    /// }
+    /// ```
+    ///
+    /// [`copy_state_to_gpu()`]: crate::Qureg::copy_state_to_gpu()
+    #[must_use]
+    pub fn is_gpu_accelerated(&self) -> bool {
+        cfg!(feature = "gpu") && !cfg!(feature = "mpi")
+    }
+
     /// Copy the state-vector (or density matrix) into GPU memory.
     ///
     /// In GPU mode, this copies the state-vector (or density matrix) from RAM
     /// to VRAM / GPU-memory, which is the version operated upon by other calls
     /// to the API.
     ///
-    /// In CPU mode, this function has no effect.
+    /// In CPU mode, this function has no effect.  Use [`is_gpu_accelerated()`]
+    /// to check if GPU-acceleration is enabled.
     ///
     /// In conjunction with [`copy_state_from_gpu()`][api-copy-state-from-gpu]
     /// (which should be called first), this allows a user to directly modify
@@ -1159,6 +2786,7 @@ impl<'a> Qureg<'a> {
     ///
     /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
     /// [api-set-amps]: crate::Qureg::set_amps()
+    /// [`is_gpu_accelerated()`]: crate::Qureg::is_gpu_accelerated()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn copy_state_to_gpu(&mut self) {
@@ -1173,7 +2801,8 @@ impl<'a> Qureg<'a> {
     /// In GPU mode, this copies the state-vector (or density matrix) from GPU
     /// memory to RAM , where it can be accessed/modified  by the user.
     ///
-    /// In CPU mode, this function has no effect.
+    /// In CPU mode, this function has no effect.  Use [`is_gpu_accelerated()`]
+    /// to check if GPU-acceleration is enabled.
     ///
     /// In conjunction with [`copy_state_to_gpu()`][api-copy-state-to-gpu] ,
     /// this allows a user to directly modify the state-vector in a hardware
@@ -1195,6 +2824,7 @@ impl<'a> Qureg<'a> {
     ///
     /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
     /// [api-set-amps]: crate::Qureg::set_amps()
+    /// [`is_gpu_accelerated()`]: crate::Qureg::is_gpu_accelerated()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn copy_state_from_gpu(&mut self) {
@@ -1207,7 +2837,8 @@ impl<'a> Qureg<'a> {
     /// In GPU mode, this copies a substate of the state-vector (or density
     /// matrix) from RAM to VRAM / GPU-memory.
     ///
-    /// In CPU mode, this function has no effect.
+    /// In CPU mode, this function has no effect.  Use [`is_gpu_accelerated()`]
+    /// to check if GPU-acceleration is enabled.
     ///
     /// In conjunction with
     /// [`copy_substate_from_gpu()`][api-copy-substate-from-gpu], this allows a
@@ -1238,6 +2869,7 @@ impl<'a> Qureg<'a> {
     /// [api-copy-state-to-gpu]: crate::Qureg::copy_state_to_gpu()
     /// [api-set-amps]: crate::Qureg::set_amps()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`is_gpu_accelerated()`]: crate::Qureg::is_gpu_accelerated()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn copy_substate_to_gpu(
@@ -1256,7 +2888,8 @@ impl<'a> Qureg<'a> {
     /// matrix) from  to VRAM / GPU-memory to RAM, which is the version
     /// operated upon by other calls to the API.
     ///
-    /// In CPU mode, this function has no effect.
+    /// In CPU mode, this function has no effect.  Use [`is_gpu_accelerated()`]
+    /// to check if GPU-acceleration is enabled.
     ///
     /// In conjunction with
     /// [`copy_substate_to_gpu()`][api-copy-substate-to-gpu], this allows a user
@@ -1287,6 +2920,7 @@ impl<'a> Qureg<'a> {
     /// [api-copy-state-from-gpu]: crate::Qureg::copy_state_from_gpu()
     /// [api-set-amps]: crate::Qureg::set_amps()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`is_gpu_accelerated()`]: crate::Qureg::is_gpu_accelerated()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn copy_substate_from_gpu(
@@ -1413,18 +3047,25 @@ impl<'a> Qureg<'a> {
         catch_quest_exception(|| unsafe { ffi::getImagAmp(self.reg, index) })
     }
 
-    /// Get the probability of a state-vector at an index in the full state
-    /// vector.
+    /// Get the real parts of a contiguous range of probability amplitudes
+    /// in the state vector.
+    ///
+    /// Fills `out` with the real parts of the amplitudes at indices
+    /// `[start, start + out.len())`.  This is useful for numerical libraries
+    /// that expect the real and imaginary components in separate arrays.
     ///
     /// # Parameters
     ///
-    /// - `index`: index in state vector of probability amplitudes
+    /// - `start`: index of the first amplitude to read
+    /// - `out`: buffer to write the real parts of the amplitudes into
     ///
     /// # Errors
     ///
+    /// - [`ArrayLengthError`],
+    ///   - if `start` is negative, or if `start + out.len()` exceeds
+    ///     [`num_amps_total()`]
     /// - [`InvalidQuESTInputError`],
     ///   - if `qureg` is a density matrix
-    ///   - if `index` is outside [0, [`num_qubits()`]).
     ///
     /// # Examples
     ///
@@ -1435,14 +3076,189 @@ impl<'a> Qureg<'a> {
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
     /// qureg.init_plus_state();
     ///
-    /// let amp = qureg.get_prob_amp(0).unwrap();
-    /// assert!((amp - 0.25).abs() < EPSILON);
+    /// let mut out = [0.; 3];
+    /// qureg.get_real_amps(1, &mut out).unwrap();
+    /// assert!(out.iter().all(|x| (x - 0.5).abs() < EPSILON));
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn get_real_amps(
+        &self,
+        start: i64,
+        out: &mut [Qreal],
+    ) -> Result<(), QuestError> {
+        validate_kind(self, QuregKind::StateVector, "get_real_amps")?;
+        validate_amps_range(self, start, out.len())?;
+        for (index, x) in (start..).zip(out.iter_mut()) {
+            *x = self.get_real_amp(index)?;
+        }
+        Ok(())
+    }
+
+    /// Get the imaginary parts of a contiguous range of probability amplitudes
+    /// in the state vector.
+    ///
+    /// Fills `out` with the imaginary parts of the amplitudes at indices
+    /// `[start, start + out.len())`.  This is useful for numerical libraries
+    /// that expect the real and imaginary components in separate arrays.
+    ///
+    /// # Parameters
+    ///
+    /// - `start`: index of the first amplitude to read
+    /// - `out`: buffer to write the imaginary parts of the amplitudes into
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `start` is negative, or if `start + out.len()` exceeds
+    ///     [`num_amps_total()`]
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a density matrix
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let mut out = [1.; 3];
+    /// qureg.get_imag_amps(1, &mut out).unwrap();
+    /// assert!(out.iter().all(|x| x.abs() < EPSILON));
+    /// ```
+    ///
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn get_imag_amps(
+        &self,
+        start: i64,
+        out: &mut [Qreal],
+    ) -> Result<(), QuestError> {
+        validate_kind(self, QuregKind::StateVector, "get_imag_amps")?;
+        validate_amps_range(self, start, out.len())?;
+        for (index, x) in (start..).zip(out.iter_mut()) {
+            *x = self.get_imag_amp(index)?;
+        }
+        Ok(())
+    }
+
+    /// Write all amplitudes of the register to `w` in a binary format,
+    /// `chunk` amplitudes at a time.
+    ///
+    /// The amplitudes are fetched into a buffer of `chunk` amplitudes, which
+    /// is written to `w` and reused for the next chunk, so that the whole
+    /// state never needs to be copied into a single host buffer.  This makes
+    /// it possible to save very large registers.
+    ///
+    /// ## Binary format
+    ///
+    /// There is no header.  The amplitudes are written in the order of their
+    /// indices, each as a pair of its real and imaginary parts, encoded as
+    /// little-endian `f64` (also if `quest_bind` is compiled with the feature
+    /// `"f32"`).  Every amplitude takes 16 bytes, and the total size is
+    /// `16 * num_amps_total()` bytes.  The amplitudes of a density matrix are
+    /// written in the order of [`get_flat_amp()`], i.e. column by column.
+    ///
+    /// In distributed mode, every node receives all amplitudes and writes
+    /// them to its own `w`.  This function must be called collectively by
+    /// all nodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `w`: the writer to write the amplitudes into
+    /// - `chunk`: the number of amplitudes fetched at a time
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `chunk` is zero
+    /// - [`IoError`],
+    ///   - if writing to `w` fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let mut bytes = Vec::new();
+    /// qureg.write_amps_chunked(&mut bytes, 3).unwrap();
+    /// assert_eq!(bytes.len(), 8 * 16);
+    ///
+    /// let re = f64::from_le_bytes(bytes[16..24].try_into().unwrap());
+    /// assert!((re - 8_f64.sqrt().recip()).abs() < 1e-6);
+    /// ```
+    ///
+    /// [`get_flat_amp()`]: crate::Qureg::get_flat_amp()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`IoError`]: crate::QuestError::IoError
+    // `Qreal` is `f64` unless the "f32" feature is enabled.
+    #[allow(clippy::useless_conversion)]
+    pub fn write_amps_chunked<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        chunk: usize,
+    ) -> Result<(), QuestError> {
+        if chunk == 0 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_amps = self.num_amps_total();
+        let chunk = i64::try_from(chunk).unwrap_or(i64::MAX).min(num_amps);
+        let mut buf = Vec::with_capacity(chunk as usize * 16);
+        let mut start = 0;
+        while start < num_amps {
+            let end = num_amps.min(start + chunk);
+            buf.clear();
+            for index in start..end {
+                let amp = self.get_flat_amp(index)?;
+                buf.extend_from_slice(&f64::from(amp.re).to_le_bytes());
+                buf.extend_from_slice(&f64::from(amp.im).to_le_bytes());
+            }
+            w.write_all(&buf)
+                .map_err(|err| QuestError::IoError(err.kind()))?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Get the probability of a state-vector at an index in the full state
+    /// vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: index in state vector of probability amplitudes
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a density matrix
+    ///   - if `index` is outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let amp = qureg.get_prob_amp(0).unwrap();
+    /// assert!((amp - 0.25).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn get_prob_amp(
         &self,
@@ -1493,6 +3309,267 @@ impl<'a> Qureg<'a> {
         .map(Into::into)
     }
 
+    /// Get a whole row of amplitudes of a density matrix.
+    ///
+    /// Fills `out` with the amplitudes of the density matrix at `row` and
+    /// columns `0..(1 << num_qubits())`.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: row of the density matrix
+    /// - `out`: buffer of length `1 << num_qubits()` to write the amplitudes
+    ///   into
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `out.len()` is not `1 << num_qubits()`
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///   - if `row` is outside [0, `1 << num_qubits()`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let mut row = [Qcomplex::new(0., 0.); 4];
+    /// qureg.get_density_row(1, &mut row).unwrap();
+    ///
+    /// assert!(row.iter().all(|amp| (amp.re - 0.25).abs() < EPSILON));
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn get_density_row(
+        &self,
+        row: i64,
+        out: &mut [Qcomplex],
+    ) -> Result<(), QuestError> {
+        validate_kind(self, QuregKind::DensityMatrix, "get_density_row")?;
+        let dim = 1_i64 << self.num_qubits();
+        if i64::try_from(out.len()) != Ok(dim) {
+            return Err(QuestError::ArrayLengthError);
+        }
+        if row < 0 || row >= dim {
+            return Err(invalid_input_error(
+                "Invalid amplitude index. Must be >=0 and <2^numQubits.",
+                "get_density_row",
+            ));
+        }
+        for (col, amp) in (0..).zip(out.iter_mut()) {
+            *amp = self.get_density_amp(row, col)?;
+        }
+        Ok(())
+    }
+
+    /// Get an amplitude at a given index of the flattened register.
+    ///
+    /// For state-vectors, this is the same as [`get_amp()`].  A density matrix
+    /// is treated as a flattened array of `2^(2N)` amplitudes, stored column
+    /// by column: the amplitude at `index` is the element of the density
+    /// matrix at row `index % (1 << N)` and column `index / (1 << N)`,
+    /// where `N` is [`num_qubits()`].  This is the same convention as used by
+    /// [`init_state_from_amps()`] and [`set_density_amps()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: index of the amplitude, within [0, [`num_amps_total()`])
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `index` is outside [0, [`num_amps_total()`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(1).unwrap();
+    ///
+    /// // row 1, column 1
+    /// let amp = qureg.get_flat_amp(5).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`get_amp()`]: crate::Qureg::get_amp()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`init_state_from_amps()`]: crate::Qureg::init_state_from_amps()
+    /// [`set_density_amps()`]: crate::Qureg::set_density_amps()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn get_flat_amp(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError> {
+        let Some(dim) = self.density_dim() else {
+            return self.get_amp(index);
+        };
+        if index < 0 || index >= self.num_amps_total() {
+            return Err(invalid_input_error(
+                "Invalid amplitude index. Must be >=0 and <2^numQubits.",
+                "get_flat_amp",
+            ));
+        }
+        self.get_density_amp(index % dim, index / dim)
+    }
+
+    /// Get the amplitude associated with the computational basis state
+    /// `|index>`, for either kind of register.
+    ///
+    /// - If `qureg` is a state-vector `|psi>`, this is the amplitude
+    ///   `<index|psi>`, the same as [`get_amp()`].
+    /// - If `qureg` is a density matrix `rho`, this is the diagonal element
+    ///   `<index|rho|index>`, the same as [`get_density_amp()`] with `row = col
+    ///   = index`.  For a valid density matrix, this is the probability of
+    ///   measuring the register in `|index>`, with zero imaginary part. Note
+    ///   that it is *not* a square root of that probability, as it would be for
+    ///   a state-vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: the index of the basis state
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `index` is outside [0, `2^N`), where `N` is [`num_qubits()`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut rho = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    /// rho.init_plus_state();
+    ///
+    /// let amp = qureg.basis_state_amplitude(3).unwrap();
+    /// assert!((amp.re - 0.5).abs() < EPSILON);
+    ///
+    /// let amp = rho.basis_state_amplitude(3).unwrap();
+    /// assert!((amp.re - 0.25).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`get_amp()`]: crate::Qureg::get_amp()
+    /// [`get_density_amp()`]: crate::Qureg::get_density_amp()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn basis_state_amplitude(
+        &self,
+        index: i64,
+    ) -> Result<Qcomplex, QuestError> {
+        if index < 0 || index >= 1 << self.num_qubits() {
+            return Err(invalid_input_error(
+                "Invalid amplitude index. Must be >=0 and <2^numQubits.",
+                "basis_state_amplitude",
+            ));
+        }
+        match self.kind() {
+            QuregKind::StateVector => self.get_amp(index),
+            QuregKind::DensityMatrix => self.get_density_amp(index, index),
+        }
+    }
+
+    /// Iterate over the amplitudes of a state-vector together with labels of
+    /// the computational basis states.
+    ///
+    /// The iterator yields tuples `(index, label, amplitude)`, where `label` is
+    /// the basis state `index` written as `|b_{N-1}...b_1b_0>`, with qubit
+    /// `0` as the rightmost (least significant) digit.
+    ///
+    /// See also [`labeled_amps_above()`] to skip amplitudes close to zero.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is a density matrix
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(1).unwrap();
+    ///
+    /// for (index, label, amp) in qureg.labeled_amps().unwrap() {
+    ///     println!("{index}: {label} {amp}");
+    /// }
+    ///
+    /// let (index, label, amp) = qureg.labeled_amps().unwrap().nth(1).unwrap();
+    /// assert_eq!(index, 1);
+    /// assert_eq!(label, "|01>");
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`labeled_amps_above()`]: crate::Qureg::labeled_amps_above()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn labeled_amps(
+        &self
+    ) -> Result<impl Iterator<Item = (i64, String, Qcomplex)> + '_, QuestError>
+    {
+        validate_kind(self, QuregKind::StateVector, "labeled_amps")?;
+        let num_qubits = self.num_qubits() as usize;
+        Ok((0..self.num_amps_total()).map(move |index| {
+            let amp = self.get_amp(index).expect("index should be valid");
+            (index, format!("|{index:0num_qubits$b}>"), amp)
+        }))
+    }
+
+    /// Iterate over the amplitudes of a state-vector that are not close to
+    /// zero, together with labels of the computational basis states.
+    ///
+    /// Same as [`labeled_amps()`], but skips all amplitudes with the absolute
+    /// value smaller than `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is a density matrix
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    ///
+    /// let labels = qureg
+    ///     .labeled_amps_above(EPSILON)
+    ///     .unwrap()
+    ///     .map(|(_, label, _)| label)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(labels, &["|00>", "|11>"]);
+    /// ```
+    ///
+    /// [`labeled_amps()`]: crate::Qureg::labeled_amps()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn labeled_amps_above(
+        &self,
+        threshold: Qreal,
+    ) -> Result<impl Iterator<Item = (i64, String, Qcomplex)> + '_, QuestError>
+    {
+        Ok(self
+            .labeled_amps()?
+            .filter(move |(_, _, amp)| amp.norm() >= threshold))
+    }
+
     /// A debugging function which calculates the total probability of the
     /// qubits.
     ///
@@ -1535,6 +3612,310 @@ impl<'a> Qureg<'a> {
             .expect("calc_total_prop should always succeed")
     }
 
+    /// Check if the register is normalized, up to `eps`.
+    ///
+    /// Returns `true`, if the total probability computed by
+    /// [`calc_total_prob()`] differs from `1` by at most `eps`.  For
+    /// state-vectors, this is the squared norm of the state, and for density
+    /// matrices, the real part of the trace.
+    ///
+    /// # Parameters
+    ///
+    /// - `eps`: the tolerance, e.g. [`EPSILON`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_plus_state();
+    /// assert!(qureg.is_normalized(EPSILON));
+    ///
+    /// qureg.set_amps(0, &[1., 1.], &[0., 0.]).unwrap();
+    /// assert!(!qureg.is_normalized(EPSILON));
+    /// ```
+    ///
+    /// [`calc_total_prob()`]: crate::Qureg::calc_total_prob()
+    /// [`EPSILON`]: crate::EPSILON
+    #[must_use]
+    pub fn is_normalized(
+        &self,
+        eps: Qreal,
+    ) -> bool {
+        (self.calc_total_prob() - 1.).abs() <= eps
+    }
+
+    /// Calculates the sum of the absolute-value-squared of every amplitude.
+    ///
+    /// For state-vectors, this is the squared L2 norm of the state-vector,
+    /// i.e. the same quantity as [`calc_total_prob()`].  For density
+    /// matrices, this is the sum `$ \sum_{ij} |\rho_{ij}|^2 $`, i.e. the
+    /// squared Hilbert-Schmidt norm (equal to [`calc_purity()`]), and *not*
+    /// the trace returned by [`calc_total_prob()`].
+    ///
+    /// Unlike [`calc_total_prob()`], this function does not use Kahan
+    /// summation.  The amplitudes are summed in parallel (and reduced across
+    /// the nodes in distributed mode), which is faster, but the rounding
+    /// error grows with the number of amplitudes: for a register with `M`
+    /// amplitudes, the relative error can be of order `M * EPSILON` in
+    /// the worst case, while Kahan summation keeps it of order `EPSILON`.
+    /// Use [`calc_total_prob()`], when accuracy matters more than speed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let norm_sq = qureg.l2_norm_sq_fast();
+    /// assert!((norm_sq - 1.).abs() < EPSILON)
+    /// ```
+    ///
+    /// [`calc_total_prob()`]: crate::Qureg::calc_total_prob()
+    /// [`calc_purity()`]: crate::Qureg::calc_purity()
+    #[must_use]
+    pub fn l2_norm_sq_fast(&self) -> Qreal {
+        if self.is_density_matrix() {
+            self.calc_purity()
+        } else {
+            calc_inner_product(self, self).map(|prod| prod.re)
+        }
+        .expect("l2_norm_sq_fast should always succeed")
+    }
+
+    /// Calculate the trace of a density matrix.
+    ///
+    /// This is the sum of all diagonal elements of the density matrix. Unlike
+    /// [`calc_total_prob()`], this function returns the full complex value of
+    /// the trace, which can be useful for un-normalized density matrices.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let trace = qureg.trace().unwrap();
+    /// assert!((trace.re - 1.).abs() < EPSILON);
+    /// assert!(trace.im.abs() < EPSILON);
+    /// ```
+    ///
+    /// [`calc_total_prob()`]: crate::Qureg::calc_total_prob()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn trace(&self) -> Result<Qcomplex, QuestError> {
+        let dim = 1_i64 << self.num_qubits();
+        let mut trace = Qcomplex::new(0., 0.);
+        for i in 0..dim {
+            trace += self.get_density_amp(i, i)?;
+        }
+        Ok(trace)
+    }
+
+    /// Return the real parts of the diagonal elements of a density matrix.
+    ///
+    /// The `i`-th element of the returned vector of length `2^N` is
+    /// `Re(rho_ii)`, the probability of measuring the register in the basis
+    /// state `|i>`.  This is the same as [`prob_of_all_outcomes()`] for all
+    /// the qubits of the register, in increasing order, which also works for
+    /// state-vectors.
+    ///
+    /// In distributed mode, every node receives the full vector.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let probs = qureg.diagonal_probabilities().unwrap();
+    /// assert!(probs.iter().all(|p| (p - 0.25).abs() < EPSILON));
+    /// ```
+    ///
+    /// [`prob_of_all_outcomes()`]: crate::Qureg::prob_of_all_outcomes()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn diagonal_probabilities(&self) -> Result<Vec<Qreal>, QuestError> {
+        validate_kind(
+            self,
+            QuregKind::DensityMatrix,
+            "diagonal_probabilities",
+        )?;
+        let qubits = (0..self.num_qubits()).collect::<Vec<_>>();
+        self.prob_of_all_outcomes(&qubits)
+    }
+
+    /// Check if the register is a valid density matrix, up to `eps`.
+    ///
+    /// Returns `true`, if the trace of the matrix differs from `1` by at most
+    /// `eps`, and if the matrix is Hermitian, i.e. `|rho_ij - conj(rho_ji)| <=
+    /// eps` for all `i`, `j`.  In particular, the diagonal elements must be
+    /// real.  Positivity of the matrix is not checked.
+    ///
+    /// The elements are read with [`get_density_amp()`], which broadcasts them
+    /// to every node, so that in distributed mode all nodes agree on the
+    /// result.  This function must then be called collectively by all nodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `eps`: the tolerance, e.g. [`EPSILON`]
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.init_plus_state();
+    /// assert!(qureg.is_valid_density_matrix(EPSILON).unwrap());
+    ///
+    /// qureg.init_debug_state();
+    /// assert!(!qureg.is_valid_density_matrix(EPSILON).unwrap());
+    /// ```
+    ///
+    /// [`get_density_amp()`]: crate::Qureg::get_density_amp()
+    /// [`EPSILON`]: crate::EPSILON
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn is_valid_density_matrix(
+        &self,
+        eps: Qreal,
+    ) -> Result<bool, QuestError> {
+        validate_kind(
+            self,
+            QuregKind::DensityMatrix,
+            "is_valid_density_matrix",
+        )?;
+        if (self.trace()? - 1.).norm() > eps {
+            return Ok(false);
+        }
+        let dim = 1_i64 << self.num_qubits();
+        for row in 0..dim {
+            for col in row..dim {
+                let amp = self.get_density_amp(row, col)?;
+                let amp_t = self.get_density_amp(col, row)?;
+                if (amp - amp_t.conj()).norm() > eps {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Calculate the partial trace of a density matrix.
+    ///
+    /// Traces out all qubits except for `keep_qubits`, and returns the reduced
+    /// density matrix as a newly allocated `Qureg`. The qubit `keep_qubits[k]`
+    /// of `qureg` becomes the qubit `k` of the reduced density matrix.
+    ///
+    /// The reduced density matrix is computed by iterating over the
+    /// elements of the full density matrix, and so this function is slow for
+    /// large registers.
+    ///
+    /// # Parameters
+    ///
+    /// - `keep_qubits`: the qubits that are not traced out
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qureg` is a state vector
+    ///   - if `keep_qubits` is empty
+    ///   - if any of `keep_qubits` is outside [0, [`num_qubits()`])
+    ///   - if `keep_qubits` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    ///
+    /// // The reduced state of a Bell pair is maximally mixed
+    /// let reduced = qureg.partial_trace(&[0]).unwrap();
+    /// assert_eq!(reduced.num_qubits(), 1);
+    ///
+    /// let amp = reduced.get_density_amp(0, 0).unwrap();
+    /// assert!((amp.re - 0.5).abs() < EPSILON);
+    /// let amp = reduced.get_density_amp(1, 0).unwrap();
+    /// assert!(amp.norm() < EPSILON);
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn partial_trace(
+        &self,
+        keep_qubits: &[i32],
+    ) -> Result<Qureg<'a>, QuestError> {
+        validate_kind(self, QuregKind::DensityMatrix, "partial_trace")?;
+        if keep_qubits.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of qubits to keep. Must be >0.",
+                "partial_trace",
+            ));
+        }
+        validate_unique_qubits(self, keep_qubits, "partial_trace")?;
+
+        let traced_qubits = (0..self.num_qubits())
+            .filter(|q| !keep_qubits.contains(q))
+            .collect::<Vec<_>>();
+        let dim_keep = 1_i64 << keep_qubits.len();
+        let dim_traced = 1_i64 << traced_qubits.len();
+
+        let num_amps = 1_usize << (2 * keep_qubits.len());
+        let mut reals = Vec::with_capacity(num_amps);
+        let mut imags = Vec::with_capacity(num_amps);
+        // Amplitudes of a density matrix are stored column by column
+        for col in 0..dim_keep {
+            for row in 0..dim_keep {
+                let mut amp = Qcomplex::new(0., 0.);
+                for t in 0..dim_traced {
+                    let traced_index = scatter_bits(t, &traced_qubits);
+                    amp += self.get_density_amp(
+                        scatter_bits(row, keep_qubits) | traced_index,
+                        scatter_bits(col, keep_qubits) | traced_index,
+                    )?;
+                }
+                reals.push(amp.re);
+                imags.push(amp.im);
+            }
+        }
+
+        let mut reduced =
+            Qureg::try_new_density(checked_len(keep_qubits.len())?, self.env)?;
+        reduced.set_density_amps(0, 0, &reals, &imags)?;
+        Ok(reduced)
+    }
+
     /// Apply a single-qubit unitary parameterized by two given complex scalars.
     ///
     /// Given valid complex numbers `alpha` and `beta`, applies the unitary:
@@ -1597,7 +3978,7 @@ impl<'a> Qureg<'a> {
         alpha: Qcomplex,
         beta: Qcomplex,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("compact_unitary", || unsafe {
             ffi::compactUnitary(
                 self.reg,
                 target_qubit,
@@ -1660,11 +4041,136 @@ impl<'a> Qureg<'a> {
         target_qubit: i32,
         u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("unitary", || unsafe {
             ffi::unitary(self.reg, target_qubit, u.0);
         })
     }
 
+    /// Apply the general single-qubit gate `U(theta, phi, lambda)`
+    /// parametrized by Euler angles.
+    ///
+    /// This is the `U` gate of [OpenQASM 3], given by the matrix:
+    ///
+    /// ```text
+    /// [             cos(theta/2)   -exp(i lambda) sin(theta/2)        ]
+    /// [ exp(i phi) sin(theta/2)     exp(i (phi + lambda)) cos(theta/2) ]
+    /// ```
+    ///
+    /// Every single-qubit unitary is equal to `U(theta, phi, lambda)`, up to
+    /// a global phase.  E.g. the Hadamard gate is `U(PI/2, 0, PI)`, and the
+    /// Pauli X gate is `U(PI, 0, PI)`.  The matrix is applied with
+    /// [`unitary()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate on
+    /// - `theta`, `phi`, `lambda`: the Euler angles in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    ///
+    /// // Hadamard gate
+    /// qureg.u3(0, PI / 2., 0., PI).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(1).unwrap();
+    /// assert!((amp - SQRT_2.recip()).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`unitary()`]: crate::Qureg::unitary()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [OpenQASM 3]: https://openqasm.com/language/gates.html#built-in-gates
+    pub fn u3(
+        &mut self,
+        target_qubit: i32,
+        theta: impl Into<Radians>,
+        phi: impl Into<Radians>,
+        lambda: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let theta = theta.into().0;
+        let phi = phi.into().0;
+        let lambda = lambda.into().0;
+
+        let (sin, cos) = (theta / 2.).sin_cos();
+        let e_phi = Qcomplex::from_polar(1., phi);
+        let e_lambda = Qcomplex::from_polar(1., lambda);
+        let e_both = Qcomplex::from_polar(1., phi + lambda);
+
+        let elems = [
+            [Qcomplex::new(cos, 0.), -e_lambda * sin],
+            [e_phi * sin, e_both * cos],
+        ];
+        let u = ComplexMatrix2::new(
+            elems.map(|row| row.map(|z| z.re)),
+            elems.map(|row| row.map(|z| z.im)),
+        );
+        self.unitary(target_qubit, &u)
+    }
+
+    /// Apply a parametric single-qubit gate.
+    ///
+    /// The matrix of `gate` is computed for the given `param` with
+    /// [`ParametricGate::matrix()`] and applied with [`unitary()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: the qubit to operate on
+    /// - `gate`: the parametric gate
+    /// - `param`: the value of the parameter
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`])
+    ///   - if the matrix of `gate` for `param` is not unitary
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let phase = ParametricGate::new(|phi| {
+    ///     let (one, zero) = (Qcomplex::new(1., 0.), Qcomplex::new(0., 0.));
+    ///     ComplexMatrix2::from_complex([
+    ///         [one, zero],
+    ///         [zero, Qcomplex::from_polar(1., phi)],
+    ///     ])
+    /// });
+    ///
+    /// for step in 0..4_u8 {
+    ///     qureg.init_plus_state();
+    ///     let phi = Qreal::from(step) * PI / 2.;
+    ///     qureg.apply_parametric(0, &phase, phi).unwrap();
+    /// }
+    /// ```
+    ///
+    /// [`ParametricGate::matrix()`]: crate::ParametricGate::matrix()
+    /// [`unitary()`]: crate::Qureg::unitary()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn apply_parametric(
+        &mut self,
+        target_qubit: i32,
+        gate: &ParametricGate,
+        param: Qreal,
+    ) -> Result<(), QuestError> {
+        self.unitary(target_qubit, &gate.matrix(param))
+    }
+
     /// Rotate a single qubit by a given angle around the X-axis of the
     /// Bloch-sphere.
     ///
@@ -1705,9 +4211,10 @@ impl<'a> Qureg<'a> {
     pub fn rotate_x(
         &mut self,
         rot_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("rotate_x", || unsafe {
             ffi::rotateX(self.reg, rot_qubit, angle);
         })
     }
@@ -1752,9 +4259,10 @@ impl<'a> Qureg<'a> {
     pub fn rotate_y(
         &mut self,
         rot_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("rotate_y", || unsafe {
             ffi::rotateY(self.reg, rot_qubit, angle);
         })
     }
@@ -1799,22 +4307,73 @@ impl<'a> Qureg<'a> {
     pub fn rotate_z(
         &mut self,
         rot_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        catch_gate("rotate_z", || unsafe {
+            ffi::rotateZ(self.reg, rot_qubit, angle);
+        })
+    }
+
+    /// Rotate a single qubit by a given angle around a given axis.
+    ///
+    /// The axis of rotation is given by a [`Vector`] on the Bloch-sphere.      
+    /// The vector must not be zero (or else an error is thrown), but needn't be
+    /// unit magnitude, since the normalization will be computed by by `QuEST`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rot_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate in radians
+    /// - `axis`: vector around which to rotate (can be non-unit; will be
+    ///   normalized)
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `rot_qubit` is outside [0, [`num_qubits()`])
+    ///   - if `axis` is the zero vector
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let angle = 2.0 * PI;
+    /// let axis = &Vector::new(0., 0., 1.);
+    /// qureg.rotate_around_axis(0, angle, axis).unwrap();
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`Vector`]: crate::Vector
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn rotate_around_axis(
+        &mut self,
+        rot_qubit: i32,
+        angle: impl Into<Radians>,
+        axis: &Vector,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::rotateZ(self.reg, rot_qubit, angle);
+        let angle = angle.into().0;
+        catch_gate("rotate_around_axis", || unsafe {
+            ffi::rotateAroundAxis(self.reg, rot_qubit, angle, axis.0);
         })
     }
 
-    /// Rotate a single qubit by a given angle around a given axis.
+    /// Rotate every qubit by a given angle around a given axis.
     ///
-    /// The axis of rotation is given by a [`Vector`] on the Bloch-sphere.      
-    /// The vector must not be zero (or else an error is thrown), but needn't be
-    /// unit magnitude, since the normalization will be computed by by `QuEST`.
+    /// This applies [`rotate_around_axis()`] to each qubit of the register,
+    /// e.g. to effect a global spin rotation.  The axis is checked once,
+    /// before any qubit is rotated.
     ///
     /// # Parameters
     ///
-    /// - `rot_qubit`: qubit to rotate
     /// - `angle`: angle by which to rotate in radians
     /// - `axis`: vector around which to rotate (can be non-unit; will be
     ///   normalized)
@@ -1822,7 +4381,6 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`]
-    ///   - if `rot_qubit` is outside [0, [`num_qubits()`])
     ///   - if `axis` is the zero vector
     ///
     /// # Examples
@@ -1832,27 +4390,32 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// let angle = 2.0 * PI;
-    /// let axis = &Vector::new(0., 0., 1.);
-    /// qureg.rotate_around_axis(0, angle, axis).unwrap();
-    /// ```
+    /// let axis = &Vector::new(1., 0., 0.);
+    /// qureg.rotate_around_axis_all(PI, axis).unwrap();
     ///
-    /// See [QuEST API] for more information.
+    /// // |000> -> (-i)^3 |111>
+    /// let amp = qureg.get_imag_amp(7).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
     ///
-    /// [`Vector`]: crate::Vector
+    /// [`rotate_around_axis()`]: crate::Qureg::rotate_around_axis()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn rotate_around_axis(
+    pub fn rotate_around_axis_all(
         &mut self,
-        rot_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
         axis: &Vector,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::rotateAroundAxis(self.reg, rot_qubit, angle, axis.0);
+        if axis.norm() <= EPSILON {
+            return Err(invalid_input_error(
+                "Invalid axis vector. Must be non-zero.",
+                "rotate_around_axis_all",
+            ));
+        }
+        let angle = angle.into();
+        self.for_each_qubit_except(&[], |qureg, qubit| {
+            qureg.rotate_around_axis(qubit, angle, axis)
         })
     }
 
@@ -1901,9 +4464,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubit: i32,
         target_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("controlled_rotate_x", || unsafe {
             ffi::controlledRotateX(
                 self.reg,
                 control_qubit,
@@ -1958,9 +4522,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubit: i32,
         target_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("controlled_rotate_y", || unsafe {
             ffi::controlledRotateY(
                 self.reg,
                 control_qubit,
@@ -2015,9 +4580,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubit: i32,
         target_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("controlled_rotate_z", || unsafe {
             ffi::controlledRotateZ(
                 self.reg,
                 control_qubit,
@@ -2082,10 +4648,11 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubit: i32,
         target_qubit: i32,
-        angle: Qreal,
+        angle: impl Into<Radians>,
         axis: &Vector,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        catch_gate("controlled_rotate_around_axis", || unsafe {
             ffi::controlledRotateAroundAxis(
                 self.reg,
                 control_qubit,
@@ -2152,7 +4719,7 @@ impl<'a> Qureg<'a> {
         alpha: Qcomplex,
         beta: Qcomplex,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_compact_unitary", || unsafe {
             ffi::controlledCompactUnitary(
                 self.reg,
                 control_qubit,
@@ -2210,7 +4777,7 @@ impl<'a> Qureg<'a> {
         target_qubit: i32,
         u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_unitary", || unsafe {
             ffi::controlledUnitary(self.reg, control_qubit, target_qubit, u.0);
         })
     }
@@ -2265,8 +4832,8 @@ impl<'a> Qureg<'a> {
         target_qubit: i32,
         u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_control_qubits = checked_len(control_qubits.len())?;
+        catch_gate("multi_controlled_unitary", || unsafe {
             ffi::multiControlledUnitary(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -2313,11 +4880,110 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("pauli_x", || unsafe {
             ffi::pauliX(self.reg, target_qubit);
         })
     }
 
+    /// Apply the Pauli-X gate to every qubit, except those in `skip`.
+    ///
+    /// This is [`for_each_qubit_except()`] with [`pauli_x()`].
+    ///
+    /// # Parameters
+    ///
+    ///  - `skip`: qubits to leave unchanged
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `skip` is outside [0, [`num_qubits()`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    ///
+    /// qureg.pauli_x_except(&[1]).unwrap();
+    ///
+    /// // the register is now in the state |101>
+    /// let amp = qureg.get_real_amp(5).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`for_each_qubit_except()`]: crate::Qureg::for_each_qubit_except()
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn pauli_x_except(
+        &mut self,
+        skip: &[i32],
+    ) -> Result<(), QuestError> {
+        self.for_each_qubit_except(skip, Self::pauli_x)
+    }
+
+    /// Call a closure for every qubit, except those in `skip`.
+    ///
+    /// The closure `f` is called with `self` and the qubit index, for every
+    /// qubit in `[0, N)` not contained in `skip`, in increasing order.  This
+    /// is useful for applying a gate to the complement of a set of qubits.
+    /// The first error returned by `f` is propagated, and the remaining
+    /// qubits are not visited.
+    ///
+    /// # Parameters
+    ///
+    ///  - `skip`: qubits to leave out
+    ///  - `f`: a closure to call for every other qubit
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `skip` is outside [0, [`num_qubits()`]).  This is
+    ///     checked before `f` is called.
+    /// - any error returned by `f`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    ///
+    /// qureg
+    ///     .for_each_qubit_except(&[0], |qureg, qubit| qureg.hadamard(qubit))
+    ///     .unwrap();
+    ///
+    /// let prob = qureg.calc_prob_of_outcome(0, 0).unwrap();
+    /// assert!((prob - 1.).abs() < EPSILON);
+    /// let prob = qureg.calc_prob_of_outcome(2, 0).unwrap();
+    /// assert!((prob - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn for_each_qubit_except(
+        &mut self,
+        skip: &[i32],
+        mut f: impl FnMut(&mut Self, i32) -> Result<(), QuestError>,
+    ) -> Result<(), QuestError> {
+        let num_qubits = self.num_qubits();
+        if skip.iter().any(|&qubit| qubit < 0 || qubit >= num_qubits) {
+            return Err(invalid_input_error(
+                "Invalid qubit index. Must be >=0 and <numQubits.",
+                "for_each_qubit_except",
+            ));
+        }
+        for qubit in (0..num_qubits).filter(|qubit| !skip.contains(qubit)) {
+            f(self, qubit)?;
+        }
+        Ok(())
+    }
+
     /// Apply the single-qubit Pauli-Y gate.
     ///
     /// # Parameters
@@ -2354,7 +5020,7 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("pauli_y", || unsafe {
             ffi::pauliY(self.reg, target_qubit);
         })
     }
@@ -2395,11 +5061,83 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("pauli_z", || unsafe {
             ffi::pauliZ(self.reg, target_qubit);
         })
     }
 
+    /// Apply a tensor product of Pauli operators.
+    ///
+    /// Applies the Pauli gate `paulis[i]` to the qubit `targets[i]`, using
+    /// [`pauli_x()`], [`pauli_y()`] and [`pauli_z()`].  Identity operators
+    /// are skipped.  Note that, unlike [`multi_rotate_pauli()`], this
+    /// function applies the Pauli gates themselves, not a rotation.
+    ///
+    /// # Parameters
+    ///
+    /// - `targets`: qubits to operate on
+    /// - `paulis`: Pauli operators to apply to the corresponding qubits in
+    ///   `targets`
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `targets.len()` and `paulis.len()` are different
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `targets` is outside [0, [`num_qubits()`])
+    ///   - if `targets` contains any repetitions
+    ///
+    /// The arguments are validated before any gate is applied, so on error
+    /// the state of `qureg` is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::*;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
+    ///
+    /// qureg
+    ///     .apply_pauli_string(&[0, 1, 2], &[PAULI_X, PAULI_I, PAULI_Y])
+    ///     .unwrap();
+    ///
+    /// // The register is now in the state i|101>
+    /// let amp = qureg.get_imag_amp(5).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`pauli_y()`]: crate::Qureg::pauli_y()
+    /// [`pauli_z()`]: crate::Qureg::pauli_z()
+    /// [`multi_rotate_pauli()`]: crate::Qureg::multi_rotate_pauli()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn apply_pauli_string(
+        &mut self,
+        targets: &[i32],
+        paulis: &[PauliOpType],
+    ) -> Result<(), QuestError> {
+        if targets.len() != paulis.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        validate_unique_qubits(self, targets, "apply_pauli_string")?;
+
+        for (&target, pauli) in targets.iter().zip(paulis) {
+            match pauli {
+                PauliOpType::PAULI_I => Ok(()),
+                PauliOpType::PAULI_X => self.pauli_x(target),
+                PauliOpType::PAULI_Y => self.pauli_y(target),
+                PauliOpType::PAULI_Z => self.pauli_z(target),
+            }?;
+        }
+        Ok(())
+    }
+
     /// Apply the single-qubit Hadamard gate.
     ///
     /// This function applies the following unitary on `qubit`:
@@ -2444,7 +5182,7 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("hadamard", || unsafe {
             ffi::hadamard(self.reg, target_qubit);
         })
     }
@@ -2502,11 +5240,67 @@ impl<'a> Qureg<'a> {
         control_qubit: i32,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_not", || unsafe {
             ffi::controlledNot(self.reg, control_qubit, target_qubit);
         })
     }
 
+    /// Apply a sequence of controlled NOT gates along the edges of a graph.
+    ///
+    /// For each pair `(control_qubit, target_qubit)` in `edges`, in order,
+    /// [`controlled_not()`] is applied.  All pairs are validated before any
+    /// gate is applied, so on error the state of `qureg` is left intact.
+    ///
+    /// # Parameters
+    ///
+    /// - `edges`: a list of pairs `(control_qubit, target_qubit)`
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `edges` is outside [0, [`num_qubits()`])
+    ///   - if `control_qubit` and `target_qubit` are equal for any pair
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.pauli_x(0).unwrap();
+    ///
+    /// qureg.controlled_not_edges(&[(0, 1), (1, 2)]).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(7).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`controlled_not()`]: crate::Qureg::controlled_not()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn controlled_not_edges(
+        &mut self,
+        edges: &[(i32, i32)],
+    ) -> Result<(), QuestError> {
+        for &(control_qubit, target_qubit) in edges {
+            validate_unique_qubits(
+                self,
+                &[target_qubit],
+                "controlled_not_edges",
+            )?;
+            validate_control_qubits(
+                self,
+                &[control_qubit],
+                &[target_qubit],
+                "controlled_not_edges",
+            )?;
+        }
+        edges.iter().try_for_each(|&(control_qubit, target_qubit)| {
+            self.controlled_not(control_qubit, target_qubit)
+        })
+    }
+
     /// Apply a NOT (or Pauli X) gate with multiple control and target qubits.
     ///
     /// This applies pauliX to qubits `targs` on every basis state for which the
@@ -2552,33 +5346,175 @@ impl<'a> Qureg<'a> {
     ///     .multi_controlled_multi_qubit_not(ctrls, targs)
     ///     .unwrap();
     ///
-    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// let amp = qureg.get_real_amp(15).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [api-pauli-x]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn multi_controlled_multi_qubit_not(
+        &mut self,
+        ctrls: &[i32],
+        targs: &[i32],
+    ) -> Result<(), QuestError> {
+        let num_ctrls = checked_len(ctrls.len())?;
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("multi_controlled_multi_qubit_not", || unsafe {
+            ffi::multiControlledMultiQubitNot(
+                self.reg,
+                ctrls.as_ptr(),
+                num_ctrls,
+                targs.as_ptr(),
+                num_targs,
+            );
+        })
+    }
+
+    /// Apply a NOT (or Pauli X) gate with multiple target qubits, conditioned
+    /// on the given state of multiple control qubits.
+    ///
+    /// This is the same as [`multi_controlled_multi_qubit_not()`], except
+    /// that the qubits `targs` are flipped on the basis states in which the
+    /// control qubits `ctrls` are in the state given by `ctrl_state`, instead
+    /// of all being `|1>`.  The control qubits conditioned on `0` are flipped
+    /// with [`pauli_x()`] before and after applying
+    /// [`multi_controlled_multi_qubit_not()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `ctrls`: a list of the control qubit indices
+    /// - `ctrl_state`: the bit values (`0` or `1`) of each control qubit, upon
+    ///   which to condition
+    /// - `targs`: a list of the qubits to be targeted by the X gates
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `ctrls` and `ctrl_state` have different lengths
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `ctrls` or `targs` is empty
+    ///   - if any qubit in `ctrls` and `targs` is outside [0, [`num_qubits()`])
+    ///   - if `ctrls` or `targs` contain any repetitions
+    ///   - if any qubit in `ctrls` is also in `targs` (and vice versa)
+    ///   - if any element of `ctrl_state` is not a bit (`0` or `1`)
+    ///
+    /// All inputs are validated before the state is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(4, &env).expect("cannot allocate memory for Qureg");
+    /// // init state |0001>
+    /// qureg.pauli_x(0).unwrap();
+    ///
+    /// qureg
+    ///     .multi_state_controlled_multi_qubit_not(&[0, 1], &[1, 0], &[2, 3])
+    ///     .unwrap();
+    ///
+    /// // flipped to |1101>
+    /// let amp = qureg.get_real_amp(13).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`multi_controlled_multi_qubit_not()`]: crate::Qureg::multi_controlled_multi_qubit_not()
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn multi_state_controlled_multi_qubit_not(
+        &mut self,
+        ctrls: &[i32],
+        ctrl_state: &[i32],
+        targs: &[i32],
+    ) -> Result<(), QuestError> {
+        let err_func = "multi_state_controlled_multi_qubit_not";
+        if ctrls.len() != ctrl_state.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        if ctrls.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of control qubits. Must be >0 and <=numQubits.",
+                err_func,
+            ));
+        }
+        if targs.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of target qubits. Must be >0 and <=numQubits.",
+                err_func,
+            ));
+        }
+        validate_unique_qubits(self, targs, err_func)?;
+        validate_control_qubits(self, ctrls, targs, err_func)?;
+        validate_control_state(ctrl_state, err_func)?;
+
+        with_flipped_controls(self, ctrls, ctrl_state, |qureg| {
+            qureg.multi_controlled_multi_qubit_not(ctrls, targs)
+        })
+    }
+
+    /// Apply a Toffoli (controlled-controlled NOT) gate.
+    ///
+    /// The qubit `target_qubit` is flipped, if and only if both
+    /// `control_qubit1` and `control_qubit2` are in the `|1>` state.  This is
+    /// equivalent to:
+    ///
+    /// ```text
+    /// qureg.multi_controlled_multi_qubit_not(
+    ///     &[control_qubit1, control_qubit2],
+    ///     &[target_qubit],
+    /// )
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubit1`: the first control qubit
+    /// - `control_qubit2`: the second control qubit
+    /// - `target_qubit`: the qubit to be flipped
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any of the qubits is outside [0, [`num_qubits()`]).
+    ///   - if any two of `control_qubit1`, `control_qubit2` and `target_qubit`
+    ///     are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |011>
+    /// qureg.init_classical_state(3).unwrap();
+    /// // flip to |111>
+    /// qureg.toffoli(0, 1, 2).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(7).unwrap();
     /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [api-pauli-x]: crate::Qureg::pauli_x()
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn multi_controlled_multi_qubit_not(
+    pub fn toffoli(
         &mut self,
-        ctrls: &[i32],
-        targs: &[i32],
+        control_qubit1: i32,
+        control_qubit2: i32,
+        target_qubit: i32,
     ) -> Result<(), QuestError> {
-        let num_ctrls = ctrls.len() as i32;
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::multiControlledMultiQubitNot(
-                self.reg,
-                ctrls.as_ptr(),
-                num_ctrls,
-                targs.as_ptr(),
-                num_targs,
-            );
-        })
+        let ctrls = &[control_qubit1, control_qubit2];
+        validate_unique_qubits(self, &[target_qubit], "toffoli")?;
+        validate_control_qubits(self, ctrls, &[target_qubit], "toffoli")?;
+        self.multi_controlled_multi_qubit_not(ctrls, &[target_qubit])
     }
 
     /// Apply a NOT (or Pauli X) gate with multiple target qubits.
@@ -2633,8 +5569,8 @@ impl<'a> Qureg<'a> {
         &mut self,
         targs: &[i32],
     ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("multi_qubit_not", || unsafe {
             let targs_ptr = targs.as_ptr();
             ffi::multiQubitNot(self.reg, targs_ptr, num_targs);
         })
@@ -2693,7 +5629,7 @@ impl<'a> Qureg<'a> {
         control_qubit: i32,
         target_qubit: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_pauli_y", || unsafe {
             ffi::controlledPauliY(self.reg, control_qubit, target_qubit);
         })
     }
@@ -2760,6 +5696,49 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Calculate the probabilities of both measurement outcomes of a qubit.
+    ///
+    /// Returns `(p0, p1)`, the probabilities of measuring `qubit` in the
+    /// state `|0>` and `|1>`, respectively, without collapsing the state.
+    /// Only `p0` is computed with [`calc_prob_of_outcome()`], and `p1` is
+    /// `1 - p0`, so that the two probabilities sum up to `1`, also for
+    /// unnormalized registers.  This is the same value that
+    /// [`calc_prob_of_outcome()`] returns for the outcome `1`.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit`: qubit to study
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qubit` is outside [0, [`num_qubits()`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let (p0, p1) = qureg.qubit_outcome_probabilities(1).unwrap();
+    /// assert!((p0 - 0.5).abs() < EPSILON);
+    /// assert!((p1 - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`calc_prob_of_outcome()`]: crate::Qureg::calc_prob_of_outcome()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn qubit_outcome_probabilities(
+        &self,
+        qubit: i32,
+    ) -> Result<(Qreal, Qreal), QuestError> {
+        let p0 = self.calc_prob_of_outcome(qubit, 0)?;
+        Ok((p0, 1. - p0))
+    }
+
     /// Calculate probabilities of every outcome of the sub-register.
     ///
     /// This function populates `outcome_probs` with the probabilities of every
@@ -2829,7 +5808,7 @@ impl<'a> Qureg<'a> {
         if outcome_probs.len() < 1 << qubits.len() {
             return Err(QuestError::ArrayLengthError);
         }
-        let num_qubits = qubits.len() as i32;
+        let num_qubits = checked_len(qubits.len())?;
         let outcome_probs_ptr = outcome_probs.as_mut_ptr();
         catch_quest_exception(|| unsafe {
             ffi::calcProbOfAllOutcomes(
@@ -2841,6 +5820,207 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Populate a new vector with the probabilities of every outcome of the
+    /// sub-register `qubits`.
+    ///
+    /// This is an allocating variant of [`calc_prob_of_all_outcomes()`]. The
+    /// returned vector has length `1 << qubits.len()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: a list of qubits to study
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any index in `qubits` is invalid, i.e. outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `qubits` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(2).unwrap();
+    ///
+    /// let outcome_probs = qureg.prob_of_all_outcomes(&[1, 2]).unwrap();
+    /// assert_eq!(outcome_probs, vec![0., 1., 0., 0.]);
+    /// ```
+    ///
+    /// [`calc_prob_of_all_outcomes()`]: crate::Qureg::calc_prob_of_all_outcomes()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn prob_of_all_outcomes(
+        &self,
+        qubits: &[i32],
+    ) -> Result<Vec<Qreal>, QuestError> {
+        validate_unique_qubits(self, qubits, "prob_of_all_outcomes")?;
+        let mut outcome_probs = vec![0.; 1 << qubits.len()];
+        self.calc_prob_of_all_outcomes(&mut outcome_probs, qubits)?;
+        Ok(outcome_probs)
+    }
+
+    /// Find the most probable computational basis state.
+    ///
+    /// Returns the index of the basis state with the largest probability of
+    /// being measured, together with that probability.  For state-vectors,
+    /// this is the probability `|amp|^2` of the amplitude; for density
+    /// matrices, the real part of the diagonal element.  If several basis
+    /// states are equally probable, the smallest index is returned.
+    ///
+    /// The probabilities are read one by one with [`get_prob_amp()`], or from
+    /// the diagonal of a density matrix with [`get_density_amp()`], so no
+    /// memory is allocated.  In distributed mode, each amplitude is
+    /// broadcast to all nodes, and every node returns the same global
+    /// maximum.  This function must then be called collectively by all nodes.
+    ///
+    /// Probabilities that are `NaN` are skipped.  If every probability is
+    /// `NaN`, `(0, NaN)` is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error only if reading the amplitudes fails,
+    /// which should not happen for a valid register.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(5).unwrap();
+    /// qureg.rotate_y(1, 0.5).unwrap();
+    ///
+    /// let (index, prob) = qureg.argmax_probability().unwrap();
+    /// assert_eq!(index, 5);
+    /// assert!((prob - 0.25_f64.cos().powi(2) as Qreal).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`get_prob_amp()`]: crate::Qureg::get_prob_amp()
+    /// [`get_density_amp()`]: crate::Qureg::get_density_amp()
+    pub fn argmax_probability(&self) -> Result<(i64, Qreal), QuestError> {
+        let mut best = (0, Qreal::NAN);
+        for index in 0..1 << self.num_qubits() {
+            let prob = match self.kind() {
+                QuregKind::StateVector => self.get_prob_amp(index)?,
+                QuregKind::DensityMatrix => {
+                    self.get_density_amp(index, index)?.re
+                }
+            };
+            // Comparisons with NaN are false, so NaN never replaces a number
+            if prob > best.1 || (best.1.is_nan() && !prob.is_nan()) {
+                best = (index, prob);
+            }
+        }
+        Ok(best)
+    }
+
+    /// Compute the marginal distribution of measurement outcomes of the
+    /// sub-register `qubits`.
+    ///
+    /// This is the same as [`prob_of_all_outcomes()`], but the
+    /// probabilities are returned together with the qubit labels as a
+    /// [`Distribution`].
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: a list of qubits to study
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any index in `qubits` is invalid, i.e. outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `qubits` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(2).unwrap();
+    ///
+    /// let dist = qureg.marginal(&[1, 2]).unwrap();
+    /// assert_eq!(dist.qubits(), &[1, 2]);
+    /// assert_eq!(dist.probs(), &[0., 1., 0., 0.]);
+    /// ```
+    ///
+    /// [`prob_of_all_outcomes()`]: crate::Qureg::prob_of_all_outcomes()
+    /// [`Distribution`]: crate::Distribution
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn marginal(
+        &self,
+        qubits: &[i32],
+    ) -> Result<Distribution, QuestError> {
+        let probs = self.prob_of_all_outcomes(qubits)?;
+        Ok(Distribution::new(qubits.to_vec(), probs))
+    }
+
+    /// Calculate the total probability of the sub-register `qubits` being in
+    /// any state.
+    ///
+    /// This is the sum of the probabilities returned by
+    /// [`prob_of_all_outcomes()`].  For a normalized register, it is `1` (up
+    /// to numerical precision).  For an unnormalized one, e.g. after
+    /// [`apply_projector()`] or an intermediate step of a state preparation
+    /// built with [`set_weighted_qureg()`], it is the total probability mass
+    /// of the register, and equals [`calc_total_prob()`] regardless of the
+    /// choice of `qubits`, since tracing out the remaining qubits does not
+    /// change the total mass.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: a non-empty list of qubits to study
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qubits` is empty
+    ///   - if any index in `qubits` is invalid, i.e. outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `qubits` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    /// qureg.apply_projector(0, 1).unwrap();
+    ///
+    /// let prob = qureg.subsystem_prob(&[1, 2]).unwrap();
+    /// assert!((prob - 0.5).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`prob_of_all_outcomes()`]: crate::Qureg::prob_of_all_outcomes()
+    /// [`apply_projector()`]: crate::Qureg::apply_projector()
+    /// [`set_weighted_qureg()`]: crate::set_weighted_qureg()
+    /// [`calc_total_prob()`]: crate::Qureg::calc_total_prob()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn subsystem_prob(
+        &self,
+        qubits: &[i32],
+    ) -> Result<Qreal, QuestError> {
+        if qubits.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of qubits. Must be >0 and <=numQubits.",
+                "subsystem_prob",
+            ));
+        }
+        let probs = self.prob_of_all_outcomes(qubits)?;
+        Ok(probs.iter().sum())
+    }
+
     /// Updates `qureg` to be consistent with measuring qubit in the given
     /// outcome.
     ///
@@ -3024,13 +6204,195 @@ impl<'a> Qureg<'a> {
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn measure_with_stats(
         &mut self,
-        measure_qubit: i32,
-        outcome_prob: &mut Qreal,
-    ) -> Result<i32, QuestError> {
-        let outcome_prob_ptr = outcome_prob as *mut _;
-        catch_quest_exception(|| unsafe {
-            ffi::measureWithStats(self.reg, measure_qubit, outcome_prob_ptr)
-        })
+        measure_qubit: i32,
+        outcome_prob: &mut Qreal,
+    ) -> Result<i32, QuestError> {
+        let outcome_prob_ptr = outcome_prob as *mut _;
+        catch_quest_exception(|| unsafe {
+            ffi::measureWithStats(self.reg, measure_qubit, outcome_prob_ptr)
+        })
+    }
+
+    /// Measures a single qubit, using the provided random number generator.
+    ///
+    /// This is the same as [`measure()`], except that the outcome is drawn
+    /// from `rng` instead of `QuEST`'s internal generator.  The probability of
+    /// outcome `0` is computed with [`calc_prob_of_outcome()`], a random number
+    /// is drawn uniformly from `[0, 1)` to choose the outcome, and then the
+    /// state is collapsed with [`collapse_to_outcome()`].  This makes the
+    /// measurement outcomes reproducible, without modifying the global seed
+    /// set by [`seed_quest()`].
+    ///
+    /// An outcome whose probability is numerically indistinguishable from
+    /// zero is never chosen.
+    ///
+    /// In distributed mode, all the nodes must draw the same random number,
+    /// i.e. `rng` must be seeded identically on every node.
+    ///
+    /// This function is available only with the feature `"rand"` enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `measure_qubit`: index of a qubit to measure
+    /// - `rng`: random number generator used to choose the outcome
+    ///
+    /// # Returns
+    ///
+    /// The measurement outcome, `0` or `1`.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `measure_qubit` is outside `[0, N)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use rand::{
+    ///     rngs::StdRng,
+    ///     SeedableRng,
+    /// };
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let mut measure_bell = |seed| {
+    ///     let rng = &mut StdRng::seed_from_u64(seed);
+    ///     qureg.init_zero_state();
+    ///     qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    ///     let outcome1 = qureg.measure_with_rng(0, rng).unwrap();
+    ///     let outcome2 = qureg.measure_with_rng(1, rng).unwrap();
+    ///     assert_eq!(outcome1, outcome2);
+    ///     outcome1
+    /// };
+    ///
+    /// // The same seed gives the same outcome
+    /// assert_eq!(measure_bell(123), measure_bell(123));
+    /// ```
+    ///
+    /// [`measure()`]: crate::Qureg::measure()
+    /// [`calc_prob_of_outcome()`]: crate::Qureg::calc_prob_of_outcome()
+    /// [`collapse_to_outcome()`]: crate::Qureg::collapse_to_outcome()
+    /// [`seed_quest()`]: crate::seed_quest()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    #[cfg(feature = "rand")]
+    pub fn measure_with_rng(
+        &mut self,
+        measure_qubit: i32,
+        rng: &mut impl rand::Rng,
+    ) -> Result<i32, QuestError> {
+        let prob0 = self.calc_prob_of_outcome(measure_qubit, 0)?;
+        let mut outcome = i32::from(rng.gen::<Qreal>() >= prob0);
+        let prob = if outcome == 0 { prob0 } else { 1. - prob0 };
+        if prob < EPSILON.sqrt() {
+            outcome = 1 - outcome;
+        }
+        self.collapse_to_outcome(measure_qubit, outcome)?;
+        Ok(outcome)
+    }
+
+    /// Measure every qubit of the register, one after another, and record
+    /// the probability of each outcome.
+    ///
+    /// Qubits are measured in order `0, 1, ..., N-1` with
+    /// [`measure_with_stats()`].  Each measurement collapses the state, hence
+    /// the probability recorded for qubit `k` is the probability of its
+    /// outcome *conditioned* on the outcomes of qubits `0, ..., k-1`.  The
+    /// product of all the recorded probabilities is the probability of the
+    /// whole measurement trajectory, i.e. of obtaining the final basis state.
+    ///
+    /// After this call, the register is in the basis state given by the
+    /// outcomes.
+    ///
+    /// # Returns
+    ///
+    /// A pair of vectors of length [`num_qubits()`]: the measurement outcomes
+    /// (`0` or `1`) and the conditional probabilities of these outcomes,
+    /// indexed by qubit.
+    ///
+    /// # Errors
+    ///
+    /// This function does not fail for a valid register.  Any error
+    /// reported by [`measure_with_stats()`] is propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // Prepare an entangled state `|00> + |11>`
+    /// qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    ///
+    /// let (outcomes, probs) = qureg.measure_all_with_probs().unwrap();
+    /// assert_eq!(outcomes[0], outcomes[1]);
+    /// assert!((probs[0] - 0.5).abs() < EPSILON);
+    /// assert!((probs[1] - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`measure_with_stats()`]: crate::Qureg::measure_with_stats()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn measure_all_with_probs(
+        &mut self
+    ) -> Result<(Vec<i32>, Vec<Qreal>), QuestError> {
+        let num_qubits = self.num_qubits();
+        let mut outcomes = Vec::with_capacity(num_qubits as usize);
+        let mut probs = Vec::with_capacity(num_qubits as usize);
+        for qubit in 0..num_qubits {
+            let mut prob = 0.;
+            outcomes.push(self.measure_with_stats(qubit, &mut prob)?);
+            probs.push(prob);
+        }
+        Ok((outcomes, probs))
+    }
+
+    /// Reset a qubit to the `|0>` state.
+    ///
+    /// The qubit is measured and then flipped with [`pauli_x()`], if the
+    /// outcome was `1`.  Since this involves a measurement, any entanglement
+    /// between `target_qubit` and the rest of the register is destroyed:
+    /// the remaining qubits collapse to a state consistent with the
+    /// measurement outcome.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: index of a qubit to reset
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`])
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.reset_qubit(0).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(2).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`pauli_x()`]: crate::Qureg::pauli_x()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn reset_qubit(
+        &mut self,
+        target_qubit: i32,
+    ) -> Result<(), QuestError> {
+        if self.measure(target_qubit)? == 1 {
+            self.pauli_x(target_qubit)?;
+        }
+        Ok(())
     }
 
     /// Enable QASM recording.
@@ -3101,6 +6463,71 @@ impl<'a> Qureg<'a> {
         .expect("stop_recording_qasm should always succeed");
     }
 
+    /// Check if QASM recording is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// assert!(!qureg.is_recording_qasm());
+    ///
+    /// qureg.start_recording_qasm();
+    /// assert!(qureg.is_recording_qasm());
+    /// ```
+    #[must_use]
+    pub fn is_recording_qasm(&self) -> bool {
+        // SAFETY: `qasmLog` is allocated by `QuEST` together with the register
+        // and stays valid until the register is destroyed.
+        unsafe { (*self.reg.qasmLog.get()).isLogging != 0 }
+    }
+
+    /// Record QASM while running a closure.
+    ///
+    /// Starts QASM recording, calls `f` on `qureg` and stops the recording
+    /// afterwards.  The recording is stopped also if `f` panics, so that
+    /// the calls to [`start_recording_qasm()`] and [`stop_recording_qasm()`]
+    /// are always balanced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg
+    ///     .with_qasm_recording(|qureg| {
+    ///         qureg.hadamard(0).and(qureg.controlled_not(0, 1))
+    ///     })
+    ///     .unwrap();
+    /// assert!(!qureg.is_recording_qasm());
+    ///
+    /// qureg.print_recorded_qasm();
+    /// ```
+    ///
+    /// [`start_recording_qasm()`]: Qureg::start_recording_qasm()
+    /// [`stop_recording_qasm()`]: Qureg::stop_recording_qasm()
+    pub fn with_qasm_recording<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        struct Guard<'q, 'a>(&'q mut Qureg<'a>);
+
+        impl Drop for Guard<'_, '_> {
+            fn drop(&mut self) {
+                self.0.stop_recording_qasm();
+            }
+        }
+
+        self.start_recording_qasm();
+        let guard = Guard(self);
+        f(guard.0)
+    }
+
     /// Clear all QASM so far recorded.
     ///
     /// This does not start or stop recording.
@@ -3232,7 +6659,8 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
     ///   - if `prob` is not in `[0, 1/2]`
     ///
@@ -3255,6 +6683,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -3262,9 +6691,10 @@ impl<'a> Qureg<'a> {
     pub fn mix_dephasing(
         &mut self,
         target_qubit: i32,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_dephasing", || unsafe {
             ffi::mixDephasing(self.reg, target_qubit, prob);
         })
     }
@@ -3295,7 +6725,8 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
     ///   - if `qubit1 = qubit2`
     ///   - if `prob` is not in `[0, 3/4]`
@@ -3319,6 +6750,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -3327,9 +6759,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         qubit1: i32,
         qubit2: i32,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_two_qubit_dephasing", || unsafe {
             ffi::mixTwoQubitDephasing(self.reg, qubit1, qubit2, prob);
         })
     }
@@ -3360,7 +6793,8 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
     ///   - if `prob` is not in `[0, 3/4]`
     ///
@@ -3380,6 +6814,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -3387,9 +6822,10 @@ impl<'a> Qureg<'a> {
     pub fn mix_depolarising(
         &mut self,
         target_qubit: i32,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_depolarising", || unsafe {
             ffi::mixDepolarising(self.reg, target_qubit, prob);
         })
     }
@@ -3425,7 +6861,8 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
     ///   - if `prob` is not in `[0, 1]`
     ///
@@ -3446,6 +6883,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`mix_dephasing()`]: crate::Qureg::mix_dephasing()
     /// [`mix_depolarising()`]: crate::Qureg::mix_depolarising()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
@@ -3455,9 +6893,10 @@ impl<'a> Qureg<'a> {
     pub fn mix_damping(
         &mut self,
         target_qubit: i32,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_damping", || unsafe {
             ffi::mixDamping(self.reg, target_qubit, prob);
         })
     }
@@ -3483,7 +6922,8 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `qubit1` or `qubit2` are outside [0, [`num_qubits()`]).
     ///   - if `qubit1 = qubit2`
     ///   - if `prob` is not in `[0, 15/16]`
@@ -3507,6 +6947,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -3515,9 +6956,10 @@ impl<'a> Qureg<'a> {
         &mut self,
         qubit1: i32,
         qubit2: i32,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_two_qubit_depolarising", || unsafe {
             ffi::mixTwoQubitDepolarising(self.reg, qubit1, qubit2, prob);
         })
     }
@@ -3540,12 +6982,16 @@ impl<'a> Qureg<'a> {
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qureg` is not a density matrix
+    ///   - if `qureg` is not a density matrix.  Use [`density_from_pure()`] to
+    ///     promote a state-vector first.
     ///   - if `target_qubit` is outside [0, [`num_qubits()`])
     ///   - if any of `prob_x`, `prob_y`, `prob_z` are not in `[0, 1]`
     ///   - if any of p in `{prob_x, prob_y or prob_z}` don't satisfy `p <= (1 -
     ///     prob_x - prob_y - prob_z)`
     ///
+    /// The probabilities are validated before calling `QuEST`, and the error
+    /// message names the violated constraint.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -3565,6 +7011,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`density_from_pure()`]: crate::Qureg::density_from_pure()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -3572,11 +7019,15 @@ impl<'a> Qureg<'a> {
     pub fn mix_pauli(
         &mut self,
         target_qubit: i32,
-        prob_x: Qreal,
-        prob_y: Qreal,
-        prob_z: Qreal,
+        prob_x: impl Into<ProbArg>,
+        prob_y: impl Into<ProbArg>,
+        prob_z: impl Into<ProbArg>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob_x = prob_x.into().0;
+        let prob_y = prob_y.into().0;
+        let prob_z = prob_z.into().0;
+        validate_pauli_probs(prob_x, prob_y, prob_z, "mix_pauli")?;
+        catch_gate("mix_pauli", || unsafe {
             ffi::mixPauli(self.reg, target_qubit, prob_x, prob_y, prob_z);
         })
     }
@@ -3622,10 +7073,11 @@ impl<'a> Qureg<'a> {
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_density_matrix(
         &mut self,
-        prob: Qreal,
+        prob: impl Into<ProbArg>,
         other_qureg: &Qureg<'_>,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        let prob = prob.into().0;
+        catch_gate("mix_density_matrix", || unsafe {
             ffi::mixDensityMatrix(self.reg, prob, other_qureg.reg);
         })
     }
@@ -3699,6 +7151,8 @@ impl<'a> Qureg<'a> {
     /// the correct linear algebra calculation.
     ///
     /// The number of qubits represented in `qureg` and `pure_state` must match.
+    /// To compute the fidelity against a density matrix, see
+    /// [`fidelity_with()`].
     ///
     /// # Parameters
     ///
@@ -3733,6 +7187,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`fidelity_with()`]: crate::Qureg::fidelity_with()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     pub fn calc_fidelity(
@@ -3744,6 +7199,132 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Calculates the fidelity of two registers, at least one of which is
+    /// pure.
+    ///
+    /// Unlike [`calc_fidelity()`], `other` can be a density matrix:
+    ///
+    /// - If `other` is a state-vector, this is the same as [`calc_fidelity()`].
+    /// - If `other` is a density matrix and `self` is a state-vector, this
+    ///   computes `<self|other|self>`.
+    /// - If both are density matrices, at least one of them must be pure (i.e.
+    ///   have purity 1, up to numerical precision).  The fidelity is then
+    ///   `Tr(self other)`, computed by [`calc_density_inner_product()`].
+    ///
+    /// The fidelity of two mixed states is not supported.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: a state-vector or a density matrix
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the number of qubits in `self` and `other` do not match
+    ///   - if both `self` and `other` are mixed density matrices
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// let mut other = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// other.init_plus_state();
+    ///
+    /// let fidelity = qureg.fidelity_with(&other).unwrap();
+    /// assert!((fidelity - 0.25).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`calc_fidelity()`]: crate::Qureg::calc_fidelity()
+    /// [`calc_density_inner_product()`]: crate::calc_density_inner_product()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn fidelity_with(
+        &self,
+        other: &Qureg<'_>,
+    ) -> Result<Qreal, QuestError> {
+        match (self.kind(), other.kind()) {
+            (_, QuregKind::StateVector) => self.calc_fidelity(other),
+            (QuregKind::StateVector, QuregKind::DensityMatrix) => {
+                other.calc_fidelity(self)
+            }
+            (QuregKind::DensityMatrix, QuregKind::DensityMatrix) => {
+                if self.num_qubits() != other.num_qubits() {
+                    return Err(invalid_input_error(
+                        "Dimensions of the qubit registers don't match.",
+                        "fidelity_with",
+                    ));
+                }
+                let tol = EPSILON.sqrt();
+                if (self.calc_purity()? - 1.).abs() > tol
+                    && (other.calc_purity()? - 1.).abs() > tol
+                {
+                    return Err(invalid_input_error(
+                        "Fidelity of two mixed states is not supported. At \
+                         least one of the density matrices must be pure.",
+                        "fidelity_with",
+                    ));
+                }
+                calc_density_inner_product(self, other)
+            }
+        }
+    }
+
+    /// Calculate the loss of fidelity with respect to a pure reference state.
+    ///
+    /// This is `1 - F`, where `F` is the fidelity computed by
+    /// [`calc_fidelity()`].  It is useful for characterizing noise channels:
+    /// after applying e.g. [`mix_depolarising()`] to a density matrix
+    /// initialized to the `reference` state, this measures how far the
+    /// register has drifted away from it.
+    ///
+    /// # Parameters
+    ///
+    /// - `reference`: a state-vector
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `reference` is not a state-vector
+    ///   - if the number of qubits in `self` and `reference` do not match
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut reference =
+    ///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+    /// let mut rho = Qureg::try_new_density(1, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// reference.init_plus_state();
+    ///
+    /// let mut last_loss = 0.;
+    /// for prob in [0.1, 0.2, 0.4] {
+    ///     rho.init_pure_state(&reference).unwrap();
+    ///     rho.mix_depolarising(0, prob).unwrap();
+    ///
+    ///     // the loss is 2/3 * prob
+    ///     let loss = rho.fidelity_loss(&reference).unwrap();
+    ///     assert!((loss - 2. / 3. * prob).abs() < EPSILON);
+    ///     assert!(loss > last_loss);
+    ///     last_loss = loss;
+    /// }
+    /// ```
+    ///
+    /// [`calc_fidelity()`]: crate::Qureg::calc_fidelity()
+    /// [`mix_depolarising()`]: crate::Qureg::mix_depolarising()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn fidelity_loss(
+        &self,
+        reference: &Qureg<'_>,
+    ) -> Result<Qreal, QuestError> {
+        validate_kind(reference, QuregKind::StateVector, "fidelity_loss")?;
+        self.calc_fidelity(reference).map(|fidelity| 1. - fidelity)
+    }
+
     /// Performs a SWAP gate between `qubit1` and `qubit2`.
     ///
     /// This effects
@@ -3799,7 +7380,7 @@ impl<'a> Qureg<'a> {
         qubit1: i32,
         qubit2: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("swap_gate", || unsafe {
             ffi::swapGate(self.reg, qubit1, qubit2);
         })
     }
@@ -3856,11 +7437,175 @@ impl<'a> Qureg<'a> {
         qb1: i32,
         qb2: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("sqrt_swap_gate", || unsafe {
             ffi::sqrtSwapGate(self.reg, qb1, qb2);
         })
     }
 
+    /// Performs a controlled SWAP (Fredkin) gate.
+    ///
+    /// The qubits `qubit1` and `qubit2` are swapped, if and only if
+    /// `control_qubit` is in the `|1>` state.  The gate is performed
+    /// internally by two controlled NOT gates and a [Toffoli gate][toffoli].
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubit`: the control qubit
+    /// - `qubit1`: qubit to swap
+    /// - `qubit2`: other qubit to swap
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any of `control_qubit`, `qubit1` or `qubit2` is outside [0,
+    ///     [`num_qubits()`]).
+    ///   - if any two of `control_qubit`, `qubit1` and `qubit2` are equal
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |011>
+    /// qureg.init_classical_state(3).unwrap();
+    /// // swap to |101>
+    /// qureg.controlled_swap(0, 1, 2).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(5).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [toffoli]: crate::Qureg::toffoli()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn controlled_swap(
+        &mut self,
+        control_qubit: i32,
+        qubit1: i32,
+        qubit2: i32,
+    ) -> Result<(), QuestError> {
+        validate_unique_qubits(self, &[qubit1, qubit2], "controlled_swap")?;
+        validate_control_qubits(
+            self,
+            &[control_qubit],
+            &[qubit1, qubit2],
+            "controlled_swap",
+        )?;
+        self.controlled_not(qubit2, qubit1)?;
+        self.toffoli(control_qubit, qubit1, qubit2)?;
+        self.controlled_not(qubit2, qubit1)
+    }
+
+    /// Relabel the qubits of the register.
+    ///
+    /// The amplitudes of `qureg` are rearranged so that the qubit `q` becomes
+    /// the qubit `permutation[q]`.  No gates are applied: this function merely
+    /// permutes amplitudes (or density matrix elements) in memory, and so it
+    /// is not recorded in QASM.
+    ///
+    /// # Parameters
+    ///
+    /// - `permutation`: a permutation of the qubit indices `0..num_qubits()`
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `permutation.len()` is not equal to [`num_qubits()`]
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any index in `permutation` is outside [0, [`num_qubits()`])
+    ///   - if `permutation` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |001>
+    /// qureg.init_classical_state(1).unwrap();
+    /// // move qubit 0 to position 2
+    /// qureg.permute_qubits(&[2, 0, 1]).unwrap();
+    ///
+    /// // the register is now in the state |100>
+    /// let amp = qureg.get_real_amp(4).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn permute_qubits(
+        &mut self,
+        permutation: &[i32],
+    ) -> Result<(), QuestError> {
+        if permutation.len() != self.num_qubits() as usize {
+            return Err(QuestError::ArrayLengthError);
+        }
+        validate_unique_qubits(self, permutation, "permute_qubits")?;
+
+        let dim = 1_i64 << self.num_qubits();
+        if !self.is_density_matrix() {
+            let mut reals = vec![0.; dim as usize];
+            let mut imags = vec![0.; dim as usize];
+            for i in 0..dim {
+                let amp = self.get_amp(i)?;
+                let j = scatter_bits(i, permutation) as usize;
+                reals[j] = amp.re;
+                imags[j] = amp.im;
+            }
+            return self.init_state_from_amps(&reals, &imags);
+        }
+
+        let mut reals = vec![0.; (dim * dim) as usize];
+        let mut imags = vec![0.; (dim * dim) as usize];
+        for col in 0..dim {
+            for row in 0..dim {
+                let amp = self.get_density_amp(row, col)?;
+                // Amplitudes of a density matrix are stored column by column
+                let j = (scatter_bits(row, permutation)
+                    + scatter_bits(col, permutation) * dim)
+                    as usize;
+                reals[j] = amp.re;
+                imags[j] = amp.im;
+            }
+        }
+        self.set_density_amps(0, 0, &reals, &imags)
+    }
+
+    /// Reverse the order of qubits in the register.
+    ///
+    /// The qubit `q` becomes the qubit `num_qubits() - 1 - q`.  This is useful
+    /// for exchanging states with software that uses the opposite endianness
+    /// convention.  See [`permute_qubits()`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // init state |011>
+    /// qureg.init_classical_state(3).unwrap();
+    /// qureg.reverse_qubit_order().unwrap();
+    ///
+    /// // the register is now in the state |110>
+    /// let amp = qureg.get_real_amp(6).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`permute_qubits()`]: crate::Qureg::permute_qubits()
+    pub fn reverse_qubit_order(&mut self) -> Result<(), QuestError> {
+        let permutation = (0..self.num_qubits()).rev().collect::<Vec<_>>();
+        self.permute_qubits(&permutation)
+    }
+
     /// Apply a general single-qubit unitary with multiple control qubits.
     ///
     /// The operation is conditioned upon a specific bit sequence:
@@ -3934,8 +7679,8 @@ impl<'a> Qureg<'a> {
         target_qubit: i32,
         u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_control_qubits = checked_len(control_qubits.len())?;
+        catch_gate("multi_state_controlled_unitary", || unsafe {
             ffi::multiStateControlledUnitary(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -4008,10 +7753,11 @@ impl<'a> Qureg<'a> {
     pub fn multi_rotate_z(
         &mut self,
         qubits: &[i32],
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        let num_qubits = qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        let num_qubits = checked_len(qubits.len())?;
+        catch_gate("multi_rotate_z", || unsafe {
             ffi::multiRotateZ(self.reg, qubits.as_ptr(), num_qubits, angle);
         })
     }
@@ -4033,6 +7779,10 @@ impl<'a> Qureg<'a> {
     /// basis, performing [`multi_rotate_z()`] on all target qubits, then
     /// restoring the original basis.
     ///
+    /// Note the factor `1/2` in the exponent.  To apply `exp(-i angle P)`
+    /// for a Pauli string `P` directly, e.g. in a product formula, use
+    /// [`apply_pauli_exponential()`].
+    ///
     ///  # Parameters
     ///
     /// - `target_qubits`: a list of the indices of the target qubits
@@ -4073,6 +7823,7 @@ impl<'a> Qureg<'a> {
     ///
     /// [`PauliOpType`]: crate::PauliOpType
     /// [`multi_rotate_z()`]: Qureg::multi_rotate_z()
+    /// [`apply_pauli_exponential()`]: crate::Qureg::apply_pauli_exponential()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -4081,18 +7832,89 @@ impl<'a> Qureg<'a> {
         &mut self,
         target_qubits: &[i32],
         target_paulis: &[PauliOpType],
-        angle: Qreal,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        let num_targets = checked_len(target_qubits.len())?;
+        catch_gate("multi_rotate_pauli", || unsafe {
+            ffi::multiRotatePauli(
+                self.reg,
+                target_qubits.as_ptr(),
+                target_paulis.as_ptr(),
+                num_targets,
+                angle,
+            );
+        })
+    }
+
+    /// Apply the exponential of a Pauli string.
+    ///
+    /// This is the unitary
+    ///
+    /// ```text
+    /// exp(-i angle P),    P = paulis[0] ⊗ paulis[1] ⊗ ...
+    /// ```
+    ///
+    /// where `paulis[j]` acts on the qubit `targets[j]`, and all the other
+    /// qubits receive the identity.  This is the building block of
+    /// Hamiltonian simulation by product formulas: a single Trotter step
+    /// applies one such exponential for every term of the Hamiltonian.
+    ///
+    /// This is the same operation as [`multi_rotate_pauli()`] with the angle
+    /// `2 * angle`, since the latter has the factor `1/2` in the exponent.
+    /// To exponentiate a whole [`PauliHamil`], see
+    /// [`apply_trotter_circuit()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `targets`: a list of the indices of the target qubits
+    /// - `paulis`: the Pauli operators acting on the corresponding qubits in
+    ///   `targets`
+    /// - `angle`: the coefficient `angle` in the exponent
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `targets` and `paulis` have different lengths
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `targets` is outside [0, [`num_qubits()`])
+    ///   - if any qubit in `targets` is repeated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// // exp(-i pi/2 XX) |00> = -i |11>
+    /// qureg
+    ///     .apply_pauli_exponential(&[0, 1], &[PAULI_X, PAULI_X], PI / 2.)
+    ///     .unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp + 1.).abs() < 2. * EPSILON);
+    /// ```
+    ///
+    /// [`multi_rotate_pauli()`]: crate::Qureg::multi_rotate_pauli()
+    /// [`apply_trotter_circuit()`]: crate::Qureg::apply_trotter_circuit()
+    /// [`PauliHamil`]: crate::PauliHamil
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn apply_pauli_exponential(
+        &mut self,
+        targets: &[i32],
+        paulis: &[PauliOpType],
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        let num_targets = target_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::multiRotatePauli(
-                self.reg,
-                target_qubits.as_ptr(),
-                target_paulis.as_ptr(),
-                num_targets,
-                angle,
-            );
-        })
+        if targets.len() != paulis.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        self.multi_rotate_pauli(targets, paulis, 2. * angle.into().0)
     }
 
     /// Apply a multi-controlled multi-target Z rotation.
@@ -4153,11 +7975,12 @@ impl<'a> Qureg<'a> {
         &mut self,
         control_qubits: &[i32],
         target_qubits: &[i32],
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        let num_controls = control_qubits.len() as i32;
-        let num_targets = target_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        let num_controls = checked_len(control_qubits.len())?;
+        let num_targets = checked_len(target_qubits.len())?;
+        catch_gate("multi_controlled_multi_rotate_z", || unsafe {
             ffi::multiControlledMultiRotateZ(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -4239,11 +8062,12 @@ impl<'a> Qureg<'a> {
         control_qubits: &[i32],
         target_qubits: &[i32],
         target_paulis: &[PauliOpType],
-        angle: Qreal,
+        angle: impl Into<Radians>,
     ) -> Result<(), QuestError> {
-        let num_controls = control_qubits.len() as i32;
-        let num_targets = target_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let angle = angle.into().0;
+        let num_controls = checked_len(control_qubits.len())?;
+        let num_targets = checked_len(target_qubits.len())?;
+        catch_gate("multi_controlled_multi_rotate_pauli", || unsafe {
             ffi::multiControlledMultiRotatePauli(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -4256,6 +8080,167 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Applies a multi-controlled rotation by a given angle around the
+    /// X-axis of the Bloch-sphere.
+    ///
+    /// The target qubit is rotated in states where all the control qubits have
+    /// value `1`.  This is equivalent to [`rotate_x()`] with multiple
+    /// controls.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: qubits which have value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if any qubit in `control_qubits`, or `target_qubit` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubits` contains any repetitions
+    ///   - if `target_qubit` is in `control_qubits`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.multi_controlled_rotate_x(&[0, 1], 2, PI).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(7).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`rotate_x()`]: crate::Qureg::rotate_x()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn multi_controlled_rotate_x(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        self.multi_controlled_multi_rotate_pauli(
+            control_qubits,
+            &[target_qubit],
+            &[PauliOpType::PAULI_X],
+            angle,
+        )
+    }
+
+    /// Applies a multi-controlled rotation by a given angle around the
+    /// Y-axis of the Bloch-sphere.
+    ///
+    /// The target qubit is rotated in states where all the control qubits have
+    /// value `1`.  This is equivalent to [`rotate_y()`] with multiple
+    /// controls.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: qubits which have value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if any qubit in `control_qubits`, or `target_qubit` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubits` contains any repetitions
+    ///   - if `target_qubit` is in `control_qubits`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.multi_controlled_rotate_y(&[0, 1], 2, PI).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(7).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`rotate_y()`]: crate::Qureg::rotate_y()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn multi_controlled_rotate_y(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        self.multi_controlled_multi_rotate_pauli(
+            control_qubits,
+            &[target_qubit],
+            &[PauliOpType::PAULI_Y],
+            angle,
+        )
+    }
+
+    /// Applies a multi-controlled rotation by a given angle around the
+    /// Z-axis of the Bloch-sphere.
+    ///
+    /// The target qubit is rotated in states where all the control qubits have
+    /// value `1`.  This is equivalent to [`rotate_z()`] with multiple
+    /// controls.
+    ///
+    /// # Parameters
+    ///
+    /// - `control_qubits`: qubits which have value `1` in the rotated states
+    /// - `target_qubit`: qubit to rotate
+    /// - `angle`: angle by which to rotate the target qubit in radians
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`]
+    ///   - if any qubit in `control_qubits`, or `target_qubit` is outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `control_qubits` contains any repetitions
+    ///   - if `target_qubit` is in `control_qubits`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(3).unwrap();
+    ///
+    /// qureg.multi_controlled_rotate_z(&[0, 1], 2, PI).unwrap();
+    ///
+    /// let amp = qureg.get_imag_amp(3).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`rotate_z()`]: crate::Qureg::rotate_z()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn multi_controlled_rotate_z(
+        &mut self,
+        control_qubits: &[i32],
+        target_qubit: i32,
+        angle: impl Into<Radians>,
+    ) -> Result<(), QuestError> {
+        let angle = angle.into().0;
+        self.multi_controlled_multi_rotate_z(
+            control_qubits,
+            &[target_qubit],
+            angle,
+        )
+    }
+
     /// Computes the expected value of a product of Pauli operators.
     ///
     /// Letting `$\sigma = \otimes_j \hat{\sigma}_j$` be the operators
@@ -4290,95 +8275,323 @@ impl<'a> Qureg<'a> {
     /// qureg.calc_expec_pauli_prod(&[4,5,6], &[PAULI_X, PAULI_I, PAULI_Z], &mut workspace);
     /// ```
     ///
-    /// will compute `$ \langle \psi | I I I I X I Z | \psi \rangle $` (where in
-    /// this notation, the left-most operator applies to the
-    /// least-significant qubit, i.e. that with index `0`).
+    /// will compute `$ \langle \psi | I I I I X I Z | \psi \rangle $` (where in
+    /// this notation, the left-most operator applies to the
+    /// least-significant qubit, i.e. that with index `0`).
+    ///
+    /// `workspace` must be a register with the same type (state-vector vs
+    /// density matrix) and dimensions (number of represented qubits) as `self`,
+    /// and is used as working space. When this function returns, `self` will
+    /// be unchanged and `workspace` will be set to `$ \sigma
+    /// | \psi \rangle $` (if `self` is a state-vector)  or $ \sigma
+    /// \rho $` (if `self` is a density matrix).
+    ///
+    /// NOTE that this last quantity is NOT the result of applying  the paulis
+    /// as unitaries, `$ \sigma^\dagger \rho \sigma $`, but is instead the
+    /// result of their direct multiplication with the density matrix. It
+    /// is therefore itself not a valid density matrix.
+    ///
+    /// This function works by cloning the `self` state into `workspace`,
+    /// applying the specified  Pauli operators to `workspace` then
+    /// computing its inner product with `self` (for state-vectors)
+    /// or its trace (for density matrices). It therefore should scale linearly
+    /// in time with the number of specified non-identity Pauli operators,
+    /// which is bounded by the number of represented qubits.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubits`: a list of the indices of the target qubits
+    /// - `pauli_codes`: a list of the Pauli codes to apply to the corresponding
+    ///   qubits in `target_qubits`
+    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
+    ///   which is modified to be the result of multiplying the state with the
+    ///   pauli operators
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubits.len() > self.num_qubits()`
+    ///   - if any qubit index in `target_qubits` is outside [0,
+    ///     [`num_qubits()`]),
+    ///   - if `target_qubits` contain any repetitions
+    ///   - if `workspace` is not of the same dimension as `self`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let qureg = {
+    ///     let mut qureg = Qureg::try_new(2, &env).expect(
+    ///         "cannot allocate memory for
+    /// Qureg",
+    ///     );
+    ///     qureg.init_plus_state();
+    ///     qureg
+    /// };
+    /// let mut workspace =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let target_qubits = &[0, 1];
+    /// let pauli_codes = &[PAULI_X, PAULI_X];
+    ///
+    /// let product = qureg
+    ///     .calc_expec_pauli_prod(target_qubits, pauli_codes, &mut workspace)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn calc_expec_pauli_prod(
+        &self,
+        target_qubits: &[i32],
+        pauli_codes: &[PauliOpType],
+        workspace: &mut Qureg<'_>,
+    ) -> Result<Qreal, QuestError> {
+        let num_targets = checked_len(target_qubits.len())?;
+        catch_quest_exception(|| unsafe {
+            ffi::calcExpecPauliProd(
+                self.reg,
+                target_qubits.as_ptr(),
+                pauli_codes.as_ptr(),
+                num_targets,
+                workspace.reg,
+            )
+        })
+    }
+
+    /// Computes the expected value of a product of Pauli operators.
+    ///
+    /// This is a convenience wrapper around [`calc_expec_pauli_prod()`] that
+    /// allocates the working-space register internally.  The workspace has
+    /// the same dimensions as `self` and is freed before the function
+    /// returns.
+    ///
+    /// # Parameters
+    ///
+    /// - `targets`: a list of the indices of the target qubits
+    /// - `paulis`: a list of the Pauli operators to apply to the corresponding
+    ///   qubits in `targets`
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `targets.len()` and `paulis.len()` are different
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `targets.len() > self.num_qubits()`
+    ///   - if any qubit index in `targets` is outside [0, [`num_qubits()`]),
+    ///   - if `targets` contain any repetitions
+    ///   - if the workspace cannot be allocated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let expec_val = qureg.expec_pauli_string(&[0], &[PAULI_X]).unwrap();
+    /// assert!((expec_val - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`calc_expec_pauli_prod()`]: crate::Qureg::calc_expec_pauli_prod()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn expec_pauli_string(
+        &self,
+        targets: &[i32],
+        paulis: &[PauliOpType],
+    ) -> Result<Qreal, QuestError> {
+        if targets.len() != paulis.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let mut workspace = if self.is_density_matrix() {
+            Qureg::try_new_density(self.num_qubits(), self.env)?
+        } else {
+            Qureg::try_new(self.num_qubits(), self.env)?
+        };
+        self.calc_expec_pauli_prod(targets, paulis, &mut workspace)
+    }
+
+    /// Computes the complex expected value of a product of Pauli operators.
+    ///
+    /// For a state-vector `|psi>`, this is `<psi| sigma |psi>`, and for a
+    /// density matrix `rho`, this is `Tr(sigma rho)`, where `sigma` is the
+    /// product of Pauli operators given by `targets` and `paulis`.
+    ///
+    /// For a valid (normalized, Hermitian) density matrix, the imaginary part
+    /// of the result is zero, up to numerical precision, and the real part is
+    /// the value returned by [`calc_expec_pauli_prod()`].  For non-physical
+    /// states, e.g. after [`apply_matrix2()`], a non-zero imaginary part
+    /// can be useful for diagnostics.  Use [`expec_pauli_string()`], if only
+    /// the real part is needed.
+    ///
+    /// This function does not need a working-space register.  Instead, it
+    /// reads all the amplitudes of the register one by one, which requires
+    /// `2^N` amplitude reads (each one a broadcast in distributed mode).
+    ///
+    /// # Parameters
+    ///
+    /// - `targets`: a list of the indices of the target qubits
+    /// - `paulis`: a list of the Pauli operators to apply to the corresponding
+    ///   qubits in `targets`
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `targets.len()` and `paulis.len()` are different
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit index in `targets` is outside [0, [`num_qubits()`]),
+    ///   - if `targets` contain any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(1, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// // a non-Hermitian matrix: rho = [[0.5, 0], [0.5i, 0.5]]
+    /// qureg
+    ///     .init_state_from_amps(&[0.5, 0., 0., 0.5], &[0., 0.5, 0., 0.])
+    ///     .unwrap();
     ///
-    /// `workspace` must be a register with the same type (state-vector vs
-    /// density matrix) and dimensions (number of represented qubits) as `self`,
-    /// and is used as working space. When this function returns, `self` will
-    /// be unchanged and `workspace` will be set to `$ \sigma
-    /// | \psi \rangle $` (if `self` is a state-vector)  or $ \sigma
-    /// \rho $` (if `self` is a density matrix).
+    /// let expec_val = qureg.expec_pauli_prod_complex(&[0], &[PAULI_X]).unwrap();
+    /// assert!(expec_val.re.abs() < EPSILON);
+    /// assert!((expec_val.im - 0.5).abs() < EPSILON);
+    /// ```
     ///
-    /// NOTE that this last quantity is NOT the result of applying  the paulis
-    /// as unitaries, `$ \sigma^\dagger \rho \sigma $`, but is instead the
-    /// result of their direct multiplication with the density matrix. It
-    /// is therefore itself not a valid density matrix.
+    /// [`calc_expec_pauli_prod()`]: crate::Qureg::calc_expec_pauli_prod()
+    /// [`apply_matrix2()`]: crate::Qureg::apply_matrix2()
+    /// [`expec_pauli_string()`]: crate::Qureg::expec_pauli_string()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    pub fn expec_pauli_prod_complex(
+        &self,
+        targets: &[i32],
+        paulis: &[PauliOpType],
+    ) -> Result<Qcomplex, QuestError> {
+        if targets.len() != paulis.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        validate_unique_qubits(self, targets, "expec_pauli_prod_complex")?;
+
+        // sigma |j> = phase(j) |j ^ flip>
+        let flip = targets
+            .iter()
+            .zip(paulis)
+            .filter(|(_, p)| {
+                matches!(p, PauliOpType::PAULI_X | PauliOpType::PAULI_Y)
+            })
+            .fold(0_i64, |acc, (&t, _)| acc | (1 << t));
+        let phase = |j: i64| {
+            targets.iter().zip(paulis).fold(
+                Qcomplex::new(1., 0.),
+                |acc, (&t, &p)| {
+                    let bit = (j >> t) & 1;
+                    match p {
+                        PauliOpType::PAULI_I | PauliOpType::PAULI_X => acc,
+                        PauliOpType::PAULI_Y if bit == 0 => {
+                            acc * Qcomplex::new(0., 1.)
+                        }
+                        PauliOpType::PAULI_Y => acc * Qcomplex::new(0., -1.),
+                        PauliOpType::PAULI_Z if bit == 0 => acc,
+                        PauliOpType::PAULI_Z => -acc,
+                    }
+                },
+            )
+        };
+
+        let dim = 1_i64 << self.num_qubits();
+        let mut expec_val = Qcomplex::new(0., 0.);
+        if self.is_density_matrix() {
+            // Tr(sigma rho) = sum_j phase(j) rho[j, j ^ flip]
+            for j in 0..dim {
+                expec_val += phase(j) * self.get_density_amp(j, j ^ flip)?;
+            }
+        } else {
+            // <psi| sigma |psi> = sum_j conj(psi[j ^ flip]) phase(j) psi[j]
+            let amps = (0..dim)
+                .map(|j| self.get_amp(j))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (j, amp) in (0..).zip(&amps) {
+                expec_val += amps[(j ^ flip) as usize].conj() * phase(j) * amp;
+            }
+        }
+        Ok(expec_val)
+    }
+
+    /// Computes the Bloch vector of a single qubit.
     ///
-    /// This function works by cloning the `self` state into `workspace`,
-    /// applying the specified  Pauli operators to `workspace` then
-    /// computing its inner product with `self` (for state-vectors)
-    /// or its trace (for density matrices). It therefore should scale linearly
-    /// in time with the number of specified non-identity Pauli operators,
-    /// which is bounded by the number of represented qubits.
+    /// Returns the vector `(<X>, <Y>, <Z>)` of expectation values of Pauli
+    /// operators acting on `qubit`.  This is the Bloch vector of the reduced
+    /// single-qubit state, both for state-vectors and density matrices.  Its
+    /// length is 1 for a pure reduced state, and smaller for a mixed one.
+    ///
+    /// A working-space register of the same dimensions as `self` is allocated
+    /// internally, as in [`expec_pauli_string()`].
     ///
     /// # Parameters
     ///
-    /// - `target_qubits`: a list of the indices of the target qubits
-    /// - `pauli_codes`: a list of the Pauli codes to apply to the corresponding
-    ///   qubits in `target_qubits`
-    /// - `workspace`: a working-space qureg with the same dimensions as `self`,
-    ///   which is modified to be the result of multiplying the state with the
-    ///   pauli operators
+    /// - `qubit`: the qubit, whose Bloch vector is computed
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `target_qubits.len() > self.num_qubits()`
-    ///   - if any qubit index in `target_qubits` is outside [0,
-    ///     [`num_qubits()`]),
-    ///   - if `target_qubits` contain any repetitions
-    ///   - if `workspace` is not of the same dimension as `self`
+    ///   - if `qubit` is outside [0, [`num_qubits()`])
+    ///   - if the workspace cannot be allocated
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
-    /// use PauliOpType::PAULI_X;
-    ///
     /// let env = QuestEnv::new();
-    /// let qureg = {
-    ///     let mut qureg = Qureg::try_new(2, &env).expect(
-    ///         "cannot allocate memory for
-    /// Qureg",
-    ///     );
-    ///     qureg.init_plus_state();
-    ///     qureg
-    /// };
-    /// let mut workspace =
+    /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
     ///
-    /// let target_qubits = &[0, 1];
-    /// let pauli_codes = &[PAULI_X, PAULI_X];
-    ///
-    /// let product = qureg
-    ///     .calc_expec_pauli_prod(target_qubits, pauli_codes, &mut workspace)
-    ///     .unwrap();
+    /// let v = qureg.bloch_vector(0).unwrap();
+    /// assert!((v.x() - 1.).abs() < EPSILON);
+    /// assert!(v.y().abs() < EPSILON);
+    /// assert!(v.z().abs() < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`expec_pauli_string()`]: crate::Qureg::expec_pauli_string()
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn calc_expec_pauli_prod(
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn bloch_vector(
         &self,
-        target_qubits: &[i32],
-        pauli_codes: &[PauliOpType],
-        workspace: &mut Qureg<'_>,
-    ) -> Result<Qreal, QuestError> {
-        let num_targets = target_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::calcExpecPauliProd(
-                self.reg,
-                target_qubits.as_ptr(),
-                pauli_codes.as_ptr(),
-                num_targets,
-                workspace.reg,
-            )
-        })
+        qubit: i32,
+    ) -> Result<Vector, QuestError> {
+        validate_unique_qubits(self, &[qubit], "bloch_vector")?;
+        let mut workspace = if self.is_density_matrix() {
+            Qureg::try_new_density(self.num_qubits(), self.env)?
+        } else {
+            Qureg::try_new(self.num_qubits(), self.env)?
+        };
+        let mut expec = |pauli| {
+            self.calc_expec_pauli_prod(&[qubit], &[pauli], &mut workspace)
+        };
+        Ok(Vector::new(
+            expec(PauliOpType::PAULI_X)?,
+            expec(PauliOpType::PAULI_Y)?,
+            expec(PauliOpType::PAULI_Z)?,
+        ))
     }
 
     /// Computes the expected value of a sum of products of Pauli operators.
@@ -4461,6 +8674,9 @@ impl<'a> Qureg<'a> {
     ///
     /// # Errors
     ///
+    /// - [`ArrayLengthError`],
+    ///   - if the length of `all_pauli_codes` is not `term_coeffs.len()` times
+    ///     [`num_qubits()`]
     /// - [`InvalidQuESTInputError`],
     ///   - if `workspace` is not of the same dimension as `self`
     ///
@@ -4494,6 +8710,7 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [`num_qubits()`]: crate::Qureg::num_qubits()
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
@@ -4504,7 +8721,13 @@ impl<'a> Qureg<'a> {
         term_coeffs: &[Qreal],
         workspace: &mut Qureg<'_>,
     ) -> Result<Qreal, QuestError> {
-        let num_sum_terms = term_coeffs.len() as i32;
+        let num_qubits = self.num_qubits() as usize;
+        if term_coeffs.len().checked_mul(num_qubits)
+            != Some(all_pauli_codes.len())
+        {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_sum_terms = checked_len(term_coeffs.len())?;
         catch_quest_exception(|| unsafe {
             ffi::calcExpecPauliSum(
                 self.reg,
@@ -4694,7 +8917,7 @@ impl<'a> Qureg<'a> {
         target_qubit2: i32,
         u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("two_qubit_unitary", || unsafe {
             ffi::twoQubitUnitary(self.reg, target_qubit1, target_qubit2, u.0);
         })
     }
@@ -4790,7 +9013,7 @@ impl<'a> Qureg<'a> {
         target_qubit2: i32,
         u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("controlled_two_qubit_unitary", || unsafe {
             ffi::controlledTwoQubitUnitary(
                 self.reg,
                 control_qubit,
@@ -4896,8 +9119,8 @@ impl<'a> Qureg<'a> {
         target_qubit2: i32,
         u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        let num_control_qubits = control_qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_control_qubits = checked_len(control_qubits.len())?;
+        catch_gate("multi_controlled_two_qubit_unitary", || unsafe {
             ffi::multiControlledTwoQubitUnitary(
                 self.reg,
                 control_qubits.as_ptr(),
@@ -4989,8 +9212,8 @@ impl<'a> Qureg<'a> {
         targs: &[i32],
         u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("multi_qubit_unitary", || unsafe {
             ffi::multiQubitUnitary(self.reg, targs.as_ptr(), num_targs, u.0);
         })
     }
@@ -5083,8 +9306,8 @@ impl<'a> Qureg<'a> {
         targs: &[i32],
         u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("controlled_multi_qubit_unitary", || unsafe {
             ffi::controlledMultiQubitUnitary(
                 self.reg,
                 ctrl,
@@ -5188,9 +9411,9 @@ impl<'a> Qureg<'a> {
         targs: &[i32],
         u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_ctrls = ctrls.len() as i32;
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_ctrls = checked_len(ctrls.len())?;
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("multi_controlled_multi_qubit_unitary", || unsafe {
             ffi::multiControlledMultiQubitUnitary(
                 self.reg,
                 ctrls.as_ptr(),
@@ -5204,7 +9427,17 @@ impl<'a> Qureg<'a> {
 
     /// Apply a general single-qubit Kraus map to a density matrix.
     ///
-    /// The map is specified by at most four Kraus operators.
+    /// The map is specified by at most four Kraus operators.  To pass a slice
+    /// of matrices instead of references, use [`mix_kraus_map_owned()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > 4`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if the Kraus map is invalid (e.g. not completely positive and trace
+    ///     preserving), if `qureg` is not a density matrix, or if any of the
+    ///     target qubits is invalid
     ///
     /// # Examples
     ///
@@ -5225,6 +9458,9 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`mix_kraus_map_owned()`]: crate::Qureg::mix_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_kraus_map(
@@ -5232,16 +9468,75 @@ impl<'a> Qureg<'a> {
         target: i32,
         ops: &[&ComplexMatrix2],
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
+        if ops.len() > 4 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_ops = checked_len(ops.len())?;
         let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
+        catch_gate("mix_kraus_map", || unsafe {
             ffi::mixKrausMap(self.reg, target, ops_inner.as_ptr(), num_ops);
         })
     }
 
+    /// Apply a general single-qubit Kraus map to a density matrix.
+    ///
+    /// Same as [`mix_kraus_map()`], but the operators are passed as a slice of
+    /// matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_kraus_map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let m = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// qureg.mix_kraus_map_owned(1, &[m]).unwrap();
+    ///
+    /// // Check if the register is now in the state |01>
+    /// let amp = qureg.get_density_amp(2, 2).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`mix_kraus_map()`]: crate::Qureg::mix_kraus_map()
+    pub fn mix_kraus_map_owned(
+        &mut self,
+        target: i32,
+        ops: &[ComplexMatrix2],
+    ) -> Result<(), QuestError> {
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_kraus_map(target, &ops)
+    }
+
     /// Apply a general two-qubit Kraus map to a density matrix.
     ///
-    /// The map is specified by at most sixteen Kraus operators.
+    /// The map is specified by at most sixteen Kraus operators.  To pass a
+    /// slice of matrices instead of references, use
+    /// [`mix_two_qubit_kraus_map_owned()`].
+    ///
+    /// The density matrix `rho` is transformed into `sum_k K_k rho K_k^†`,
+    /// where `K_k = ops[k]`.  Since this is a sum, the order of the operators
+    /// in `ops` does not affect the result.  The operators are passed to
+    /// `QuEST` in the same order as in `ops`.
+    ///
+    /// Each operator is expressed in the basis `|target2, target1>`, i.e.
+    /// `target1` is treated as the *least* significant qubit: the row (and
+    /// column) index `i` of the matrix corresponds to the basis state in which
+    /// `target1` has value `i & 1` and `target2` has value `i >> 1`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > 16`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if the Kraus map is invalid (e.g. not completely positive and trace
+    ///     preserving), if `qureg` is not a density matrix, or if any of the
+    ///     target qubits is invalid
     ///
     /// # Examples
     ///
@@ -5278,6 +9573,9 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`mix_two_qubit_kraus_map_owned()`]: crate::Qureg::mix_two_qubit_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_two_qubit_kraus_map(
@@ -5286,13 +9584,176 @@ impl<'a> Qureg<'a> {
         target2: i32,
         ops: &[&ComplexMatrix4],
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
+        if ops.len() > 16 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_ops = checked_len(ops.len())?;
+        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
+        catch_gate("mix_two_qubit_kraus_map", || unsafe {
+            ffi::mixTwoQubitKrausMap(
+                self.reg,
+                target1,
+                target2,
+                ops_inner.as_ptr(),
+                num_ops,
+            );
+        })
+    }
+
+    /// Apply a general two-qubit Kraus map to a density matrix.
+    ///
+    /// Same as [`mix_two_qubit_kraus_map()`], but the operators are passed as a
+    /// slice of matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_two_qubit_kraus_map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let m = ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [1., 0., 0., 0.],
+    ///     ],
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
+    /// qureg.mix_two_qubit_kraus_map_owned(1, 2, &[m]).unwrap();
+    ///
+    /// // Check if the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`mix_two_qubit_kraus_map()`]: crate::Qureg::mix_two_qubit_kraus_map()
+    pub fn mix_two_qubit_kraus_map_owned(
+        &mut self,
+        target1: i32,
+        target2: i32,
+        ops: &[ComplexMatrix4],
+    ) -> Result<(), QuestError> {
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_two_qubit_kraus_map(target1, target2, &ops)
+    }
+
+    /// Apply a general N-qubit Kraus map to a density matrix.
+    ///
+    /// The map is specified by at most `(2N)^2` Kraus operators, where `N` is
+    /// the number of target qubits.  To pass a
+    /// slice of matrices instead of references, use
+    /// [`mix_multi_qubit_kraus_map_owned()`].
+    ///
+    /// This can be used to simulate a variety of decoherence channels, and
+    /// generalizes [`mix_kraus_map()`] and [`mix_two_qubit_kraus_map()`] to an
+    /// arbitrary number of target qubits.  The Kraus operators must form a
+    /// completely positive, trace-preserving (CPTP) map, i.e. they must
+    /// satisfy the completeness relation: `sum_j K_j^dagger K_j = I`.
+    ///
+    /// The first qubit in `targets` is treated as the least significant
+    /// qubit of each operator in `ops`.
+    ///
+    /// # Parameters
+    ///
+    /// - `targets`: the qubits the Kraus map acts on
+    /// - `ops`: the Kraus operators, each acting on `targets.len()` qubits
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > (2 * targets.len())^2`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if any index in `targets` is outside [0, [`num_qubits()`])
+    ///   - if `targets` contains any repetitions
+    ///   - if the [`num_qubits()`][matrix-num-qubits] of any operator in `ops`
+    ///     is not equal to `targets.len()`
+    ///   - if the Kraus map is not CPTP, or if `qureg` is not a density matrix
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// init_complex_matrix_n(
+    ///     m,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[1., 0., 0., 0.],
+    ///     ],
+    ///     &[
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// let targets = &[1, 2];
+    /// qureg.mix_multi_qubit_kraus_map(targets, &[m]).unwrap();
+    ///
+    /// // Check if the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`mix_kraus_map()`]: crate::Qureg::mix_kraus_map()
+    /// [`mix_two_qubit_kraus_map()`]: crate::Qureg::mix_two_qubit_kraus_map()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [matrix-num-qubits]: crate::ComplexMatrixN::num_qubits()
+    /// [`mix_multi_qubit_kraus_map_owned()`]: crate::Qureg::mix_multi_qubit_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn mix_multi_qubit_kraus_map(
+        &mut self,
+        targets: &[i32],
+        ops: &[&ComplexMatrixN],
+    ) -> Result<(), QuestError> {
+        validate_unique_qubits(self, targets, "mix_multi_qubit_kraus_map")?;
+        let max_ops = targets
+            .len()
+            .checked_mul(2)
+            .and_then(|n| n.checked_mul(n))
+            .ok_or(QuestError::ArrayLengthError)?;
+        if ops.len() > max_ops {
+            return Err(QuestError::ArrayLengthError);
+        }
+        if ops.iter().any(|x| x.num_qubits() as usize != targets.len()) {
+            return Err(invalid_input_error(
+                "Every Kraus operator must be of the same number of qubits as \
+                 the number of targets.",
+                "mix_multi_qubit_kraus_map",
+            ));
+        }
+        let num_targets = checked_len(targets.len())?;
+        let num_ops = checked_len(ops.len())?;
         let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
-            ffi::mixTwoQubitKrausMap(
+        catch_gate("mix_multi_qubit_kraus_map", || unsafe {
+            ffi::mixMultiQubitKrausMap(
                 self.reg,
-                target1,
-                target2,
+                targets.as_ptr(),
+                num_targets,
                 ops_inner.as_ptr(),
                 num_ops,
             );
@@ -5301,7 +9762,12 @@ impl<'a> Qureg<'a> {
 
     /// Apply a general N-qubit Kraus map to a density matrix.
     ///
-    /// The map is specified by at most `(2N)^2` Kraus operators.
+    /// Same as [`mix_multi_qubit_kraus_map()`], but the operators are passed as
+    /// a slice of matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_multi_qubit_kraus_map()`].
     ///
     /// # Examples
     ///
@@ -5311,9 +9777,9 @@ impl<'a> Qureg<'a> {
     /// let mut qureg = Qureg::try_new_density(3, &env)
     ///     .expect("cannot allocate memory for Qureg");
     ///
-    /// let m = &mut ComplexMatrixN::try_new(2).unwrap();
+    /// let mut m = ComplexMatrixN::try_new(2).unwrap();
     /// init_complex_matrix_n(
-    ///     m,
+    ///     &mut m,
     ///     &[
     ///         &[0., 0., 0., 1.],
     ///         &[0., 1., 0., 0.],
@@ -5328,41 +9794,38 @@ impl<'a> Qureg<'a> {
     ///     ],
     /// )
     /// .unwrap();
-    /// let targets = &[1, 2];
-    /// qureg.mix_multi_qubit_kraus_map(targets, &[m]).unwrap();
+    /// qureg
+    ///     .mix_multi_qubit_kraus_map_owned(&[1, 2], &[m])
+    ///     .unwrap();
     ///
     /// // Check if the register is now in the state |011>
     /// let amp = qureg.get_density_amp(6, 6).unwrap();
     /// assert!((amp.re - 1.).abs() < EPSILON);
     /// ```
     ///
-    /// See [QuEST API] for more information.
-    ///
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn mix_multi_qubit_kraus_map(
+    /// [`mix_multi_qubit_kraus_map()`]: crate::Qureg::mix_multi_qubit_kraus_map()
+    pub fn mix_multi_qubit_kraus_map_owned(
         &mut self,
         targets: &[i32],
-        ops: &[&ComplexMatrixN],
+        ops: &[ComplexMatrixN],
     ) -> Result<(), QuestError> {
-        let num_targets = targets.len() as i32;
-        let num_ops = ops.len() as i32;
-        let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
-            ffi::mixMultiQubitKrausMap(
-                self.reg,
-                targets.as_ptr(),
-                num_targets,
-                ops_inner.as_ptr(),
-                num_ops,
-            );
-        })
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_multi_qubit_kraus_map(targets, &ops)
     }
 
     /// Apply a general non-trace-preserving single-qubit Kraus map.
     ///
     /// The state must be a density matrix, and the map is specified by at most
-    /// four operators.
+    /// four operators.  To pass a slice of matrices instead of references,
+    /// use [`mix_nontp_kraus_map_owned()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > 4`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is not a density matrix, or if any of the target qubits
+    ///     is invalid
     ///
     /// # Examples
     ///
@@ -5383,6 +9846,9 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`mix_nontp_kraus_map_owned()`]: crate::Qureg::mix_nontp_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_nontp_kraus_map(
@@ -5390,9 +9856,12 @@ impl<'a> Qureg<'a> {
         target: i32,
         ops: &[&ComplexMatrix2],
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
+        if ops.len() > 4 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_ops = checked_len(ops.len())?;
         let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
+        catch_gate("mix_nontp_kraus_map", || unsafe {
             ffi::mixNonTPKrausMap(
                 self.reg,
                 target,
@@ -5402,10 +9871,54 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Apply a general non-trace-preserving single-qubit Kraus map.
+    ///
+    /// Same as [`mix_nontp_kraus_map()`], but the operators are passed as a
+    /// slice of matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_nontp_kraus_map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let m = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// qureg.mix_nontp_kraus_map_owned(1, &[m]).unwrap();
+    ///
+    /// // Check if the register is now in the state |01>
+    /// let amp = qureg.get_density_amp(2, 2).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`mix_nontp_kraus_map()`]: crate::Qureg::mix_nontp_kraus_map()
+    pub fn mix_nontp_kraus_map_owned(
+        &mut self,
+        target: i32,
+        ops: &[ComplexMatrix2],
+    ) -> Result<(), QuestError> {
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_nontp_kraus_map(target, &ops)
+    }
+
     /// Apply a general non-trace-preserving two-qubit Kraus map.
     ///
     /// The state must be a density matrix, and the map is specified
-    /// by at most 16 operators.
+    /// by at most 16 operators.  To pass a slice of matrices instead of
+    /// references, use [`mix_nontp_two_qubit_kraus_map_owned()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > 16`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if `qureg` is not a density matrix, or if any of the target qubits
+    ///     is invalid
     ///
     /// # Examples
     ///
@@ -5442,6 +9955,9 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`mix_nontp_two_qubit_kraus_map_owned()`]: crate::Qureg::mix_nontp_two_qubit_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_nontp_two_qubit_kraus_map(
@@ -5450,9 +9966,12 @@ impl<'a> Qureg<'a> {
         target2: i32,
         ops: &[&ComplexMatrix4],
     ) -> Result<(), QuestError> {
-        let num_ops = ops.len() as i32;
+        if ops.len() > 16 {
+            return Err(QuestError::ArrayLengthError);
+        }
+        let num_ops = checked_len(ops.len())?;
         let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
+        catch_gate("mix_nontp_two_qubit_kraus_map", || unsafe {
             ffi::mixNonTPTwoQubitKrausMap(
                 self.reg,
                 target1,
@@ -5463,10 +9982,74 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Apply a general non-trace-preserving two-qubit Kraus map.
+    ///
+    /// Same as [`mix_nontp_two_qubit_kraus_map()`], but the operators are
+    /// passed as a slice of matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_nontp_two_qubit_kraus_map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let m = ComplexMatrix4::new(
+    ///     [
+    ///         [0., 0., 0., 1.],
+    ///         [0., 1., 0., 0.],
+    ///         [0., 0., 1., 0.],
+    ///         [1., 0., 0., 0.],
+    ///     ],
+    ///     [
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///         [0., 0., 0., 0.],
+    ///     ],
+    /// );
+    /// qureg
+    ///     .mix_nontp_two_qubit_kraus_map_owned(1, 2, &[m])
+    ///     .unwrap();
+    ///
+    /// // Check if the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`mix_nontp_two_qubit_kraus_map()`]: crate::Qureg::mix_nontp_two_qubit_kraus_map()
+    pub fn mix_nontp_two_qubit_kraus_map_owned(
+        &mut self,
+        target1: i32,
+        target2: i32,
+        ops: &[ComplexMatrix4],
+    ) -> Result<(), QuestError> {
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_nontp_two_qubit_kraus_map(target1, target2, &ops)
+    }
+
     /// Apply a general N-qubit non-trace-preserving Kraus map.
     ///
     /// The state must be a density matrix, and the map is specified
-    /// by at most `2^(2N)` operators.
+    /// by at most `(2N)^2` operators, where `N` is the number of target
+    /// qubits.  To pass a slice of matrices instead of references, use
+    /// [`mix_nontp_multi_qubit_kraus_map_owned()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`]
+    ///   - if `ops.len() > (2 * targets.len())^2`
+    /// - [`InvalidQuESTInputError`]
+    ///   - if any index in `targets` is outside [0, [`num_qubits()`])
+    ///   - if `targets` contains any repetitions
+    ///   - if the [`num_qubits()`][matrix-num-qubits] of any operator in `ops`
+    ///     is not equal to `targets.len()`
+    ///   - if `qureg` is not a density matrix
     ///
     /// # Examples
     ///
@@ -5504,6 +10087,11 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [matrix-num-qubits]: crate::ComplexMatrixN::num_qubits()
+    /// [`mix_nontp_multi_qubit_kraus_map_owned()`]: crate::Qureg::mix_nontp_multi_qubit_kraus_map_owned()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn mix_nontp_multi_qubit_kraus_map(
@@ -5511,10 +10099,30 @@ impl<'a> Qureg<'a> {
         targets: &[i32],
         ops: &[&ComplexMatrixN],
     ) -> Result<(), QuestError> {
-        let num_targets = targets.len() as i32;
-        let num_ops = ops.len() as i32;
+        validate_unique_qubits(
+            self,
+            targets,
+            "mix_nontp_multi_qubit_kraus_map",
+        )?;
+        let max_ops = targets
+            .len()
+            .checked_mul(2)
+            .and_then(|n| n.checked_mul(n))
+            .ok_or(QuestError::ArrayLengthError)?;
+        if ops.len() > max_ops {
+            return Err(QuestError::ArrayLengthError);
+        }
+        if ops.iter().any(|x| x.num_qubits() as usize != targets.len()) {
+            return Err(invalid_input_error(
+                "Every Kraus operator must be of the same number of qubits as \
+                 the number of targets.",
+                "mix_nontp_multi_qubit_kraus_map",
+            ));
+        }
+        let num_targets = checked_len(targets.len())?;
+        let num_ops = checked_len(ops.len())?;
         let ops_inner = ops.iter().map(|x| x.0).collect::<Vec<_>>();
-        catch_quest_exception(|| unsafe {
+        catch_gate("mix_nontp_multi_qubit_kraus_map", || unsafe {
             ffi::mixNonTPMultiQubitKrausMap(
                 self.reg,
                 targets.as_ptr(),
@@ -5525,10 +10133,79 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Apply a general non-trace-preserving N-qubit Kraus map.
+    ///
+    /// Same as [`mix_nontp_multi_qubit_kraus_map()`], but the operators are
+    /// passed as a slice of matrices instead of a slice of references.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mix_nontp_multi_qubit_kraus_map()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(3, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    ///
+    /// let mut m = ComplexMatrixN::try_new(2).unwrap();
+    /// init_complex_matrix_n(
+    ///     &mut m,
+    ///     &[
+    ///         &[0., 0., 0., 1.],
+    ///         &[0., 1., 0., 0.],
+    ///         &[0., 0., 1., 0.],
+    ///         &[1., 0., 0., 0.],
+    ///     ],
+    ///     &[
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///         &[0., 0., 0., 0.],
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// qureg
+    ///     .mix_nontp_multi_qubit_kraus_map_owned(&[1, 2], &[m])
+    ///     .unwrap();
+    ///
+    /// // Check if the register is now in the state |011>
+    /// let amp = qureg.get_density_amp(6, 6).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`mix_nontp_multi_qubit_kraus_map()`]: crate::Qureg::mix_nontp_multi_qubit_kraus_map()
+    pub fn mix_nontp_multi_qubit_kraus_map_owned(
+        &mut self,
+        targets: &[i32],
+        ops: &[ComplexMatrixN],
+    ) -> Result<(), QuestError> {
+        let ops = ops.iter().collect::<Vec<_>>();
+        self.mix_nontp_multi_qubit_kraus_map(targets, &ops)
+    }
+
     /// Applies a trotterisation of unitary evolution.
     ///
     /// The unitary evelution `$\exp(-i \, \text{hamil} \, \text{time})$` is
-    /// applied to `qureg`. # Examples
+    /// applied to `qureg`.
+    ///
+    /// # Parameters
+    ///
+    /// - `hamil`: the Hamiltonian to evolve under
+    /// - `time`: the evolution time
+    /// - `order`: the order of the Suzuki-Trotter decomposition
+    /// - `reps`: the number of repetitions of the decomposition
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if `order` is neither `1`, nor a
+    /// positive even number (QuEST supports only orders `1, 2, 4, 6, ...`),
+    /// or if `reps` is smaller than `1`. These are checked before calling
+    /// `QuEST`.
+    ///
+    /// # Examples
     ///
     /// ```rust
     /// # use quest_bind::*;
@@ -5558,6 +10235,7 @@ impl<'a> Qureg<'a> {
     /// See [QuEST API] for more information.
     ///
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn apply_trotter_circuit(
         &mut self,
@@ -5566,13 +10244,112 @@ impl<'a> Qureg<'a> {
         order: i32,
         reps: i32,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        if order != 1 && (order <= 0 || order % 2 != 0) {
+            return Err(invalid_input_error(
+                "The Trotterisation order must be 1, or an even number (for \
+                 higher-order Suzuki symmetrized expansions).",
+                "apply_trotter_circuit",
+            ));
+        }
+        if reps < 1 {
+            return Err(invalid_input_error(
+                "The number of Trotter repetitions must be >=1.",
+                "apply_trotter_circuit",
+            ));
+        }
+        catch_gate("apply_trotter_circuit", || unsafe {
             ffi::applyTrotterCircuit(self.reg, hamil.0, time, order, reps);
         })
     }
 
+    /// Evolves the state under a Hamiltonian for the given time.
+    ///
+    /// This is a convenience wrapper around [`apply_trotter_circuit()`],
+    /// with the parameters of the Suzuki-Trotter decomposition specified by
+    /// `config`.
+    ///
+    /// # Parameters
+    ///
+    /// - `hamil`: the Hamiltonian to evolve under
+    /// - `time`: the evolution time
+    /// - `config`: the order and the number of repetitions of the decomposition
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] if `config.order` is neither `1`,
+    /// nor a positive even number, or if `config.reps` is smaller than `1`,
+    /// or if any of them does not fit into `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// use PauliOpType::PAULI_X;
+    ///
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// let hamil = &mut PauliHamil::try_new(1, 1).unwrap();
+    /// init_pauli_hamil(hamil, &[1.], &[PAULI_X]).unwrap();
+    ///
+    /// qureg
+    ///     .evolve(hamil, PI / 2., TrotterConfig::default())
+    ///     .unwrap();
+    ///
+    /// // qureg is now in `|1>` state:
+    /// let qb1 = qureg.measure(0).unwrap();
+    /// assert_eq!(qb1, 1);
+    /// ```
+    ///
+    /// [`apply_trotter_circuit()`]: crate::Qureg::apply_trotter_circuit()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn evolve(
+        &mut self,
+        hamil: &PauliHamil,
+        time: Qreal,
+        config: TrotterConfig,
+    ) -> Result<(), QuestError> {
+        let order = i32::try_from(config.order).map_err(|_| {
+            invalid_input_error(
+                "The Trotterisation order must be 1, or an even number (for \
+                 higher-order Suzuki symmetrized expansions).",
+                "evolve",
+            )
+        })?;
+        let reps = i32::try_from(config.reps).map_err(|_| {
+            invalid_input_error(
+                "The number of Trotter repetitions is too large.",
+                "evolve",
+            )
+        })?;
+        self.apply_trotter_circuit(hamil, time, order, reps)
+    }
+
     /// Apply a general 2-by-2 matrix, which may be non-unitary.
     ///
+    /// The matrix is applied by *left-multiplication* only, both to
+    /// state-vectors and to density matrices.  That is, a state-vector `|psi>`
+    /// becomes `u |psi>`, and a density matrix `rho` becomes `u rho`, and
+    /// *not* `u rho u^dagger`.
+    ///
+    /// NOTE that this differs from the action of unitary gates, like
+    /// [`unitary()`], on density matrices.  Even if `u` is unitary, the
+    /// resulting density matrix is in general not Hermitian, and hence is not
+    /// a valid density matrix.  To apply a unitary matrix `u` as a gate, use
+    /// [`unitary()`] instead.  The result is not normalized either, unless
+    /// `u` is unitary and the register is a state-vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit`: qubit to operate on
+    /// - `u`: matrix to apply
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit` is outside [0, [`num_qubits()`]).
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -5589,8 +10366,30 @@ impl<'a> Qureg<'a> {
     /// assert!((amp - 1.).abs() < EPSILON);
     /// ```
     ///
+    /// On a density matrix, the matrix is only left-multiplied:
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut rho = Qureg::try_new_density(1, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// rho.init_zero_state();
+    ///
+    /// let u = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    /// rho.apply_matrix2(0, u).unwrap();
+    ///
+    /// // rho = |1><0|
+    /// let amp = rho.get_density_amp(1, 0).unwrap();
+    /// assert!((amp.re - 1.).abs() < EPSILON);
+    /// let amp = rho.get_density_amp(1, 1).unwrap();
+    /// assert!(amp.re.abs() < EPSILON);
+    /// ```
+    ///
     /// See [QuEST API] for more information.
     ///
+    /// [`unitary()`]: crate::Qureg::unitary()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn apply_matrix2(
@@ -5598,13 +10397,40 @@ impl<'a> Qureg<'a> {
         target_qubit: i32,
         u: &ComplexMatrix2,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("apply_matrix2", || unsafe {
             ffi::applyMatrix2(self.reg, target_qubit, u.0);
         })
     }
 
     /// Apply a general 4-by-4 matrix, which may be non-unitary.
     ///
+    /// The matrix is applied by *left-multiplication* only, both to
+    /// state-vectors and to density matrices.  That is, a state-vector `|psi>`
+    /// becomes `u |psi>`, and a density matrix `rho` becomes `u rho`, and
+    /// *not* `u rho u^dagger`.  The resulting density matrix is therefore in
+    /// general not a valid density matrix; see [`apply_matrix2()`] for more
+    /// information.  To apply a unitary matrix `u` as a gate, use
+    /// [`two_qubit_unitary()`] instead.
+    ///
+    /// The matrix `u` acts on the basis states of the two target qubits,
+    /// where `target_qubit1` is treated as the least significant qubit.
+    ///
+    /// # Parameters
+    ///
+    /// - `target_qubit1`: first qubit to operate on, treated as least
+    ///   significant in `u`
+    /// - `target_qubit2`: second qubit to operate on, treated as most
+    ///   significant in `u`
+    /// - `u`: matrix to apply
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `target_qubit1` or `target_qubit2` are outside [0,
+    ///     [`num_qubits()`])
+    ///   - if `target_qubit1` equals `target_qubit2`
+    ///   - if each node cannot fit 4 amplitudes in distributed mode
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -5640,6 +10466,10 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`apply_matrix2()`]: crate::Qureg::apply_matrix2()
+    /// [`two_qubit_unitary()`]: crate::Qureg::two_qubit_unitary()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn apply_matrix4(
@@ -5648,7 +10478,7 @@ impl<'a> Qureg<'a> {
         target_qubit2: i32,
         u: &ComplexMatrix4,
     ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
+        catch_gate("apply_matrix4", || unsafe {
             ffi::applyMatrix4(self.reg, target_qubit1, target_qubit2, u.0);
         })
     }
@@ -5700,14 +10530,32 @@ impl<'a> Qureg<'a> {
         targs: &[i32],
         u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("apply_matrix_n", || unsafe {
             ffi::applyMatrixN(self.reg, targs.as_ptr(), num_targs, u.0);
         })
     }
 
     /// Apply a general N-by-N matrix with additional controlled qubits.
     ///
+    /// The matrix `u` is applied to the qubits `targs`, if and only if all
+    /// qubits in `ctrls` are in the `|1>` state.
+    ///
+    /// # Parameters
+    ///
+    /// - `ctrls`: a list of the control qubit indices
+    /// - `targs`: a list of the target qubit indices
+    /// - `u`: a matrix of size `1 << targs.len()`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidQuESTInputError`] before calling `QuEST`,
+    ///
+    /// - if any qubit in `ctrls` or `targs` is outside [0, [`num_qubits()`])
+    /// - if `ctrls` or `targs` contain any repetitions
+    /// - if any qubit in `ctrls` is also in `targs`
+    /// - if `u.num_qubits()` is not equal to `targs.len()`
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -5744,6 +10592,8 @@ impl<'a> Qureg<'a> {
     ///
     /// See [QuEST API] for more information.
     ///
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn apply_multi_controlled_matrix_n(
@@ -5752,9 +10602,22 @@ impl<'a> Qureg<'a> {
         targs: &[i32],
         u: &ComplexMatrixN,
     ) -> Result<(), QuestError> {
-        let num_ctrls = ctrls.len() as i32;
-        let num_targs = targs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        validate_unique_qubits(self, targs, "apply_multi_controlled_matrix_n")?;
+        validate_control_qubits(
+            self,
+            ctrls,
+            targs,
+            "apply_multi_controlled_matrix_n",
+        )?;
+        if u.num_qubits() as usize != targs.len() {
+            return Err(invalid_input_error(
+                "The matrix size does not match the number of target qubits.",
+                "apply_multi_controlled_matrix_n",
+            ));
+        }
+        let num_ctrls = checked_len(ctrls.len())?;
+        let num_targs = checked_len(targs.len())?;
+        catch_gate("apply_multi_controlled_matrix_n", || unsafe {
             ffi::applyMultiControlledMatrixN(
                 self.reg,
                 ctrls.as_ptr(),
@@ -5766,6 +10629,94 @@ impl<'a> Qureg<'a> {
         })
     }
 
+    /// Apply a diagonal operator to a subset of the qubits.
+    ///
+    /// The operator `op` acts on `qubits.len()` qubits, where `qubits[k]` is
+    /// the `k`-th (least significant first) qubit of the index of its
+    /// diagonal elements.  The remaining qubits of the register are left
+    /// untouched, i.e. this applies `op` tensored with the identity.
+    ///
+    /// `QuEST` v3.5.0 supports only diagonal operators spanning the entire
+    /// register, so this function broadcasts `op` into a temporary
+    /// [`DiagonalOp`] of the full size, and applies it with
+    /// [`apply_diagonal_op()`].  The same caveats hold: for density matrices,
+    /// the operator is applied by left-multiplication only.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: the qubits `op` acts upon
+    /// - `op`: a diagonal operator on `qubits.len()` qubits
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qubits` contains an index outside [0, [`num_qubits()`])
+    ///   - if `qubits` are not unique
+    ///   - if `op.num_qubits()` differs from `qubits.len()`
+    ///   - if `op` is distributed over more than one node
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = &QuestEnv::new();
+    /// let qureg =
+    ///     &mut Qureg::try_new(3, env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(4).unwrap();
+    ///
+    /// let op = &mut DiagonalOp::try_new(1, env).unwrap();
+    /// init_diagonal_op(op, &[1., -1.], &[0., 0.]).unwrap();
+    ///
+    /// // apply Pauli Z to qubit 2
+    /// qureg.apply_diagonal_op_subset(&[2], op).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(4).unwrap();
+    /// assert!((amp + 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`DiagonalOp`]: crate::DiagonalOp
+    /// [`apply_diagonal_op()`]: crate::apply_diagonal_op()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn apply_diagonal_op_subset(
+        &mut self,
+        qubits: &[i32],
+        op: &DiagonalOp<'_>,
+    ) -> Result<(), QuestError> {
+        validate_unique_qubits(self, qubits, "apply_diagonal_op_subset")?;
+        if op.num_qubits() as usize != qubits.len() {
+            return Err(invalid_input_error(
+                "The operator size does not match the number of qubits.",
+                "apply_diagonal_op_subset",
+            ));
+        }
+        if op.op.numChunks > 1 {
+            return Err(invalid_input_error(
+                "The operator must not be distributed.",
+                "apply_diagonal_op_subset",
+            ));
+        }
+
+        let sub_len = 1_usize << qubits.len();
+        let (sub_re, sub_im) = unsafe {
+            (
+                std::slice::from_raw_parts(op.op.real, sub_len),
+                std::slice::from_raw_parts(op.op.imag, sub_len),
+            )
+        };
+        let dim = 1_i64 << self.num_qubits();
+        let (real, imag): (Vec<_>, Vec<_>) = (0..dim)
+            .map(|i| {
+                let k = gather_bits(i, qubits) as usize;
+                (sub_re[k], sub_im[k])
+            })
+            .unzip();
+
+        let full = &mut DiagonalOp::try_new(self.num_qubits(), self.env)?;
+        init_diagonal_op(full, &real, &imag)?;
+        apply_diagonal_op(self, full)
+    }
+
     /// Apply a phase function.
     ///
     /// Induces a phase change upon each amplitude of `qureg`, determined by the
@@ -5900,9 +10851,9 @@ impl<'a> Qureg<'a> {
         coeffs: &[Qreal],
         exponents: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_qubits = qubits.len() as i32;
-        let num_terms = coeffs.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_qubits = checked_len(qubits.len())?;
+        let num_terms = checked_len(coeffs.len())?;
+        catch_gate("apply_phase_func", || unsafe {
             ffi::applyPhaseFunc(
                 self.reg,
                 qubits.as_ptr(),
@@ -6027,10 +10978,10 @@ impl<'a> Qureg<'a> {
         override_inds: &[i64],
         override_phases: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_qubits = qubits.len() as i32;
-        let num_terms = coeffs.len() as i32;
-        let num_overrides = override_inds.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_qubits = checked_len(qubits.len())?;
+        let num_terms = checked_len(coeffs.len())?;
+        let num_overrides = checked_len(override_inds.len())?;
+        catch_gate("apply_phase_func_overrides", || unsafe {
             ffi::applyPhaseFuncOverrides(
                 self.reg,
                 qubits.as_ptr(),
@@ -6209,8 +11160,8 @@ impl<'a> Qureg<'a> {
         exponents: &[Qreal],
         num_terms_per_reg: &[i32],
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        catch_gate("apply_multi_var_phase_func", || unsafe {
             ffi::applyMultiVarPhaseFunc(
                 self.reg,
                 qubits.as_ptr(),
@@ -6278,9 +11229,9 @@ impl<'a> Qureg<'a> {
         override_inds: &[i64],
         override_phases: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        let num_overrides = override_phases.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        let num_overrides = checked_len(override_phases.len())?;
+        catch_gate("apply_multi_var_phase_func_overrides", || unsafe {
             ffi::applyMultiVarPhaseFuncOverrides(
                 self.reg,
                 qubits.as_ptr(),
@@ -6336,8 +11287,8 @@ impl<'a> Qureg<'a> {
         encoding: BitEncoding,
         function_name_code: PhaseFunc,
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        catch_gate("apply_named_phase_func", || unsafe {
             ffi::applyNamedPhaseFunc(
                 self.reg,
                 qubits.as_ptr(),
@@ -6396,9 +11347,9 @@ impl<'a> Qureg<'a> {
         override_inds: &[i64],
         override_phases: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        let num_overrides = override_phases.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        let num_overrides = checked_len(override_phases.len())?;
+        catch_gate("apply_named_phase_func_overrides", || unsafe {
             ffi::applyNamedPhaseFuncOverrides(
                 self.reg,
                 qubits.as_ptr(),
@@ -6456,9 +11407,9 @@ impl<'a> Qureg<'a> {
         function_name_code: PhaseFunc,
         params: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        let num_params = params.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        let num_params = checked_len(params.len())?;
+        catch_gate("apply_param_named_phase_func", || unsafe {
             ffi::applyParamNamedPhaseFunc(
                 self.reg,
                 qubits.as_ptr(),
@@ -6523,10 +11474,10 @@ impl<'a> Qureg<'a> {
         override_inds: &[i64],
         override_phases: &[Qreal],
     ) -> Result<(), QuestError> {
-        let num_regs = num_qubits_per_reg.len() as i32;
-        let num_params = params.len() as i32;
-        let num_overrides = override_phases.len() as i32;
-        catch_quest_exception(|| unsafe {
+        let num_regs = checked_len(num_qubits_per_reg.len())?;
+        let num_params = checked_len(params.len())?;
+        let num_overrides = checked_len(override_phases.len())?;
+        catch_gate("apply_param_named_phase_func_overrides", || unsafe {
             ffi::applyParamNamedPhaseFuncOverrides(
                 self.reg,
                 qubits.as_ptr(),
@@ -6588,7 +11539,7 @@ impl<'a> Qureg<'a> {
     /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn apply_full_qft(&mut self) {
-        catch_quest_exception(|| unsafe {
+        catch_gate("apply_full_qft", || unsafe {
             ffi::applyFullQFT(self.reg);
         })
         .expect("apply_full_qft should always succeed");
@@ -6615,26 +11566,311 @@ impl<'a> Qureg<'a> {
     /// the QFT circuit directly.
     ///
     ///
-    /// Furthermore, in distributed mode, this function requires only
-    /// `log2(#nodes)` rounds of pair-wise communication, and hence is
-    /// exponentially faster than directly performing the DFT on the
-    /// amplitudes of `qureg`.
+    /// Furthermore, in distributed mode, this function requires only
+    /// `log2(#nodes)` rounds of pair-wise communication, and hence is
+    /// exponentially faster than directly performing the DFT on the
+    /// amplitudes of `qureg`.
+    ///
+    /// See [`apply_full_qft()`] to apply the QFT to he entirety
+    /// of `Qureg`.
+    ///
+    /// # Parameters
+    ///
+    /// `qureg`: a state-vector or density matrix to modify
+    /// `qubits` a list of the qubits to operate the QFT upon
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if the length of `qubits` is less than [`qureg.num_qubits()`]
+    ///   - if any of `qubits` is outside [0, [`num_qubits()`]).
+    ///   - if `qubits` contains any repetitions
+    ///
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.apply_qft(&[0, 1]).unwrap();
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`apply_full_qft()`]: crate::Qureg::apply_full_qft()
+    /// [`apply_named_phase_func()`]: crate::Qureg::apply_named_phase_func()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn apply_qft(
+        &mut self,
+        qubits: &[i32],
+    ) -> Result<(), QuestError> {
+        let num_qubits = checked_len(qubits.len())?;
+        catch_gate("apply_qft", || unsafe {
+            ffi::applyQFT(self.reg, qubits.as_ptr(), num_qubits);
+        })
+    }
+
+    /// Apply the inverse of the full quantum Fourier transform (QFT).
+    ///
+    /// This undoes [`apply_full_qft()`], and is the same as
+    /// [`apply_inverse_qft()`] targeting every qubit in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// qureg.apply_full_inverse_qft();
+    ///
+    /// let amp = qureg.get_real_amp(0).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`apply_full_qft()`]: crate::Qureg::apply_full_qft()
+    /// [`apply_inverse_qft()`]: crate::Qureg::apply_inverse_qft()
+    pub fn apply_full_inverse_qft(&mut self) {
+        let qubits = (0..self.num_qubits()).collect::<Vec<_>>();
+        self.apply_inverse_qft(&qubits)
+            .expect("apply_full_inverse_qft should always succeed");
+    }
+
+    /// Apply the inverse quantum Fourier transform (QFT) to a specific subset
+    /// of qubits.
+    ///
+    /// This undoes [`apply_qft()`] with the same `qubits`, in the same order,
+    /// e.g. at the end of the phase estimation algorithm.  It works for both
+    /// state-vectors and density matrices.
+    ///
+    /// The circuit of [`apply_qft()`] is reversed: the qubits are swapped
+    /// first, and then each qubit is acted on by the conjugated
+    /// controlled-phase gates, merged into a single call to
+    /// [`apply_param_named_phase_func()`], followed by the Hadamard gate.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: a list of the qubits to operate the inverse QFT upon
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qubits` is empty
+    ///   - if any of `qubits` is outside [0, [`num_qubits()`]).
+    ///   - if `qubits` contains any repetitions
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(5).unwrap();
+    ///
+    /// qureg.apply_qft(&[2, 0]).unwrap();
+    /// qureg.apply_inverse_qft(&[2, 0]).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(5).unwrap();
+    /// assert!((amp - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`apply_qft()`]: crate::Qureg::apply_qft()
+    /// [`apply_param_named_phase_func()`]: crate::Qureg::apply_param_named_phase_func()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    pub fn apply_inverse_qft(
+        &mut self,
+        qubits: &[i32],
+    ) -> Result<(), QuestError> {
+        if qubits.is_empty() {
+            return Err(invalid_input_error(
+                "Invalid number of target qubits. Must be >0 and <=numQubits.",
+                "apply_inverse_qft",
+            ));
+        }
+        validate_unique_qubits(self, qubits, "apply_inverse_qft")?;
+
+        let num_qubits = qubits.len();
+        for i in 0..num_qubits / 2 {
+            self.swap_gate(qubits[i], qubits[num_qubits - 1 - i])?;
+        }
+        for q in 0..num_qubits {
+            if q > 0 {
+                // phase -PI/2^q * (value of qubits[..q]) * qubits[q]
+                let num_lower = checked_len(q)?;
+                self.apply_param_named_phase_func(
+                    &qubits[..=q],
+                    &[num_lower, 1],
+                    BitEncoding::UNSIGNED,
+                    PhaseFunc::SCALED_PRODUCT,
+                    &[-PI / Qreal::powi(2., num_lower)],
+                )?;
+            }
+            self.hadamard(qubits[q])?;
+        }
+        Ok(())
+    }
+
+    /// Apply a projector.
+    ///
+    /// Force the target `qubit` of `qureg` into the given classical `outcome`,
+    /// via a non-renormalising projection.
+    ///
+    /// This function zeroes all amplitudes in the state-vector or
+    /// density-matrix which correspond to the opposite `outcome` given.
+    /// Unlike [`collapse_to_outcome()`], it does not thereafter normalise
+    /// `qureg`, and hence may leave it in a non-physical state.
+    ///
+    /// Note there is no requirement that the `outcome` state has a non-zero
+    /// proability, and hence this function may leave `qureg` in a blank state,
+    /// like that produced by [`init_blank_state()`].
+    ///
+    /// See [`collapse_to_outcome()`] for a norm-preserving equivalent, like a
+    /// forced measurement
+    ///
+    /// # Parameters
+    ///
+    /// - `qubit`: the qubit to which to apply the projector
+    /// - `outcome`: the single-qubit outcome (`0` or `1`) to project `qubit`
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `qubit` is outside [0, [`num_qubits()`]).
+    ///   - if `outcome` is not in {0,1}
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    ///
+    /// qureg.apply_projector(0, 0).unwrap();
+    ///
+    /// let amp = qureg.get_real_amp(3).unwrap();
+    /// assert!(amp.abs() < EPSILON);
+    /// ```
+    ///
+    /// See [QuEST API] for more information.
+    ///
+    /// [`collapse_to_outcome()`]: crate::Qureg::collapse_to_outcome()
+    /// [`init_blank_state()`]: crate::Qureg::init_blank_state()
+    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
+    #[allow(clippy::needless_pass_by_ref_mut)]
+    pub fn apply_projector(
+        &mut self,
+        qubit: i32,
+        outcome: i32,
+    ) -> Result<(), QuestError> {
+        catch_gate("apply_projector", || unsafe {
+            ffi::applyProjector(self.reg, qubit, outcome);
+        })
+    }
+
+    /// Force the target qubits into the given bit pattern, via projectors.
+    ///
+    /// Applies [`apply_projector()`] for each pair of `qubits[k]` and
+    /// `bits[k]`.  This sets to zero all amplitudes of `qureg` (or rows and
+    /// columns of a density matrix) inconsistent with the whole bit pattern.
+    /// Like [`apply_projector()`], this function does not renormalise
+    /// `qureg`, and may leave it in a blank state.
+    ///
+    /// # Parameters
+    ///
+    /// - `qubits`: the qubits to which to apply the projectors
+    /// - `bits`: the outcomes (`0` or `1`) to project the corresponding qubits
+    ///   in `qubits` onto
+    ///
+    /// # Errors
+    ///
+    /// - [`ArrayLengthError`],
+    ///   - if `qubits` and `bits` have different lengths
+    /// - [`InvalidQuESTInputError`],
+    ///   - if any qubit in `qubits` is outside [0, [`num_qubits()`])
+    ///   - if `qubits` contain any repetitions
+    ///   - if any element of `bits` is not in {0,1}
+    ///
+    /// All inputs are validated before the state is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// // project qubits 0 and 2 onto |1> and |0> respectively
+    /// qureg.apply_projector_bits(&[0, 2], &[1, 0]).unwrap();
+    ///
+    /// let prob = qureg.calc_total_prob();
+    /// assert!((prob - 0.25).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`apply_projector()`]: crate::Qureg::apply_projector()
+    /// [`num_qubits()`]: crate::Qureg::num_qubits()
+    /// [`ArrayLengthError`]: crate::QuestError::ArrayLengthError
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn apply_projector_bits(
+        &mut self,
+        qubits: &[i32],
+        bits: &[u8],
+    ) -> Result<(), QuestError> {
+        if qubits.len() != bits.len() {
+            return Err(QuestError::ArrayLengthError);
+        }
+        validate_unique_qubits(self, qubits, "apply_projector_bits")?;
+        if bits.iter().any(|&bit| bit > 1) {
+            return Err(invalid_input_error(
+                "Invalid measurement outcome -- must be either 0 or 1.",
+                "apply_projector_bits",
+            ));
+        }
+        for (&qubit, &bit) in qubits.iter().zip(bits) {
+            self.apply_projector(qubit, i32::from(bit))?;
+        }
+        Ok(())
+    }
+
+    /// Apply Hamiltonian `PauliHamil` to `self`, writing the result to `out`.
+    ///
+    /// This is a method form of [`apply_pauli_hamil()`].  It modifies `out`
+    /// to be the result of applying `hamil` (a Hermitian but not necessarily
+    /// unitary operator) to `self`.  The initial state in `out` is not used.
     ///
-    /// See [`apply_full_qft()`] to apply the QFT to he entirety
-    /// of `Qureg`.
+    /// The state of `self` is logically unchanged.  It is, however,
+    /// temporarily modified and then reverted by re-applying Paulis
+    /// (XX=YY=ZZ=I), so it may see a change by small numerical errors.  This
+    /// is why `self` is borrowed mutably.
     ///
     /// # Parameters
     ///
-    /// `qureg`: a state-vector or density matrix to modify
-    /// `qubits` a list of the qubits to operate the QFT upon
+    /// - `hamil`: the Hamiltonian to apply
+    /// - `out`: register to store the result in
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if the length of `qubits` is less than [`qureg.num_qubits()`]
-    ///   - if any of `qubits` is outside [0, [`num_qubits()`]).
-    ///   - if `qubits` contains any repetitions
+    ///   - if `self` and `out` are not both state-vectors or both density
+    ///     matrices
+    ///   - if `self`, `out` and `hamil` act on different numbers of qubits
     ///
+    /// All dimensions are validated before calling `QuEST`.
     ///
     /// # Examples
     ///
@@ -6642,56 +11878,162 @@ impl<'a> Qureg<'a> {
     /// # use quest_bind::*;
     /// let env = QuestEnv::new();
     /// let mut qureg =
-    ///     Qureg::try_new(3, &env).expect("cannot allocate memory for Qureg");
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// let mut out =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_zero_state();
     ///
-    /// qureg.apply_qft(&[0, 1]).unwrap();
-    /// ```
+    /// let hamil = PauliHamilBuilder::new(2)
+    ///     .add_term(SQRT_2.recip(), "IX")
+    ///     .add_term(SQRT_2.recip(), "XI")
+    ///     .build()
+    ///     .unwrap();
+    /// qureg.apply_pauli_hamil_to(&hamil, &mut out).unwrap();
     ///
-    /// See [QuEST API] for more information.
+    /// // out is now in `|01> + |10>` state:
+    /// assert!((out.get_prob_amp(0b01).unwrap() - 0.5).abs() < EPSILON);
+    /// assert!((out.get_prob_amp(0b10).unwrap() - 0.5).abs() < EPSILON);
+    /// ```
     ///
-    /// [`apply_full_qft()`]: crate::Qureg::apply_full_qft()
-    /// [`apply_named_phase_func()`]: crate::Qureg::apply_named_phase_func()
+    /// [`apply_pauli_hamil()`]: crate::apply_pauli_hamil()
     /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn apply_qft(
+    pub fn apply_pauli_hamil_to(
         &mut self,
-        qubits: &[i32],
+        hamil: &PauliHamil,
+        out: &mut Qureg<'_>,
     ) -> Result<(), QuestError> {
-        let num_qubits = qubits.len() as i32;
-        catch_quest_exception(|| unsafe {
-            ffi::applyQFT(self.reg, qubits.as_ptr(), num_qubits);
-        })
+        let err_func = "apply_pauli_hamil_to";
+        validate_kind(out, self.kind(), err_func)?;
+        if out.num_qubits() != self.num_qubits() {
+            return Err(invalid_input_error(
+                "Dimensions of the qubit registers don't match.",
+                err_func,
+            ));
+        }
+        if hamil.0.numQubits != self.num_qubits() {
+            return Err(invalid_input_error(
+                "The PauliHamil must act on the same number of qubits as \
+                 exist in the Qureg.",
+                err_func,
+            ));
+        }
+        apply_pauli_hamil(self, hamil, out)
     }
+} // Qureg
 
-    /// Apply a projector.
+#[cfg(feature = "ndarray")]
+impl<'a> Qureg<'a> {
+    /// Copy the amplitudes of a state-vector into a one-dimensional array.
     ///
-    /// Force the target `qubit` of `qureg` into the given classical `outcome`,
-    /// via a non-renormalising projection.
+    /// The amplitude of the basis state with index `i` is stored at
+    /// position `i` of the returned array of length [`num_amps_total()`].
     ///
-    /// This function zeroes all amplitudes in the state-vector or
-    /// density-matrix which correspond to the opposite `outcome` given.
-    /// Unlike [`collapse_to_outcome()`], it does not thereafter normalise
-    /// `qureg`, and hence may leave it in a non-physical state.
+    /// In distributed mode, the amplitudes are gathered from all the nodes,
+    /// and each node receives the whole state-vector.  This requires one
+    /// broadcast per amplitude, and the full state-vector must fit in the
+    /// memory of every node.  The function must be called by all the nodes.
     ///
-    /// Note there is no requirement that the `outcome` state has a non-zero
-    /// proability, and hence this function may leave `qureg` in a blank state,
-    /// like that produced by [`init_blank_state()`].
+    /// This function is available only with the feature `"ndarray"` enabled.
     ///
-    /// See [`collapse_to_outcome()`] for a norm-preserving equivalent, like a
-    /// forced measurement
+    /// # Errors
     ///
-    /// # Parameters
+    /// - [`InvalidQuESTInputError`],
+    ///   - if `self` is a density matrix.  Use [`to_ndarray()`] instead.
     ///
-    /// - `qubit`: the qubit to which to apply the projector
-    /// - `outcome`: the single-qubit outcome (`0` or `1`) to project `qubit`
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg =
+    ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// qureg.init_classical_state(2).unwrap();
+    ///
+    /// let amps = qureg.to_ndarray1().unwrap();
+    /// assert_eq!(amps.len(), 4);
+    /// assert!((amps[2].re - 1.).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`num_amps_total()`]: crate::Qureg::num_amps_total()
+    /// [`to_ndarray()`]: crate::Qureg::to_ndarray()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn to_ndarray1(&self) -> Result<ndarray::Array1<Qcomplex>, QuestError> {
+        if self.is_density_matrix() {
+            return Err(invalid_input_error(
+                "Expected a state-vector, found a density matrix. Use \
+                 to_ndarray() instead.",
+                "to_ndarray1",
+            ));
+        }
+        (0..self.num_amps_total())
+            .map(|index| self.get_amp(index))
+            .collect()
+    }
+
+    /// Copy the elements of a density matrix into a two-dimensional array.
+    ///
+    /// The element `rho[(row, col)]` of the returned `2^N`-by-`2^N` array is
+    /// the same as the value of [`get_density_amp(row, col)`].
+    ///
+    /// In distributed mode, the elements are gathered from all the nodes,
+    /// and each node receives the whole density matrix.  This requires one
+    /// broadcast per element, and the full matrix must fit in the memory of
+    /// every node.  The function must be called by all the nodes.
+    ///
+    /// This function is available only with the feature `"ndarray"` enabled.
     ///
     /// # Errors
     ///
     /// - [`InvalidQuESTInputError`],
-    ///   - if `qubit` is outside [0, [`num_qubits()`]).
-    ///   - if `outcome` is not in {0,1}
+    ///   - if `self` is a state-vector.  Use [`to_ndarray1()`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use quest_bind::*;
+    /// let env = QuestEnv::new();
+    /// let mut qureg = Qureg::try_new_density(2, &env)
+    ///     .expect("cannot allocate memory for Qureg");
+    /// qureg.init_plus_state();
+    ///
+    /// let rho = qureg.to_ndarray().unwrap();
+    /// assert_eq!(rho.dim(), (4, 4));
+    /// assert!((rho[(1, 2)].re - 0.25).abs() < EPSILON);
+    /// ```
+    ///
+    /// [`get_density_amp(row, col)`]: crate::Qureg::get_density_amp()
+    /// [`to_ndarray1()`]: crate::Qureg::to_ndarray1()
+    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+    pub fn to_ndarray(&self) -> Result<ndarray::Array2<Qcomplex>, QuestError> {
+        if !self.is_density_matrix() {
+            return Err(invalid_input_error(
+                "Expected a density matrix, found a state-vector. Use \
+                 to_ndarray1() instead.",
+                "to_ndarray",
+            ));
+        }
+        let dim = 1_usize << self.num_qubits();
+        let mut rho = ndarray::Array2::zeros((dim, dim));
+        for ((row, col), amp) in rho.indexed_iter_mut() {
+            *amp = self.get_density_amp(row as i64, col as i64)?;
+        }
+        Ok(rho)
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl<'a> Qureg<'a> {
+    /// Report the accumulated wall-clock time spent in each gate method.
+    ///
+    /// Every call to a method applying a gate, a decoherence channel or
+    /// another operator to a register adds the time it took to an
+    /// accumulator keyed by the name of the method, e.g. `"hadamard"`.
+    /// Methods composed of other gates, like [`toffoli()`], are recorded
+    /// under the names of their constituents.  The accumulators are
+    /// thread-local and shared by all registers on the current thread.
+    ///
+    /// This function is available only with the feature `"profiling"`
+    /// enabled.  Without it, no time is measured.
     ///
     /// # Examples
     ///
@@ -6700,32 +12042,35 @@ impl<'a> Qureg<'a> {
     /// let env = QuestEnv::new();
     /// let mut qureg =
     ///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+    /// Qureg::reset_timing_report();
     ///
-    /// qureg.apply_projector(0, 0).unwrap();
+    /// qureg.hadamard(0).unwrap();
+    /// qureg.controlled_not(0, 1).unwrap();
     ///
-    /// let amp = qureg.get_real_amp(3).unwrap();
-    /// assert!(amp.abs() < EPSILON);
+    /// let report = Qureg::timing_report();
+    /// assert!(report.contains_key("hadamard"));
+    /// assert!(report.contains_key("controlled_not"));
     /// ```
     ///
-    /// See [QuEST API] for more information.
+    /// [`toffoli()`]: crate::Qureg::toffoli()
+    #[must_use]
+    pub fn timing_report(
+    ) -> std::collections::HashMap<&'static str, std::time::Duration> {
+        GATE_TIMINGS.with(|timings| timings.borrow().clone())
+    }
+
+    /// Clear the accumulated gate timings of the current thread.
     ///
-    /// [`collapse_to_outcome()`]: crate::Qureg::collapse_to_outcome()
-    /// [`init_blank_state()`]: crate::Qureg::init_blank_state()
-    /// [`QubitIndexError`]: crate::QuestError::QubitIndexError
-    /// [`num_qubits()`]: crate::Qureg::num_qubits()
-    /// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
-    /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    pub fn apply_projector(
-        &mut self,
-        qubit: i32,
-        outcome: i32,
-    ) -> Result<(), QuestError> {
-        catch_quest_exception(|| unsafe {
-            ffi::applyProjector(self.reg, qubit, outcome);
-        })
+    /// See [`timing_report()`] for more information.
+    ///
+    /// This function is available only with the feature `"profiling"`
+    /// enabled.
+    ///
+    /// [`timing_report()`]: crate::Qureg::timing_report()
+    pub fn reset_timing_report() {
+        GATE_TIMINGS.with(|timings| timings.borrow_mut().clear());
     }
-} // Qureg
+}
 
 impl<'a> Drop for Qureg<'a> {
     fn drop(&mut self) {
@@ -6736,6 +12081,36 @@ impl<'a> Drop for Qureg<'a> {
     }
 }
 
+/// Print the parameters of the register, but never its amplitudes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let qureg =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+///
+/// let debug = format!("{qureg:?}");
+/// assert!(
+///     debug.starts_with("Qureg { num_qubits: 2, is_density_matrix: false")
+/// );
+/// ```
+impl<'a> fmt::Debug for Qureg<'a> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("Qureg")
+            .field("num_qubits", &self.num_qubits())
+            .field("is_density_matrix", &self.is_density_matrix())
+            .field("num_amps_total", &self.num_amps_total())
+            .field("num_chunks", &self.reg.numChunks)
+            .field("gpu_accelerated", &self.is_gpu_accelerated())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Apply Hamiltonian `PauliHamil`.
 ///
 /// Modifies `out_qureg` to be the result of applying `PauliHamil` (a
@@ -6774,8 +12149,11 @@ impl<'a> Drop for Qureg<'a> {
 /// assert!(qb1 != qb2);
 /// ```
 ///
+/// See also the method form: [`Qureg::apply_pauli_hamil_to()`].
+///
 /// See [QuEST API] for more information.
 ///
+/// [`Qureg::apply_pauli_hamil_to()`]: crate::Qureg::apply_pauli_hamil_to()
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub fn apply_pauli_hamil(
@@ -6836,7 +12214,7 @@ pub fn apply_pauli_sum(
     term_coeffs: &[Qreal],
     out_qureg: &mut Qureg<'_>,
 ) -> Result<(), QuestError> {
-    let num_sum_terms = term_coeffs.len() as i32;
+    let num_sum_terms = checked_len(term_coeffs.len())?;
     catch_quest_exception(|| unsafe {
         ffi::applyPauliSum(
             in_qureg.reg,
@@ -6860,6 +12238,10 @@ pub fn apply_pauli_sum(
 ///
 /// This function correctly returns the result of the above formulations even
 /// when `a` and `b` are incorrectly normalised (i.e. are general matrices).
+/// No normalization is performed: the distance between `rho` and `2 * rho`
+/// is the Frobenius norm of `rho`, not zero.  With the feature
+/// `"warn-unnormalized"` enabled, a warning is logged if the trace of either
+/// `a` or `b` deviates from 1.
 ///
 /// # Parameters
 ///
@@ -6898,6 +12280,11 @@ pub fn calc_hilbert_schmidt_distance(
     a: &Qureg<'_>,
     b: &Qureg<'_>,
 ) -> Result<Qreal, QuestError> {
+    #[cfg(feature = "warn-unnormalized")]
+    {
+        warn_if_unnormalized(a, "calc_hilbert_schmidt_distance");
+        warn_if_unnormalized(b, "calc_hilbert_schmidt_distance");
+    }
     catch_quest_exception(|| unsafe {
         ffi::calcHilbertSchmidtDistance(a.reg, b.reg)
     })
@@ -6966,6 +12353,32 @@ pub fn calc_inner_product(
 
 /// Computes the Hilbert-Schmidt scalar product.
 ///
+/// This is the real part of the Frobenius inner product of two equal-size
+/// density matrices:
+///
+/// ```latex
+///  \text{Re}\,\text{Tr}(\rho_1^\dagger \rho_2) = \text{Re} \sum_{ij}
+///  (\rho_1)_{ij}^* (\rho_2)_{ij}
+/// ```
+///
+/// For valid density matrices this is real and lies in `[0, 1]`.  The inputs
+/// are not normalized: if `rho1` or `rho2` are general matrices (e.g.
+/// prepared with [`set_density_amps()`]), this returns the literal value of
+/// the formula above, which is not bounded and can be negative.  With the
+/// feature `"warn-unnormalized"` enabled, a warning is logged if the trace
+/// of either `rho1` or `rho2` deviates from 1.
+///
+/// # Parameters
+///
+/// - `rho1`: a density matrix
+/// - `rho2`: an equally-sized density matrix
+///
+/// # Errors
+///
+/// - [`InvalidQuESTInputError`]
+///   - if either `rho1` or `rho2` are not density matrices
+///   - if `rho1` and `rho2` have mismatching dimension
+///
 /// # Examples
 ///
 /// ```rust
@@ -6986,11 +12399,18 @@ pub fn calc_inner_product(
 ///
 /// See [QuEST API] for more information.
 ///
+/// [`set_density_amps()`]: crate::Qureg::set_density_amps()
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
 /// [QuEST API]: https://quest-kit.github.io/QuEST/modules.html
 pub fn calc_density_inner_product(
     rho1: &Qureg<'_>,
     rho2: &Qureg<'_>,
 ) -> Result<Qreal, QuestError> {
+    #[cfg(feature = "warn-unnormalized")]
+    {
+        warn_if_unnormalized(rho1, "calc_density_inner_product");
+        warn_if_unnormalized(rho2, "calc_density_inner_product");
+    }
     catch_quest_exception(|| unsafe {
         ffi::calcDensityInnerProduct(rho1.reg, rho2.reg)
     })
@@ -7031,3 +12451,406 @@ pub fn set_weighted_qureg(
         );
     })
 }
+
+/// Compute the tensor product of two registers.
+///
+/// Allocates a new `Qureg` of `a.num_qubits() + b.num_qubits()` qubits and
+/// initializes it to the state `a ⊗ b`.  The qubits of `b` become the
+/// qubits `0..b.num_qubits()` of the new register, and the qubits of `a`
+/// are placed right after them.
+///
+/// Both `a` and `b` must be either state-vectors or density matrices.  For
+/// density matrices, the new register is the density matrix of the product
+/// state.
+///
+/// # Parameters
+///
+/// - `a`: the register occupying the most significant qubits
+/// - `b`: the register occupying the least significant qubits
+/// - `env`: the runtime environment to allocate the new register in
+///
+/// # Errors
+///
+/// - [`InvalidQuESTInputError`],
+///   - if one of the registers is a state-vector and the other is a density
+///     matrix
+///   - if the new register cannot be allocated
+/// - [`AllocationError`],
+///   - if the size of the new register overflows the address space
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// let env = QuestEnv::new();
+/// let mut a =
+///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+/// let mut b =
+///     Qureg::try_new(1, &env).expect("cannot allocate memory for Qureg");
+/// a.init_classical_state(1).unwrap();
+/// b.init_zero_state();
+///
+/// let qureg = tensor(&a, &b, &env).unwrap();
+/// assert_eq!(qureg.num_qubits(), 2);
+///
+/// // The register is now in the state |10>
+/// let amp = qureg.get_real_amp(2).unwrap();
+/// assert!((amp - 1.).abs() < EPSILON);
+/// ```
+///
+/// [`InvalidQuESTInputError`]: crate::QuestError::InvalidQuESTInputError
+/// [`AllocationError`]: crate::QuestError::AllocationError
+pub fn tensor<'a>(
+    a: &Qureg<'_>,
+    b: &Qureg<'_>,
+    env: &'a QuestEnv,
+) -> Result<Qureg<'a>, QuestError> {
+    validate_kind(b, a.kind(), "tensor")?;
+    let num_qubits = a.num_qubits() + b.num_qubits();
+    let dim_a = 1_i64 << a.num_qubits();
+    let dim_b = 1_i64 << b.num_qubits();
+
+    if !a.is_density_matrix() {
+        let mut qureg = Qureg::try_new(num_qubits, env)?;
+        let amps_a = (0..dim_a)
+            .map(|i| a.get_amp(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let amps_b = (0..dim_b)
+            .map(|i| b.get_amp(i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (reals, imags): (Vec<_>, Vec<_>) = amps_a
+            .iter()
+            .flat_map(|x| amps_b.iter().map(move |y| x * y))
+            .map(|z| (z.re, z.im))
+            .unzip();
+
+        qureg.init_state_from_amps(&reals, &imags)?;
+        return Ok(qureg);
+    }
+
+    // Allocate the register first, so that its size is validated before the
+    // buffers are allocated
+    let mut qureg = Qureg::try_new_density(num_qubits, env)?;
+    let num_amps = usize::try_from(qureg.num_amps_total())
+        .map_err(|_| QuestError::AllocationError)?;
+    let mut reals = Vec::with_capacity(num_amps);
+    let mut imags = Vec::with_capacity(num_amps);
+    // Amplitudes of a density matrix are stored column by column
+    for col in 0..dim_a * dim_b {
+        for row in 0..dim_a * dim_b {
+            let z = a.get_density_amp(row / dim_b, col / dim_b)?
+                * b.get_density_amp(row % dim_b, col % dim_b)?;
+            reals.push(z.re);
+            imags.push(z.im);
+        }
+    }
+
+    qureg.set_density_amps(0, 0, &reals, &imags)?;
+    Ok(qureg)
+}
+
+/// Construct [`QuestError::InvalidQuESTInputError`] for a failure detected
+/// before calling `QuEST`.
+fn invalid_input_error(
+    err_msg: &str,
+    err_func: &str,
+) -> QuestError {
+    QuestError::InvalidQuESTInputError {
+        err_msg:  err_msg.to_owned(),
+        err_func: err_func.to_owned(),
+    }
+}
+
+/// Convert the length of a slice passed to `QuEST` into `i32`.
+fn checked_len(len: usize) -> Result<i32, QuestError> {
+    i32::try_from(len).map_err(|_| QuestError::ArrayLengthError)
+}
+
+/// Check if `qureg` is of the `expected` kind.
+fn validate_kind(
+    qureg: &Qureg<'_>,
+    expected: QuregKind,
+    err_func: &str,
+) -> Result<(), QuestError> {
+    let found = qureg.kind();
+    if found == expected {
+        Ok(())
+    } else {
+        Err(invalid_input_error(
+            &format!("Expected {expected}, found {found}."),
+            err_func,
+        ))
+    }
+}
+
+/// Check if `qubits` are valid, distinct qubit indices of `qureg`.
+fn validate_unique_qubits(
+    qureg: &Qureg<'_>,
+    qubits: &[i32],
+    err_func: &str,
+) -> Result<(), QuestError> {
+    for (i, qubit) in qubits.iter().enumerate() {
+        if *qubit < 0 || *qubit >= qureg.num_qubits() {
+            return Err(invalid_input_error(
+                "Invalid target qubit. Must be >=0 and <numQubits.",
+                err_func,
+            ));
+        }
+        if qubits[..i].contains(qubit) {
+            return Err(invalid_input_error(
+                "The target qubits must be unique.",
+                err_func,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check if `ctrls` are valid, distinct qubit indices of `qureg`, disjoint
+/// from `targets`.
+fn validate_control_qubits(
+    qureg: &Qureg<'_>,
+    ctrls: &[i32],
+    targets: &[i32],
+    err_func: &str,
+) -> Result<(), QuestError> {
+    for (i, ctrl) in ctrls.iter().enumerate() {
+        if *ctrl < 0 || *ctrl >= qureg.num_qubits() {
+            return Err(invalid_input_error(
+                "Invalid control qubit. Must be >=0 and <numQubits.",
+                err_func,
+            ));
+        }
+        if ctrls[..i].contains(ctrl) {
+            return Err(invalid_input_error(
+                "The control qubits should be unique.",
+                err_func,
+            ));
+        }
+        if targets.contains(ctrl) {
+            return Err(invalid_input_error(
+                "Control and target qubits must be disjoint.",
+                err_func,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check if the size of a register of `kind` with `num_qubits` fits in the
+/// address space.  This says nothing about the available memory.
+///
+/// The number of amplitudes must fit in `i64` and `usize`, and the total size
+/// in bytes (real and imaginary parts) must not exceed `isize::MAX`.
+fn validate_alloc_size(
+    num_qubits: i32,
+    kind: QuregKind,
+    err_func: &str,
+) -> Result<(), QuestError> {
+    if num_qubits <= 0 {
+        return Err(invalid_input_error(
+            "Invalid number of qubits. Must create >0.",
+            err_func,
+        ));
+    }
+    let exp = match kind {
+        QuregKind::StateVector => Some(num_qubits),
+        QuregKind::DensityMatrix => num_qubits.checked_mul(2),
+    };
+    let num_amps = exp
+        .and_then(|exp| u32::try_from(exp).ok())
+        .and_then(|exp| 1_i64.checked_shl(exp))
+        .filter(|&num_amps| num_amps > 0);
+    let num_bytes = num_amps
+        .and_then(|num_amps| usize::try_from(num_amps).ok())
+        .and_then(|num_amps| {
+            num_amps.checked_mul(2 * std::mem::size_of::<Qreal>())
+        })
+        .filter(|&num_bytes| isize::try_from(num_bytes).is_ok());
+    match num_bytes {
+        Some(_) => Ok(()),
+        None => Err(QuestError::AllocationError),
+    }
+}
+
+/// Check if Pauli error probabilities are valid for [`Qureg::mix_pauli()`].
+///
+/// Each probability must lie in `[0, 1]` and must not exceed the probability
+/// of no error, `1 - prob_x - prob_y - prob_z`.
+fn validate_pauli_probs(
+    prob_x: Qreal,
+    prob_y: Qreal,
+    prob_z: Qreal,
+    err_func: &str,
+) -> Result<(), QuestError> {
+    let probs = [
+        ("X", "prob_x", prob_x),
+        ("Y", "prob_y", prob_y),
+        ("Z", "prob_z", prob_z),
+    ];
+    for (pauli, name, prob) in probs {
+        if !(0. ..=1.).contains(&prob) {
+            return Err(invalid_input_error(
+                &format!(
+                    "The probability of a Pauli {pauli} error, {name} = \
+                     {prob}, must be in [0, 1]."
+                ),
+                err_func,
+            ));
+        }
+    }
+    let prob_no_error = 1. - prob_x - prob_y - prob_z;
+    for (pauli, name, prob) in probs {
+        if prob > prob_no_error {
+            return Err(invalid_input_error(
+                &format!(
+                    "The probability of a Pauli {pauli} error, {name} = \
+                     {prob}, must not exceed the probability of no error, 1 - \
+                     prob_x - prob_y - prob_z = {prob_no_error}."
+                ),
+                err_func,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Range of global amplitude indices stored in the chunk of `reg`.
+pub(crate) fn chunk_index_range(reg: &ffi::Qureg) -> std::ops::Range<i64> {
+    let start = i64::from(reg.chunkId) * reg.numAmpsPerChunk;
+    start..start + reg.numAmpsPerChunk
+}
+
+#[cfg(feature = "profiling")]
+thread_local! {
+    static GATE_TIMINGS: std::cell::RefCell<
+        std::collections::HashMap<&'static str, std::time::Duration>,
+    > = std::cell::RefCell::default();
+}
+
+/// Call a gate with [`catch_quest_exception()`].
+///
+/// With the feature `"profiling"` enabled, the wall-clock time of the call
+/// is added to the accumulator of `gate`.  Otherwise, this is the same as
+/// calling [`catch_quest_exception()`] directly.
+#[inline]
+fn catch_gate<T, F>(
+    gate: &'static str,
+    f: F,
+) -> Result<T, QuestError>
+where
+    F: FnOnce() -> T + UnwindSafe,
+{
+    #[cfg(feature = "profiling")]
+    {
+        let start = std::time::Instant::now();
+        let res = catch_quest_exception(f);
+        let elapsed = start.elapsed();
+        GATE_TIMINGS.with(|timings| {
+            *timings.borrow_mut().entry(gate).or_default() += elapsed;
+        });
+        res
+    }
+    #[cfg(not(feature = "profiling"))]
+    {
+        let _ = gate;
+        catch_quest_exception(f)
+    }
+}
+
+/// Log a warning if the total probability of `qureg` deviates from 1.
+#[cfg(feature = "warn-unnormalized")]
+fn warn_if_unnormalized(
+    qureg: &Qureg<'_>,
+    func: &str,
+) {
+    let total_prob = qureg.calc_total_prob();
+    if (total_prob - 1.).abs() > EPSILON.sqrt() {
+        log::warn!(
+            "Unnormalized register passed to {func}: total probability is \
+             {total_prob}"
+        );
+    }
+}
+
+/// Check if `len` amplitudes starting at `start` lie within `qureg`.
+fn validate_amps_range(
+    qureg: &Qureg<'_>,
+    start: i64,
+    len: usize,
+) -> Result<(), QuestError> {
+    let end = i64::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len));
+    match end {
+        Some(end) if start >= 0 && end <= qureg.num_amps_total() => Ok(()),
+        _ => Err(QuestError::ArrayLengthError),
+    }
+}
+
+/// Check if all elements of `control_state` are bits.
+fn validate_control_state(
+    control_state: &[i32],
+    err_func: &str,
+) -> Result<(), QuestError> {
+    if control_state.iter().all(|&bit| bit == 0 || bit == 1) {
+        Ok(())
+    } else {
+        Err(invalid_input_error(
+            "The state of the control qubits must be a bit sequence (0s and \
+             1s).",
+            err_func,
+        ))
+    }
+}
+
+/// Apply `f` to `qureg` with the controls conditioned on `0` flipped.
+///
+/// Every qubit in `ctrls`, whose corresponding bit in `ctrl_state` is `0`,
+/// is flipped with [`Qureg::pauli_x()`] before calling `f`, and flipped back
+/// afterwards.  This turns a gate controlled on `|1>` into one controlled on
+/// the state `ctrl_state`.
+fn with_flipped_controls(
+    qureg: &mut Qureg<'_>,
+    ctrls: &[i32],
+    ctrl_state: &[i32],
+    f: impl FnOnce(&mut Qureg<'_>) -> Result<(), QuestError>,
+) -> Result<(), QuestError> {
+    let flipped = ctrls
+        .iter()
+        .zip(ctrl_state)
+        .filter_map(|(&qubit, &bit)| (bit == 0).then_some(qubit))
+        .collect::<Vec<_>>();
+    for &qubit in &flipped {
+        qureg.pauli_x(qubit)?;
+    }
+    f(qureg)?;
+    for &qubit in &flipped {
+        qureg.pauli_x(qubit)?;
+    }
+    Ok(())
+}
+
+/// Collect the bit at the position `qubits[k]` of `index` as the `k`-th bit.
+///
+/// This is the inverse of [`scatter_bits()`].
+fn gather_bits(
+    index: i64,
+    qubits: &[i32],
+) -> i64 {
+    qubits
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (k, q)| acc | (((index >> q) & 1) << k))
+}
+
+/// Place the `k`-th bit of `bits` at the position `qubits[k]`.
+fn scatter_bits(
+    bits: i64,
+    qubits: &[i32],
+) -> i64 {
+    qubits
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (k, q)| acc | (((bits >> k) & 1) << q))
+}
@@ -0,0 +1,105 @@
+//! Helpers for testing code that uses `quest_bind`.
+//!
+//! The assertions in this module compare amplitudes up to a given tolerance,
+//! and panic with a message that identifies the offending amplitude.
+//!
+//! This module is available only with the feature `"test-util"` enabled.
+
+use crate::{
+    Qcomplex,
+    Qreal,
+    Qureg,
+};
+
+/// Assert that an amplitude of `qureg` is close to `expected`.
+///
+/// The amplitude at `index` is read with [`Qureg::get_flat_amp()`], i.e. for
+/// density matrices, `index` refers to the matrix flattened column-wise.
+/// The amplitude is close to `expected`, if their difference has the
+/// absolute value at most `eps`.
+///
+/// # Panics
+///
+/// This function panics if the amplitude cannot be read, or if it is not
+/// close to `expected`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// use quest_bind::test_util::assert_amp_close;
+///
+/// let env = QuestEnv::new();
+/// let mut qureg =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+/// qureg.init_plus_state();
+///
+/// assert_amp_close(&qureg, 3, 0.5, EPSILON);
+/// ```
+///
+/// [`Qureg::get_flat_amp()`]: crate::Qureg::get_flat_amp()
+#[track_caller]
+pub fn assert_amp_close(
+    qureg: &Qureg<'_>,
+    index: i64,
+    expected: impl Into<Qcomplex>,
+    eps: Qreal,
+) {
+    let expected = expected.into();
+    let amp = qureg.get_flat_amp(index).unwrap_or_else(|err| {
+        panic!("cannot read amplitude at index {index}: {err:?}")
+    });
+    assert!(
+        (amp - expected).norm() <= eps,
+        "amplitude at index {index} is not close to the expected value\n  \
+         found: {amp}\n  expected: {expected}\n  eps: {eps}"
+    );
+}
+
+/// Assert that two registers are in the same state, amplitude by amplitude.
+///
+/// The registers must be of the same kind and have the same number of
+/// qubits.  Each pair of amplitudes is compared as in
+/// [`assert_amp_close()`].  Note that states differing by a global phase
+/// factor are considered different.
+///
+/// # Panics
+///
+/// This function panics if the registers differ in kind or size, or at the
+/// first pair of amplitudes that are not close.
+///
+/// # Examples
+///
+/// ```rust
+/// # use quest_bind::*;
+/// use quest_bind::test_util::assert_state_close;
+///
+/// let env = QuestEnv::new();
+/// let mut a =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+/// let mut b =
+///     Qureg::try_new(2, &env).expect("cannot allocate memory for Qureg");
+///
+/// a.init_zero_state();
+/// a.hadamard(0).unwrap();
+/// a.hadamard(1).unwrap();
+/// b.init_plus_state();
+///
+/// assert_state_close(&a, &b, 10. * EPSILON);
+/// ```
+#[track_caller]
+pub fn assert_state_close(
+    a: &Qureg<'_>,
+    b: &Qureg<'_>,
+    eps: Qreal,
+) {
+    assert_eq!(a.kind(), b.kind(), "registers are of different kinds");
+    assert_eq!(
+        a.num_qubits(),
+        b.num_qubits(),
+        "registers have different numbers of qubits"
+    );
+    for index in 0..a.num_amps_total() {
+        assert_amp_close(a, index, b.get_flat_amp(index).unwrap(), eps);
+    }
+}
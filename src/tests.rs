@@ -7,6 +7,40 @@ use num::{
 
 use super::*;
 
+#[test]
+fn qubit_index_01() {
+    let env = &QuestEnv::new();
+    let qureg = &Qureg::try_new(3, env).unwrap();
+
+    assert_eq!(qureg.qubit_index(0), Ok(0));
+    assert_eq!(qureg.qubit_index(2), Ok(2));
+
+    assert_eq!(qureg.qubit_index(3), Err(QuestError::QubitIndexError));
+    assert_eq!(
+        qureg.qubit_index(i32::MAX as usize + 1),
+        Err(QuestError::QubitIndexError)
+    );
+    assert_eq!(
+        qureg.qubit_index(usize::MAX),
+        Err(QuestError::QubitIndexError)
+    );
+}
+
+#[test]
+fn prelude_01() {
+    use crate::prelude as p;
+
+    let env = &p::QuestEnv::new();
+    let qureg = &mut p::Qureg::try_new(1, env).unwrap();
+    qureg.init_plus_state();
+    assert_eq!(qureg.kind(), p::QuregKind::StateVector);
+
+    let prob: p::Qreal = qureg.calc_total_prob();
+    assert!((prob - 1.).abs() < p::EPSILON);
+
+    let _: p::QuestError = qureg.hadamard(1).unwrap_err();
+}
+
 #[test]
 fn create_qureg_01() -> Result<(), QuestError> {
     let env = &QuestEnv::new();
@@ -24,6 +58,29 @@ fn create_qureg_01() -> Result<(), QuestError> {
 //     let _ = Qureg::try_new_density(-1, env).unwrap_err();
 // }
 
+#[test]
+fn create_qureg_overflow_01() {
+    let env = &QuestEnv::new();
+
+    // The largest register, whose size in bytes still fits in `isize`.
+    let max_qubits =
+        (isize::BITS - 2 - (2 * std::mem::size_of::<Qreal>()).trailing_zeros())
+            as i32;
+
+    for num_qubits in [max_qubits + 1, 62, 63, 64, i32::MAX] {
+        assert_eq!(
+            Qureg::try_new(num_qubits, env).unwrap_err(),
+            QuestError::AllocationError
+        );
+    }
+    for num_qubits in [max_qubits / 2 + 1, 31, 32, i32::MAX / 2 + 1, i32::MAX] {
+        assert_eq!(
+            Qureg::try_new_density(num_qubits, env).unwrap_err(),
+            QuestError::AllocationError
+        );
+    }
+}
+
 #[test]
 fn create_density_qureg_01() -> Result<(), QuestError> {
     let env = &QuestEnv::new();
@@ -36,6 +93,574 @@ fn create_density_qureg_01() -> Result<(), QuestError> {
     Ok(())
 }
 
+#[test]
+fn assert_debug_state_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    qureg.init_zero_state();
+    qureg.assert_debug_state().unwrap_err();
+
+    qureg.init_debug_state();
+    qureg.assert_debug_state().unwrap();
+
+    qureg.hadamard(2).unwrap();
+    qureg.assert_debug_state().unwrap_err();
+
+    qureg.init_debug_state();
+    qureg.set_amps(5, &[0.], &[1.1]).unwrap();
+    let err = qureg.assert_debug_state().unwrap_err();
+    assert_eq!(
+        err,
+        QuestError::InvalidQuESTInputError {
+            err_msg:  format!(
+                "The amplitude at index 5 is {}, expected {}.",
+                Qcomplex::new(0., 1.1),
+                Qcomplex::new(1., 1.1)
+            ),
+            err_func: "assert_debug_state".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn assert_debug_state_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+
+    rho.init_debug_state();
+    rho.assert_debug_state().unwrap();
+
+    rho.init_plus_state();
+    rho.assert_debug_state().unwrap_err();
+}
+
+#[test]
+fn reset_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.reset();
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+    assert!((qureg.calc_total_prob() - 1.).abs() < EPSILON);
+
+    qureg.reset_density().unwrap_err();
+}
+
+#[test]
+fn reset_density_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.reset_density().unwrap();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = qureg.get_density_amp(row, col).unwrap();
+            let expected = if (row, col) == (0, 0) { 1. } else { 0. };
+            assert!((amp.re - expected).abs() < EPSILON);
+            assert!(amp.im.abs() < EPSILON);
+        }
+    }
+
+    qureg.init_plus_state();
+    qureg.reset();
+    let amp = qureg.get_density_amp(0, 0).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn init_plus_state_on_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+
+    qureg.init_plus_state_on(&[2, 0]).unwrap();
+    for i in 0..8 {
+        let expected = if i & 0b010 == 0 { 0.5 } else { 0. };
+        let amp = qureg.get_amp(i).unwrap();
+        assert!((amp.re - expected).abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+
+    qureg.init_plus_state_on(&[]).unwrap();
+    assert!((qureg.get_real_amp(0).unwrap() - 1.).abs() < EPSILON);
+
+    qureg.init_plus_state_on(&[0, 0]).unwrap_err();
+    qureg.init_plus_state_on(&[3]).unwrap_err();
+    qureg.init_plus_state_on(&[-1]).unwrap_err();
+    // the state is unchanged
+    assert!((qureg.get_real_amp(0).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn init_plus_state_on_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    let other = &mut Qureg::try_new_density(2, env).unwrap();
+
+    qureg.init_plus_state_on(&[0, 1]).unwrap();
+    other.init_plus_state();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = qureg.get_density_amp(row, col).unwrap();
+            let expected = other.get_density_amp(row, col).unwrap();
+            assert!((amp - expected).norm() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn init_w_state_01() {
+    let env = &QuestEnv::new();
+    for num_qubits in 1..6 {
+        let qureg = &mut Qureg::try_new(num_qubits, env).unwrap();
+        qureg.init_debug_state();
+        qureg.init_w_state().unwrap();
+
+        let expected = Qreal::from(num_qubits as u8).sqrt().recip();
+        for index in 0..qureg.num_amps_total() {
+            let amp = qureg.get_amp(index).unwrap();
+            if index.count_ones() == 1 {
+                assert!((amp.re - expected).abs() < EPSILON, "index: {index}");
+                assert!(amp.im.abs() < EPSILON, "index: {index}");
+            } else {
+                assert!(amp.norm() < EPSILON, "index: {index}");
+            }
+        }
+    }
+}
+
+#[test]
+fn init_w_state_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(3, env).unwrap();
+    rho.init_w_state().unwrap();
+
+    let expected = Qreal::from(3_u8).recip();
+    for row in [1, 2, 4] {
+        for col in [1, 2, 4] {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            assert!((amp.re - expected).abs() < EPSILON);
+        }
+    }
+    assert!((rho.calc_purity().unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn init_ghz_state_01() {
+    let env = &QuestEnv::new();
+    let expected = Qreal::from(2_u8).sqrt().recip();
+    for num_qubits in 1..6 {
+        let qureg = &mut Qureg::try_new(num_qubits, env).unwrap();
+        qureg.init_debug_state();
+        qureg.init_ghz_state().unwrap();
+
+        let last = qureg.num_amps_total() - 1;
+        for index in 0..=last {
+            let amp = qureg.get_amp(index).unwrap();
+            if index == 0 || index == last {
+                assert!((amp.re - expected).abs() < EPSILON, "index: {index}");
+                assert!(amp.im.abs() < EPSILON, "index: {index}");
+            } else {
+                assert!(amp.norm() < EPSILON, "index: {index}");
+            }
+        }
+    }
+}
+
+#[test]
+fn init_ghz_state_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+
+    // all qubits are measured in the same state
+    for _ in 0..10 {
+        qureg.init_ghz_state().unwrap();
+        let outcomes = (0..4)
+            .map(|qubit| qureg.measure(qubit).unwrap())
+            .collect::<Vec<_>>();
+        assert!(outcomes.iter().all(|&outcome| outcome == outcomes[0]));
+    }
+}
+
+#[test]
+fn with_applied_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+
+    let copy = qureg.with_applied(|_| Ok(())).unwrap();
+    assert_eq!(copy.num_qubits(), 3);
+    assert_eq!(copy.kind(), QuregKind::StateVector);
+    for i in 0..8 {
+        assert_eq!(copy.get_amp(i).unwrap(), qureg.get_amp(i).unwrap());
+    }
+
+    let flipped = qureg.with_applied(|q| q.pauli_x(2)).unwrap();
+    for i in 0..8 {
+        assert_eq!(flipped.get_amp(i ^ 4).unwrap(), qureg.get_amp(i).unwrap());
+    }
+    // the original register is not modified
+    qureg.assert_debug_state().unwrap();
+
+    qureg.with_applied(|q| q.pauli_x(3)).unwrap_err();
+}
+
+#[test]
+fn with_applied_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_zero_state();
+
+    let flipped = rho.with_applied(|q| q.pauli_x(1)).unwrap();
+    assert_eq!(flipped.kind(), QuregKind::DensityMatrix);
+    let amp = flipped.get_density_amp(2, 2).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+    let amp = rho.get_density_amp(0, 0).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn controlled_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    expected.init_debug_state();
+
+    let ret = qureg
+        .controlled(0, |q| {
+            q.rotate_x(1, 0.4)?;
+            q.pauli_y(2)?;
+            q.controlled_not(1, 2)?;
+            Ok(42)
+        })
+        .unwrap();
+    assert_eq!(ret, 42);
+
+    expected.controlled_rotate_x(0, 1, 0.4).unwrap();
+    expected.controlled_pauli_y(0, 2).unwrap();
+    expected
+        .multi_controlled_multi_qubit_not(&[0, 1], &[2])
+        .unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let exp = expected.get_amp(i).unwrap();
+        assert!((amp - exp).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn controlled_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.controlled(2, |q| q.pauli_x(1)).unwrap_err();
+    qureg.controlled(-1, |q| q.pauli_x(1)).unwrap_err();
+
+    // errors leave the register unchanged
+    qureg.controlled(0, |q| q.pauli_x(0)).unwrap_err();
+    qureg.controlled(0, |q| q.pauli_x(2)).unwrap_err();
+    for i in 0..4 {
+        let amp = qureg.get_real_amp(i).unwrap();
+        assert!((amp - 0.5).abs() < EPSILON);
+    }
+
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.controlled(0, |q| q.pauli_x(1)).unwrap_err();
+}
+
+#[test]
+fn density_from_pure_01() {
+    let env = &QuestEnv::new();
+    let pure = &mut Qureg::try_new(3, env).unwrap();
+    pure.init_plus_state();
+    pure.pauli_z(1).unwrap();
+
+    let qureg = Qureg::density_from_pure(pure, env).unwrap();
+    assert_eq!(qureg.kind(), QuregKind::DensityMatrix);
+    assert_eq!(qureg.num_qubits(), 3);
+
+    for row in 0..8 {
+        for col in 0..8 {
+            let expected =
+                pure.get_amp(row).unwrap() * pure.get_amp(col).unwrap().conj();
+            let amp = qureg.get_density_amp(row, col).unwrap();
+            assert!((amp - expected).norm() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn density_from_pure_02() {
+    let env = &QuestEnv::new();
+    let rho = &Qureg::try_new_density(2, env).unwrap();
+
+    let err = Qureg::density_from_pure(rho, env).unwrap_err();
+    assert_eq!(
+        err,
+        QuestError::InvalidQuESTInputError {
+            err_msg:  "Expected StateVector, found DensityMatrix.".to_owned(),
+            err_func: "density_from_pure".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn to_density_matrix_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_plus_state();
+
+    // noise cannot be applied to a state-vector directly
+    qureg.mix_dephasing(0, 0.2).unwrap_err();
+
+    let rho = &mut qureg.to_density_matrix(env).unwrap();
+    assert_eq!(rho.kind(), QuregKind::DensityMatrix);
+    assert_eq!(rho.num_qubits(), 1);
+    rho.mix_dephasing(0, 0.2).unwrap();
+
+    // the coherences are damped by the factor 1 - 2 * prob
+    for (row, col, expected) in
+        [(0, 0, 0.5), (1, 1, 0.5), (0, 1, 0.3), (1, 0, 0.3)]
+    {
+        let amp = rho.get_density_amp(row, col).unwrap();
+        assert!((amp.re - expected).abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+
+    // the state-vector is unchanged
+    let amp = qureg.get_real_amp(1).unwrap();
+    assert!((amp - Qreal::from(2_u8).sqrt().recip()).abs() < EPSILON);
+}
+
+#[test]
+fn to_density_matrix_02() {
+    let env = &QuestEnv::new();
+    let rho = &Qureg::try_new_density(2, env).unwrap();
+
+    let err = rho.to_density_matrix(env).unwrap_err();
+    assert_eq!(
+        err,
+        QuestError::InvalidQuESTInputError {
+            err_msg:  "Expected StateVector, found DensityMatrix.".to_owned(),
+            err_func: "density_from_pure".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn from_quest_file_01() {
+    let path = std::env::temp_dir().join(format!(
+        "quest_bind_from_quest_file_01_{}.csv",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        "# amplitudes\n0.5, 0.0\n0.5, 0.0\n0.0, 0.5\n0.0, -0.5\n",
+    )
+    .unwrap();
+    let filename = path.to_str().unwrap();
+
+    let env = &QuestEnv::new();
+    let qureg = Qureg::from_quest_file(filename, env).unwrap();
+    assert_eq!(qureg.num_qubits(), 2);
+    let expected = [(0.5, 0.), (0.5, 0.), (0., 0.5), (0., -0.5)];
+    for (i, (re, im)) in (0..).zip(expected) {
+        let amp = qureg.get_amp(i).unwrap();
+        assert!((amp.re - re).abs() < EPSILON);
+        assert!((amp.im - im).abs() < EPSILON);
+    }
+
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    other.init_state_from_file(filename).unwrap();
+    for i in 0..4 {
+        assert_eq!(other.get_amp(i).unwrap(), qureg.get_amp(i).unwrap());
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_quest_file_02() {
+    let path = std::env::temp_dir().join(format!(
+        "quest_bind_from_quest_file_02_{}.csv",
+        std::process::id()
+    ));
+    let filename = path.to_str().unwrap();
+    let env = &QuestEnv::new();
+
+    // no such file
+    Qureg::from_quest_file(filename, env).unwrap_err();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_state_from_file(filename).unwrap_err();
+    qureg.init_state_from_file("bad\0name").unwrap_err();
+
+    // the header written by report_state() is not ignored
+    std::fs::write(&path, "real, imag\n1.0, 0.0\n0.0, 0.0\n").unwrap();
+    Qureg::from_quest_file(filename, env).unwrap_err();
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn read_amps_binary_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_debug_state();
+
+    let mut bytes = Vec::new();
+    qureg.write_amps_chunked(&mut bytes, 5).unwrap();
+
+    let copy = Qureg::read_amps_binary(bytes.as_slice(), 4, env).unwrap();
+    assert!(!copy.is_density_matrix());
+    for index in 0..16 {
+        assert_eq!(copy.get_amp(index).unwrap(), qureg.get_amp(index).unwrap());
+    }
+}
+
+#[test]
+fn read_amps_binary_02() {
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(
+            &mut self,
+            _: &mut [u8],
+        ) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        }
+    }
+
+    let env = &QuestEnv::new();
+    let bytes = [0_u8; 4 * 16];
+
+    Qureg::read_amps_binary(&bytes[..], 2, env).unwrap();
+    assert_eq!(
+        Qureg::read_amps_binary(&bytes[..63], 2, env).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    assert_eq!(
+        Qureg::read_amps_binary(&bytes[..], 1, env).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    assert_eq!(
+        Qureg::read_amps_binary(FailingReader, 2, env).unwrap_err(),
+        QuestError::IoError(std::io::ErrorKind::PermissionDenied)
+    );
+    Qureg::read_amps_binary(&bytes[..], 0, env).unwrap_err();
+}
+
+#[test]
+fn density_dim_01() {
+    let env = &QuestEnv::new();
+    for num_qubits in 1..5 {
+        let rho = &Qureg::try_new_density(num_qubits, env).unwrap();
+        let dim = rho.density_dim().unwrap();
+        assert_eq!(dim, 1 << num_qubits);
+        assert_eq!(dim * dim, rho.num_amps_total());
+        rho.get_density_amp(dim - 1, dim - 1).unwrap();
+        rho.get_density_amp(dim, 0).unwrap_err();
+
+        let qureg = &Qureg::try_new(num_qubits, env).unwrap();
+        assert_eq!(qureg.density_dim(), None);
+    }
+}
+
+#[test]
+fn local_index_range_01() {
+    let env = &QuestEnv::new();
+    let qureg = &Qureg::try_new(3, env).unwrap();
+
+    let range = qureg.local_index_range();
+    for index in -1..=8 {
+        assert_eq!(qureg.owns_index(index), range.contains(&index));
+    }
+
+    // The chunks of all nodes partition the whole register
+    for qureg in [qureg, &Qureg::try_new_density(3, env).unwrap()] {
+        let mut reg = qureg.reg;
+        let mut end = 0;
+        for chunk_id in 0..reg.numChunks {
+            reg.chunkId = chunk_id;
+            let range = qureg::chunk_index_range(&reg);
+            assert_eq!(range.start, end);
+            assert!(!range.is_empty());
+            end = range.end;
+        }
+        assert_eq!(end, qureg.num_amps_total());
+    }
+}
+
+#[test]
+fn local_index_range_02() {
+    let env = &QuestEnv::new();
+    let qureg = Qureg::try_new(4, env).unwrap();
+    let mut reg = qureg.reg;
+
+    // Simulate a register distributed over 4 nodes
+    reg.numChunks = 4;
+    reg.numAmpsPerChunk = 4;
+    let ranges = (0..4)
+        .map(|chunk_id| {
+            reg.chunkId = chunk_id;
+            qureg::chunk_index_range(&reg)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(ranges, [0..4, 4..8, 8..12, 12..16]);
+}
+
+#[test]
+fn qureg_kind_01() {
+    let env = &QuestEnv::new();
+    let qureg = Qureg::try_new(2, env).unwrap();
+    assert_eq!(qureg.kind(), QuregKind::StateVector);
+    assert_eq!(qureg.kind().to_string(), "StateVector");
+
+    let qureg = Qureg::try_new_density(2, env).unwrap();
+    assert_eq!(qureg.kind(), QuregKind::DensityMatrix);
+    assert_eq!(qureg.kind().to_string(), "DensityMatrix");
+}
+
+#[test]
+fn raw_qureg_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    let expected = qureg.get_amp(5).unwrap();
+
+    let qureg = qureg.with_applied(|_| Ok(())).unwrap();
+    let reg: RawQureg = qureg.into_raw();
+    assert_eq!(reg.numQubitsRepresented, 3);
+    assert_eq!(reg.isDensityMatrix, 0);
+
+    let qureg = unsafe { Qureg::from_raw(reg, env) };
+    assert_eq!(qureg.num_qubits(), 3);
+    assert_eq!(qureg.get_amp(5).unwrap(), expected);
+}
+
+#[test]
+fn debug_fmt_01() {
+    let env = &QuestEnv::new();
+    let qureg = &Qureg::try_new_density(3, env).unwrap();
+    let debug = format!("{qureg:?}");
+    assert!(debug.starts_with("Qureg { num_qubits: 3, is_density_matrix: true"));
+    assert!(debug.contains("num_amps_total: 64"));
+    assert!(!debug.contains("0x"));
+
+    let op = &DiagonalOp::try_new(2, env).unwrap();
+    let debug = format!("{op:?}");
+    assert!(debug.starts_with("DiagonalOp { num_qubits: 2"));
+    assert!(!debug.contains("0x"));
+
+    let hamil = &PauliHamil::try_new(2, 3).unwrap();
+    let debug = format!("{hamil:?}");
+    assert_eq!(debug, "PauliHamil { num_qubits: 2, num_sum_terms: 3, .. }");
+}
+
 #[test]
 fn get_matrix_n_elem_01() {
     let env = &QuestEnv::new();
@@ -105,6 +730,112 @@ fn init_complex_matrix_n_03() {
     assert_eq!(m.row_imag_as_slice(3), &[223., 224., 225., 226.]);
 }
 
+#[test]
+fn complex_matrix2_from_complex_01() {
+    let m = [
+        [Qcomplex::new(1., 2.), Qcomplex::new(3., 4.)],
+        [Qcomplex::new(5., 6.), Qcomplex::new(7., 8.)],
+    ];
+    let mtr = ComplexMatrix2::from_complex(m);
+    assert_eq!(mtr.0.real, [[1., 3.], [5., 7.]]);
+    assert_eq!(mtr.0.imag, [[2., 4.], [6., 8.]]);
+}
+
+#[test]
+fn complex_matrix4_from_complex_01() {
+    let mut m = [[Qcomplex::zero(); 4]; 4];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, z) in row.iter_mut().enumerate() {
+            *z = Qcomplex::new(Qreal::from(i as u8), Qreal::from(j as u8));
+        }
+    }
+    let mtr = ComplexMatrix4::from_complex(m);
+    for i in 0..4 {
+        assert_eq!(mtr.0.real[i], [Qreal::from(i as u8); 4]);
+        assert_eq!(mtr.0.imag[i], [0., 1., 2., 3.]);
+    }
+}
+
+#[test]
+fn complex_matrix_n_dim_01() {
+    let mtr = ComplexMatrixN::try_new(2).unwrap();
+    assert_eq!(mtr.num_qubits(), 2);
+    assert_eq!(mtr.dim(), 4);
+
+    let mtr = ComplexMatrixN::try_new(1).unwrap();
+    assert_eq!(mtr.num_qubits(), 1);
+    assert_eq!(mtr.dim(), 2);
+}
+
+#[test]
+fn complex_matrix_n_from_rows_01() {
+    let rows = (0..4_u8).map(|i| {
+        (0..4_u8)
+            .map(|j| Qcomplex::new(Qreal::from(i), Qreal::from(j)))
+            .collect::<Vec<_>>()
+    });
+    let mtr = ComplexMatrixN::from_rows(rows).unwrap();
+    assert_eq!(mtr.num_qubits(), 2);
+    for i in 0..4 {
+        assert_eq!(mtr.row_real_as_slice(i), &[Qreal::from(i as u8); 4]);
+        assert_eq!(mtr.row_imag_as_slice(i), &[0., 1., 2., 3.]);
+    }
+}
+
+#[test]
+fn complex_matrix_n_from_rows_02() {
+    let zero = Qcomplex::zero();
+
+    // empty
+    assert_eq!(
+        ComplexMatrixN::from_rows([]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    // zero qubits
+    assert_eq!(
+        ComplexMatrixN::from_rows([vec![zero]]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    // ragged
+    assert_eq!(
+        ComplexMatrixN::from_rows([vec![zero; 2], vec![zero; 3]]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    // not square
+    assert_eq!(
+        ComplexMatrixN::from_rows([vec![zero; 4], vec![zero; 4]]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    // not a power of two
+    assert_eq!(
+        ComplexMatrixN::from_rows(vec![vec![zero; 3]; 3]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+}
+
+#[test]
+fn stack_complex_matrix_n_01() {
+    let re = [[1., 2.], [3., 4.]];
+    let im = [[5., 6.], [7., 8.]];
+    let stack = StackComplexMatrixN::new(&re, &im).unwrap();
+    assert_eq!(stack.num_qubits(), 1);
+
+    let mtr = stack.as_matrix();
+    assert_eq!(mtr.num_qubits(), 1);
+    assert_eq!(mtr.dim(), 2);
+    assert_eq!(mtr.row_real_as_slice(1), &[3., 4.]);
+    assert_eq!(mtr.row_imag_as_slice(0), &[5., 6.]);
+
+    assert_eq!(
+        StackComplexMatrixN::new(&[[0.]], &[[0.]]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+    assert_eq!(
+        StackComplexMatrixN::new(&[[0.; 3]; 3], &[[0.; 3]; 3]).unwrap_err(),
+        QuestError::ArrayLengthError
+    );
+}
+
 #[test]
 fn complex_matrix_n_row_slice_02() {
     let mtr = &mut ComplexMatrixN::try_new(2).unwrap();
@@ -187,6 +918,101 @@ fn apply_diagonal_op_01() {
     apply_diagonal_op(&mut qureg, &op).unwrap_err();
 }
 
+#[test]
+fn apply_diagonal_op_02() {
+    let env = &QuestEnv::new();
+    let mut qureg = Qureg::try_new_density(1, env).unwrap();
+    let mut op = DiagonalOp::try_new(1, env).unwrap();
+
+    // D = diag(1, i)
+    qureg.init_plus_state();
+    init_diagonal_op(&mut op, &[1., 0.], &[0., 1.]).unwrap();
+    apply_diagonal_op(&mut qureg, &op).unwrap();
+
+    // rho -> D rho: row r is multiplied by d_r
+    let expected = [
+        [Qcomplex::new(0.5, 0.), Qcomplex::new(0.5, 0.)],
+        [Qcomplex::new(0., 0.5), Qcomplex::new(0., 0.5)],
+    ];
+    for (row, elems) in expected.iter().enumerate() {
+        for (col, elem) in elems.iter().enumerate() {
+            let amp = qureg.get_density_amp(row as i64, col as i64).unwrap();
+            assert!((amp - elem).norm() < EPSILON);
+        }
+    }
+
+    // rho -> D rho D^dagger: off-diagonals pick up the relative phase
+    qureg.init_plus_state();
+    let mtr = &mut ComplexMatrixN::try_new(1).unwrap();
+    init_complex_matrix_n(
+        mtr,
+        &[&[1., 0.], &[0., 0.]],
+        &[&[0., 0.], &[0., 1.]],
+    )
+    .unwrap();
+    qureg.multi_qubit_unitary(&[0], mtr).unwrap();
+
+    let expected = [
+        [Qcomplex::new(0.5, 0.), Qcomplex::new(0., -0.5)],
+        [Qcomplex::new(0., 0.5), Qcomplex::new(0.5, 0.)],
+    ];
+    for (row, elems) in expected.iter().enumerate() {
+        for (col, elem) in elems.iter().enumerate() {
+            let amp = qureg.get_density_amp(row as i64, col as i64).unwrap();
+            assert!((amp - elem).norm() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn apply_diagonal_op_subset_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+
+    // diag(d_0, d_1, d_2, d_3) on qubits [2, 0]: qubit 2 is the low bit
+    let re = [1., 2., 3., 4.];
+    let im = [0.5, -1., 0., 2.];
+    let op = &mut DiagonalOp::try_new(2, env).unwrap();
+    init_diagonal_op(op, &re, &im).unwrap();
+
+    let full_re = &mut [0.; 8];
+    let full_im = &mut [0.; 8];
+    for i in 0..8 {
+        let k = ((i >> 2) & 1) | ((i & 1) << 1);
+        full_re[i] = re[k];
+        full_im[i] = im[k];
+    }
+    let full = &mut DiagonalOp::try_new(3, env).unwrap();
+    init_diagonal_op(full, full_re, full_im).unwrap();
+
+    qureg.init_debug_state();
+    expected.init_debug_state();
+    qureg.apply_diagonal_op_subset(&[2, 0], op).unwrap();
+    apply_diagonal_op(expected, full).unwrap();
+
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let exp = expected.get_amp(i).unwrap();
+        assert!((amp - exp).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn apply_diagonal_op_subset_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let op = &mut DiagonalOp::try_new(2, env).unwrap();
+    init_diagonal_op(op, &[1., 1., 1., 1.], &[0., 0., 0., 0.]).unwrap();
+
+    qureg.apply_diagonal_op_subset(&[0, 1], op).unwrap();
+    qureg.apply_diagonal_op_subset(&[0], op).unwrap_err();
+    qureg.apply_diagonal_op_subset(&[0, 1, 2], op).unwrap_err();
+    qureg.apply_diagonal_op_subset(&[1, 1], op).unwrap_err();
+    qureg.apply_diagonal_op_subset(&[0, 3], op).unwrap_err();
+    qureg.apply_diagonal_op_subset(&[-1, 0], op).unwrap_err();
+}
+
 #[test]
 fn calc_expec_diagonal_op_() {
     let env = &QuestEnv::new();
@@ -229,6 +1055,55 @@ fn initialize_pauli_hamil_01() {
     .unwrap();
 }
 
+#[test]
+fn pauli_hamil_builder_01() {
+    let hamil = PauliHamilBuilder::new(3)
+        .add_term(0.5, "XZI")
+        .add_term(-1.5, "iyZ")
+        .build()
+        .unwrap();
+
+    assert_eq!(hamil.0.numQubits, 3);
+    assert_eq!(hamil.0.numSumTerms, 2);
+    let coeffs = unsafe { std::slice::from_raw_parts(hamil.0.termCoeffs, 2) };
+    assert_eq!(coeffs, &[0.5, -1.5]);
+    let codes = unsafe { std::slice::from_raw_parts(hamil.0.pauliCodes, 6) };
+    assert_eq!(
+        codes.iter().map(|&c| c as i32).collect::<Vec<_>>(),
+        [1, 3, 0, 0, 2, 3]
+    );
+}
+
+#[test]
+fn pauli_hamil_builder_02() {
+    let err_msg = |builder: PauliHamilBuilder| match builder.build() {
+        Err(QuestError::InvalidQuESTInputError {
+            err_msg, ..
+        }) => err_msg,
+        _ => panic!("expected InvalidQuESTInputError"),
+    };
+
+    assert_eq!(
+        err_msg(PauliHamilBuilder::new(3).add_term(0.5, "XZ")),
+        "Invalid Pauli string \"XZ\": expected 3 operators, found 2."
+    );
+    assert_eq!(
+        err_msg(
+            PauliHamilBuilder::new(2)
+                .add_term(0.5, "XA")
+                .add_term(0.5, "XZI")
+        ),
+        "Invalid Pauli operator 'A' in \"XA\". Must be one of: I, X, Y, Z."
+    );
+
+    // no terms
+    PauliHamilBuilder::new(2).build().unwrap_err();
+    PauliHamilBuilder::new(0)
+        .add_term(0.5, "")
+        .build()
+        .unwrap_err();
+}
+
 #[test]
 fn set_amps_01() {
     let env = &QuestEnv::new();
@@ -303,6 +1178,84 @@ fn set_density_amps_02() {
     assert_eq!(res, QuestError::ArrayLengthError);
 }
 
+#[test]
+fn conjugate_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    expected.init_debug_state();
+
+    qureg.conjugate();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        assert_eq!(amp, expected.get_amp(i).unwrap().conj());
+    }
+
+    qureg.conjugate();
+    for i in 0..8 {
+        assert_eq!(qureg.get_amp(i).unwrap(), expected.get_amp(i).unwrap());
+    }
+}
+
+#[test]
+fn conjugate_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    let expected = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_debug_state();
+    expected.init_debug_state();
+
+    rho.conjugate();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let exp = expected.get_density_amp(row, col).unwrap();
+            assert_eq!(amp, exp.conj());
+        }
+    }
+}
+
+#[test]
+fn transpose_density_01() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    let expected = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_debug_state();
+    expected.init_debug_state();
+
+    rho.transpose_density().unwrap();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let exp = expected.get_density_amp(col, row).unwrap();
+            assert_eq!(amp, exp);
+        }
+    }
+
+    // a valid density matrix is Hermitian
+    rho.init_plus_state();
+    rho.rotate_y(0, 0.3).unwrap();
+    rho.phase_shift(1, 0.7).unwrap();
+    expected.clone_qureg(rho).unwrap();
+    rho.conjugate();
+    rho.transpose_density().unwrap();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let exp = expected.get_density_amp(row, col).unwrap();
+            assert!((amp - exp).norm() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn transpose_density_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.transpose_density().unwrap_err();
+}
+
 #[test]
 fn phase_shift_01() {
     let env = &QuestEnv::new();
@@ -348,58 +1301,294 @@ fn multi_controlled_phase_shift_01() {
 }
 
 #[test]
-fn controlled_phase_flip_01() {
+fn multi_controlled_global_phase_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+    other.init_plus_state();
+
+    qureg.multi_controlled_global_phase(&[0, 2], 0.3).unwrap();
+    other.multi_controlled_phase_shift(&[0, 2], 0.3).unwrap();
+
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    let amp = qureg.get_amp(0b101).unwrap();
+    let expected = Qcomplex::from_polar(1. / (8. as Qreal).sqrt(), 0.3);
+    assert!((amp - expected).norm() < EPSILON);
+
+    qureg
+        .multi_controlled_global_phase(&[0, 0], 0.3)
+        .unwrap_err();
+    qureg.multi_controlled_global_phase(&[3], 0.3).unwrap_err();
+    qureg.multi_controlled_global_phase(&[], 0.3).unwrap_err();
+}
+
+#[test]
+fn multi_state_controlled_phase_shift_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg
+        .multi_state_controlled_phase_shift(&[2, 0], &[1, 0], 0.3)
+        .unwrap();
+
+    let norm = 1. / (8. as Qreal).sqrt();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = if i & 0b101 == 0b100 {
+            Qcomplex::from_polar(norm, 0.3)
+        } else {
+            Qcomplex::new(norm, 0.)
+        };
+        assert!((amp - expected).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn multi_state_controlled_phase_shift_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    let amps = (0..8)
+        .map(|i| qureg.get_amp(i).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        qureg.multi_state_controlled_phase_shift(&[0, 1], &[0], 0.3),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg
+        .multi_state_controlled_phase_shift(&[], &[], 0.3)
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_phase_shift(&[0, 3], &[0, 0], 0.3)
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_phase_shift(&[0, 0], &[0, 0], 0.3)
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_phase_shift(&[0, 1], &[0, 2], 0.3)
+        .unwrap_err();
+
+    // The state is unchanged after invalid input
+    for (i, amp) in (0..8).zip(amps) {
+        assert_eq!(qureg.get_amp(i).unwrap(), amp);
+    }
+}
+
+#[test]
+fn phase_on_basis_state_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+    other.init_debug_state();
+
+    qureg.phase_on_basis_state(5, PI / 2.).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let mut expected = other.get_amp(i).unwrap();
+        if i == 5 {
+            expected *= Qcomplex::new(0., 1.);
+        }
+        assert!((amp - expected).norm() < 10. * EPSILON);
+    }
+
+    qureg.phase_on_basis_state(-1, PI).unwrap_err();
+    qureg.phase_on_basis_state(8, PI).unwrap_err();
+}
+
+#[test]
+fn phase_on_basis_state_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_plus_state();
+
+    rho.phase_on_basis_state(1, PI / 2.).unwrap();
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let expected = match (row == 1, col == 1) {
+                (true, false) => Qcomplex::new(0., 0.25),
+                (false, true) => Qcomplex::new(0., -0.25),
+                _ => Qcomplex::new(0.25, 0.),
+            };
+            assert!((amp - expected).norm() < 10. * EPSILON);
+        }
+    }
+
+    // indices are basis states, not elements of the density matrix
+    rho.phase_on_basis_state(4, PI).unwrap_err();
+}
+
+#[test]
+fn controlled_phase_flip_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    qureg.controlled_phase_flip(0, 1).unwrap();
+    qureg.controlled_phase_flip(0, 2).unwrap();
+
+    qureg.controlled_phase_flip(0, 3).unwrap_err();
+    qureg.controlled_phase_flip(-1, 1).unwrap_err();
+}
+
+#[test]
+fn multi_controlled_phase_flip_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.multi_controlled_phase_flip(&[0, 1, 3]).unwrap();
+    qureg.multi_controlled_phase_flip(&[0, 1, 3]).unwrap();
+
+    qureg
+        .multi_controlled_phase_flip(&[0, 4, 3, 4])
+        .unwrap_err();
+    qureg.multi_controlled_phase_flip(&[0, 7, -1]).unwrap_err();
+}
+
+#[test]
+fn s_gate_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg.s_gate(0).unwrap();
+    assert!((qureg.get_imag_amp(0).unwrap()).abs() < EPSILON);
+
+    qureg.pauli_x(0).unwrap();
+    qureg.s_gate(0).unwrap();
+
+    let amp = qureg.get_imag_amp(1).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg.s_gate(-1).unwrap_err();
+    qureg.s_gate(3).unwrap_err();
+}
+
+#[test]
+fn t_gate_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg.t_gate(0).unwrap();
+    qureg.t_gate(-1).unwrap_err();
+    qureg.t_gate(3).unwrap_err();
+}
+
+#[test]
+fn sdg_gate_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+    qureg.t_gate(1).unwrap();
+
+    let amps = (0..4)
+        .map(|i| qureg.get_amp(i).unwrap())
+        .collect::<Vec<_>>();
+    qureg.s_gate(0).unwrap();
+    qureg.sdg_gate(0).unwrap();
+    for (i, amp) in amps.iter().enumerate() {
+        let new_amp = qureg.get_amp(i as i64).unwrap();
+        assert!((new_amp - amp).norm() < EPSILON);
+    }
+
+    qureg.sdg_gate(-1).unwrap_err();
+    qureg.sdg_gate(2).unwrap_err();
+}
+
+#[test]
+fn tdg_gate_01() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+    qureg.s_gate(1).unwrap();
 
-    qureg.controlled_phase_flip(0, 1).unwrap();
-    qureg.controlled_phase_flip(0, 2).unwrap();
+    let amps = (0..4)
+        .map(|i| qureg.get_amp(i).unwrap())
+        .collect::<Vec<_>>();
+    qureg.t_gate(0).unwrap();
+    qureg.tdg_gate(0).unwrap();
+    for (i, amp) in amps.iter().enumerate() {
+        let new_amp = qureg.get_amp(i as i64).unwrap();
+        assert!((new_amp - amp).norm() < EPSILON);
+    }
 
-    qureg.controlled_phase_flip(0, 3).unwrap_err();
-    qureg.controlled_phase_flip(-1, 1).unwrap_err();
+    qureg.tdg_gate(-1).unwrap_err();
+    qureg.tdg_gate(2).unwrap_err();
 }
 
 #[test]
-fn multi_controlled_phase_flip_01() {
+fn controlled_s_gate_01() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(4, env).unwrap();
-    qureg.multi_controlled_phase_flip(&[0, 1, 3]).unwrap();
-    qureg.multi_controlled_phase_flip(&[0, 1, 3]).unwrap();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
 
-    qureg
-        .multi_controlled_phase_flip(&[0, 4, 3, 4])
-        .unwrap_err();
-    qureg.multi_controlled_phase_flip(&[0, 7, -1]).unwrap_err();
+    // control qubit in state |0>
+    qureg.init_classical_state(1).unwrap();
+    qureg.controlled_s_gate(1, 0).unwrap();
+    assert!((qureg.get_real_amp(1).unwrap() - 1.).abs() < EPSILON);
+    assert!(qureg.get_imag_amp(1).unwrap().abs() < EPSILON);
+
+    // control qubit in state |1>
+    qureg.init_classical_state(3).unwrap();
+    qureg.controlled_s_gate(1, 0).unwrap();
+    let amp = qureg.get_amp(3).unwrap();
+
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    other.init_classical_state(3).unwrap();
+    other.s_gate(0).unwrap();
+    let expected = other.get_amp(3).unwrap();
+    assert!((amp - expected).norm() < EPSILON);
+    assert!((amp.im - 1.).abs() < EPSILON);
+
+    qureg.controlled_s_gate(0, 0).unwrap_err();
+    qureg.controlled_s_gate(-1, 0).unwrap_err();
+    qureg.controlled_s_gate(0, 2).unwrap_err();
 }
 
 #[test]
-fn s_gate_01() {
+fn controlled_t_gate_01() {
     let env = &QuestEnv::new();
     let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
 
-    qureg.s_gate(0).unwrap();
-    assert!((qureg.get_imag_amp(0).unwrap()).abs() < EPSILON);
+    // control qubit in state |0>
+    qureg.init_classical_state(2).unwrap();
+    qureg.controlled_t_gate(0, 1).unwrap();
+    assert!((qureg.get_real_amp(2).unwrap() - 1.).abs() < EPSILON);
+    assert!(qureg.get_imag_amp(2).unwrap().abs() < EPSILON);
 
-    qureg.pauli_x(0).unwrap();
-    qureg.s_gate(0).unwrap();
+    // control qubit in state |1>
+    qureg.init_classical_state(3).unwrap();
+    qureg.controlled_t_gate(0, 1).unwrap();
+    let amp = qureg.get_amp(3).unwrap();
 
-    let amp = qureg.get_imag_amp(1).unwrap();
-    assert!((amp - 1.).abs() < EPSILON);
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    other.init_classical_state(3).unwrap();
+    other.t_gate(1).unwrap();
+    let expected = other.get_amp(3).unwrap();
+    assert!((amp - expected).norm() < EPSILON);
+    assert!((amp.im - SQRT_2 / 2.).abs() < EPSILON);
 
-    qureg.s_gate(-1).unwrap_err();
-    qureg.s_gate(3).unwrap_err();
+    qureg.controlled_t_gate(1, 1).unwrap_err();
+    qureg.controlled_t_gate(-1, 0).unwrap_err();
+    qureg.controlled_t_gate(0, 2).unwrap_err();
 }
 
 #[test]
-fn t_gate_01() {
+fn is_gpu_accelerated_01() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
+    let qureg = &Qureg::try_new(2, env).unwrap();
 
-    qureg.t_gate(0).unwrap();
-    qureg.t_gate(-1).unwrap_err();
-    qureg.t_gate(3).unwrap_err();
+    assert_eq!(
+        qureg.is_gpu_accelerated(),
+        cfg!(feature = "gpu") && !cfg!(feature = "mpi")
+    );
 }
 
 #[test]
@@ -447,6 +1636,106 @@ fn get_imag_amp_01() {
     qureg.get_imag_amp(-1).unwrap_err();
 }
 
+#[test]
+fn get_real_imag_amps_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg
+        .init_state_from_amps(&[0.1, 0.2, 0.3, 0.4], &[0.5, 0.6, 0.7, 0.8])
+        .unwrap();
+
+    let reals = &mut [0.; 4];
+    let imags = &mut [0.; 4];
+    qureg.get_real_amps(0, reals).unwrap();
+    qureg.get_imag_amps(0, imags).unwrap();
+    assert_eq!(reals, &[0.1, 0.2, 0.3, 0.4]);
+    assert_eq!(imags, &[0.5, 0.6, 0.7, 0.8]);
+
+    let reals = &mut [0.; 2];
+    let imags = &mut [0.; 2];
+    qureg.get_real_amps(2, reals).unwrap();
+    qureg.get_imag_amps(1, imags).unwrap();
+    assert_eq!(reals, &[0.3, 0.4]);
+    assert_eq!(imags, &[0.6, 0.7]);
+
+    qureg.get_real_amps(4, &mut []).unwrap();
+    qureg.get_imag_amps(4, &mut []).unwrap();
+
+    assert_eq!(
+        qureg.get_real_amps(3, reals),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.get_imag_amps(-1, imags),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.get_real_amps(i64::MAX, reals),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
+#[test]
+fn get_real_imag_amps_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.get_real_amps(0, &mut [0.; 2]).unwrap_err();
+    qureg.get_imag_amps(0, &mut []).unwrap_err();
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn write_amps_chunked_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+
+    let mut expected = Vec::new();
+    for index in 0..8 {
+        let amp = qureg.get_amp(index).unwrap();
+        expected.extend_from_slice(&f64::from(amp.re).to_le_bytes());
+        expected.extend_from_slice(&f64::from(amp.im).to_le_bytes());
+    }
+
+    for chunk in [1, 3, 8, 100, usize::MAX] {
+        let mut bytes = Vec::new();
+        qureg.write_amps_chunked(&mut bytes, chunk).unwrap();
+        assert_eq!(bytes, expected);
+    }
+
+    assert_eq!(
+        qureg.write_amps_chunked(&mut Vec::new(), 0),
+        Err(QuestError::ArrayLengthError)
+    );
+    let buf = &mut [0_u8; 40];
+    assert_eq!(
+        qureg.write_amps_chunked(&mut &mut buf[..], 2),
+        Err(QuestError::IoError(std::io::ErrorKind::WriteZero))
+    );
+}
+
+#[test]
+#[allow(clippy::useless_conversion)]
+fn write_amps_chunked_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_debug_state();
+
+    let mut bytes = Vec::new();
+    rho.write_amps_chunked(&mut bytes, 5).unwrap();
+    assert_eq!(bytes.len(), 16 * 16);
+
+    for (index, pair) in (0..).zip(bytes.chunks_exact(16)) {
+        let amp = rho.get_flat_amp(index).unwrap();
+        let re = f64::from_le_bytes(pair[..8].try_into().unwrap());
+        let im = f64::from_le_bytes(pair[8..].try_into().unwrap());
+        assert_eq!(re, f64::from(amp.re));
+        assert_eq!(im, f64::from(amp.im));
+    }
+}
+
 #[test]
 fn get_prob_amp_01() {
     let env = &QuestEnv::new();
@@ -488,6 +1777,358 @@ fn get_density_amp_02() {
     qureg.get_density_amp(4, 0).unwrap_err();
 }
 
+#[test]
+fn get_density_row_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_debug_state();
+
+    let row = &mut [Qcomplex::zero(); 4];
+    for r in 0..4 {
+        qureg.get_density_row(r, row).unwrap();
+        for (c, amp) in (0..4).zip(row.iter()) {
+            let expected = qureg.get_density_amp(r, c).unwrap();
+            assert_eq!(*amp, expected);
+        }
+    }
+
+    qureg.get_density_row(-1, row).unwrap_err();
+    qureg.get_density_row(4, row).unwrap_err();
+    assert_eq!(
+        qureg.get_density_row(0, &mut [Qcomplex::zero(); 3]),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.get_density_row(0, &mut [Qcomplex::zero(); 5]),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
+#[test]
+fn get_density_row_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    qureg
+        .get_density_row(0, &mut [Qcomplex::zero(); 4])
+        .unwrap_err();
+}
+
+#[test]
+fn get_flat_amp_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_classical_state(2).unwrap();
+
+    let amp = qureg.get_flat_amp(2).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+    let amp = qureg.get_flat_amp(0).unwrap();
+    assert!(amp.norm() < EPSILON);
+
+    qureg.get_flat_amp(4).unwrap_err();
+    qureg.get_flat_amp(-1).unwrap_err();
+}
+
+#[test]
+fn get_flat_amp_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_debug_state();
+
+    for index in 0..16 {
+        let amp = qureg.get_flat_amp(index).unwrap();
+        let expected = qureg.get_density_amp(index % 4, index / 4).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    qureg.get_flat_amp(16).unwrap_err();
+    qureg.get_flat_amp(-1).unwrap_err();
+}
+
+#[test]
+fn basis_state_amplitude_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_debug_state();
+
+    for index in 0..4 {
+        let amp = qureg.basis_state_amplitude(index).unwrap();
+        assert_eq!(amp, qureg.get_amp(index).unwrap());
+    }
+
+    qureg.basis_state_amplitude(4).unwrap_err();
+    qureg.basis_state_amplitude(-1).unwrap_err();
+}
+
+#[test]
+fn basis_state_amplitude_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_debug_state();
+
+    for index in 0..4 {
+        let amp = rho.basis_state_amplitude(index).unwrap();
+        assert_eq!(amp, rho.get_density_amp(index, index).unwrap());
+    }
+
+    rho.init_classical_state(2).unwrap();
+    let amp = rho.basis_state_amplitude(2).unwrap();
+    assert!((amp - 1.).norm() < EPSILON);
+
+    // indices are bound by 2^N, and not by num_amps_total()
+    rho.basis_state_amplitude(4).unwrap_err();
+    rho.basis_state_amplitude(-1).unwrap_err();
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_util_01() {
+    use crate::test_util::{
+        assert_amp_close,
+        assert_state_close,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_debug_state();
+    other.init_debug_state();
+
+    assert_amp_close(qureg, 1, Qcomplex::new(0.2, 0.3), EPSILON);
+    assert_state_close(qureg, other, 0.);
+
+    let rho = &mut Qureg::try_new_density(1, env).unwrap();
+    rho.init_plus_state();
+    assert_amp_close(rho, 2, 0.5, EPSILON);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic(expected = "amplitude at index 2 is not close")]
+fn test_util_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let other = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+    other.init_plus_state();
+    other.phase_on_basis_state(2, PI).unwrap();
+
+    crate::test_util::assert_state_close(qureg, other, 0.1);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic(expected = "registers are of different kinds")]
+fn test_util_03() {
+    let env = &QuestEnv::new();
+    let qureg = &Qureg::try_new(1, env).unwrap();
+    let rho = &Qureg::try_new_density(1, env).unwrap();
+
+    crate::test_util::assert_state_close(qureg, rho, 0.1);
+}
+
+#[test]
+fn labeled_amps_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(6).unwrap();
+
+    let amps = qureg.labeled_amps().unwrap().collect::<Vec<_>>();
+    assert_eq!(amps.len(), 8);
+    for (i, (index, label, amp)) in amps.iter().enumerate() {
+        assert_eq!(*index, i as i64);
+        assert_eq!(label, &format!("|{i:03b}>"));
+        let expected = if i == 6 { 1. } else { 0. };
+        assert!((amp.re - expected).abs() < EPSILON);
+    }
+    assert_eq!(amps[6].1, "|110>");
+}
+
+#[test]
+fn labeled_amps_above_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(2).unwrap();
+    qureg.hadamard(2).unwrap();
+
+    let amps = qureg
+        .labeled_amps_above(EPSILON)
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(amps.len(), 2);
+    assert_eq!((amps[0].0, amps[0].1.as_str()), (2, "|010>"));
+    assert_eq!((amps[1].0, amps[1].1.as_str()), (6, "|110>"));
+
+    assert_eq!(qureg.labeled_amps_above(1.).unwrap().count(), 0);
+}
+
+#[test]
+fn labeled_amps_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_zero_state();
+
+    let err = rho.labeled_amps().err().unwrap();
+    assert!(matches!(
+        err,
+        QuestError::InvalidQuESTInputError { err_func, .. }
+            if err_func == "labeled_amps"
+    ));
+    rho.labeled_amps_above(EPSILON).err().unwrap();
+}
+
+#[test]
+fn diagonal_probabilities_01() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(3, env).unwrap();
+
+    for index in 0..8 {
+        rho.init_classical_state(index).unwrap();
+        let probs = rho.diagonal_probabilities().unwrap();
+        assert_eq!(probs.len(), 8);
+        for (i, prob) in (0..).zip(probs) {
+            let expected = if i == index { 1. } else { 0. };
+            assert!((prob - expected).abs() < EPSILON);
+        }
+    }
+
+    // only the real parts of the diagonal are returned
+    rho.init_debug_state();
+    let probs = rho.diagonal_probabilities().unwrap();
+    for (i, prob) in (0..).zip(probs) {
+        let amp = rho.get_density_amp(i, i).unwrap();
+        assert!((prob - amp.re).abs() < EPSILON);
+    }
+
+    let qureg = &Qureg::try_new(3, env).unwrap();
+    qureg.diagonal_probabilities().unwrap_err();
+}
+
+#[test]
+fn is_valid_density_matrix_01() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+
+    rho.init_plus_state();
+    rho.rotate_x(0, 0.4).unwrap();
+    rho.controlled_phase_shift(0, 1, 1.1).unwrap();
+    rho.mix_dephasing(1, 0.2).unwrap();
+    assert!(rho.is_valid_density_matrix(EPSILON).unwrap());
+
+    rho.init_classical_state(3).unwrap();
+    assert!(rho.is_valid_density_matrix(EPSILON).unwrap());
+
+    let qureg = &Qureg::try_new(2, env).unwrap();
+    qureg.is_valid_density_matrix(EPSILON).unwrap_err();
+}
+
+#[test]
+fn is_valid_density_matrix_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(1, env).unwrap();
+
+    // trace 1, but not Hermitian
+    rho.set_density_amps(0, 0, &[0.5, 0.5, 0.5, 0.5], &[0., 0.1, 0.1, 0.])
+        .unwrap();
+    assert!(!rho.is_valid_density_matrix(EPSILON).unwrap());
+
+    // Hermitian, but trace 2
+    rho.set_density_amps(0, 0, &[1., 0.5, 0.5, 1.], &[0., 0.1, -0.1, 0.])
+        .unwrap();
+    assert!(!rho.is_valid_density_matrix(EPSILON).unwrap());
+
+    // complex diagonal
+    rho.set_density_amps(0, 0, &[0.5, 0., 0., 0.5], &[0.1, 0., 0., -0.1])
+        .unwrap();
+    assert!(!rho.is_valid_density_matrix(EPSILON).unwrap());
+
+    rho.set_density_amps(0, 0, &[0.5, 0.5, 0.5, 0.5], &[0., 0.1, -0.1, 0.])
+        .unwrap();
+    assert!(rho.is_valid_density_matrix(EPSILON).unwrap());
+}
+
+#[test]
+fn trace_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let trace = qureg.trace().unwrap();
+    assert!((trace.re - 1.).abs() < EPSILON);
+    assert!(trace.im.abs() < EPSILON);
+
+    qureg.set_density_amps(0, 0, &[2.], &[3.]).unwrap();
+    let trace = qureg.trace().unwrap();
+    assert!((trace.re - 2.75).abs() < EPSILON);
+    assert!((trace.im - 3.).abs() < EPSILON);
+}
+
+#[test]
+fn trace_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.trace().unwrap_err();
+}
+
+#[test]
+fn partial_trace_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(3, env).unwrap();
+    // Prepare |1> on qubit 0 and a Bell pair on qubits 1 and 2
+    qureg.init_classical_state(1).unwrap();
+    qureg.hadamard(1).unwrap();
+    qureg.controlled_not(1, 2).unwrap();
+
+    let reduced = qureg.partial_trace(&[0]).unwrap();
+    assert_eq!(reduced.num_qubits(), 1);
+    assert!(reduced.is_density_matrix());
+    let amp = reduced.get_density_amp(1, 1).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+
+    let reduced = qureg.partial_trace(&[2, 1]).unwrap();
+    assert_eq!(reduced.num_qubits(), 2);
+    for (row, col) in [(0, 0), (3, 3), (0, 3), (3, 0)] {
+        let amp = reduced.get_density_amp(row, col).unwrap();
+        assert!((amp.re - 0.5).abs() < EPSILON);
+    }
+    let amp = reduced.get_density_amp(1, 1).unwrap();
+    assert!(amp.norm() < EPSILON);
+
+    let reduced = qureg.partial_trace(&[1]).unwrap();
+    for (row, col) in [(0, 0), (1, 1)] {
+        let amp = reduced.get_density_amp(row, col).unwrap();
+        assert!((amp.re - 0.5).abs() < EPSILON);
+    }
+    let amp = reduced.get_density_amp(0, 1).unwrap();
+    assert!(amp.norm() < EPSILON);
+}
+
+#[test]
+fn partial_trace_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(3, env).unwrap();
+    qureg.init_plus_state();
+
+    qureg.partial_trace(&[0, 1, 2]).unwrap();
+
+    qureg.partial_trace(&[]).unwrap_err();
+    qureg.partial_trace(&[-1]).unwrap_err();
+    qureg.partial_trace(&[3]).unwrap_err();
+    qureg.partial_trace(&[0, 0]).unwrap_err();
+
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let err = qureg.partial_trace(&[0]).unwrap_err();
+    assert_eq!(
+        err,
+        QuestError::InvalidQuESTInputError {
+            err_msg:  "Expected DensityMatrix, found StateVector.".to_owned(),
+            err_func: "partial_trace".to_owned(),
+        }
+    );
+}
+
 #[test]
 fn compact_unitary_01() {
     let env = &QuestEnv::new();
@@ -541,17 +2182,112 @@ fn unitary_01() {
 }
 
 #[test]
-fn unitary_02() {
+fn unitary_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    // This isn't a unitary
+    let mtr = ComplexMatrix2::new([[1., 2.], [0., 0.]], [[0., 0.], [1., 2.]]);
+    qureg.unitary(0, &mtr).unwrap_err();
+    qureg.unitary(1, &mtr).unwrap_err();
+    qureg.unitary(2, &mtr).unwrap_err();
+    qureg.unitary(-1, &mtr).unwrap_err();
+}
+
+#[test]
+fn u3_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+
+    // Hadamard gate
+    qureg.init_debug_state();
+    other.init_debug_state();
+    qureg.u3(1, PI / 2., 0., PI).unwrap();
+    other.hadamard(1).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    // Pauli X gate
+    qureg.init_debug_state();
+    other.init_debug_state();
+    qureg.u3(2, PI, 0., PI).unwrap();
+    other.pauli_x(2).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    qureg.u3(3, 0., 0., 0.).unwrap_err();
+    qureg.u3(-1, 0., 0., 0.).unwrap_err();
+}
+
+#[test]
+fn u3_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    let other = &mut Qureg::try_new(1, env).unwrap();
+
+    // U(theta, -PI/2, PI/2) is the rotation around the X-axis
+    let theta = 0.3;
+    qureg.init_zero_state();
+    other.init_zero_state();
+    qureg.u3(0, theta, -PI / 2., PI / 2.).unwrap();
+    other.rotate_x(0, theta).unwrap();
+    for i in 0..2 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn apply_parametric_01() {
     let env = &QuestEnv::new();
     let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
+    let other = &mut Qureg::try_new(2, env).unwrap();
+
+    let rz = ParametricGate::new(|theta| {
+        let zero = Qcomplex::zero();
+        ComplexMatrix2::from_complex([
+            [Qcomplex::from_polar(1., -theta / 2.), zero],
+            [zero, Qcomplex::from_polar(1., theta / 2.)],
+        ])
+    });
+
+    for step in -8_i8..=8 {
+        let theta = Qreal::from(step) * PI / 8.;
+        qureg.init_debug_state();
+        other.init_debug_state();
+        qureg.apply_parametric(1, &rz, theta).unwrap();
+        other.rotate_z(1, theta).unwrap();
+        for i in 0..4 {
+            let amp = qureg.get_amp(i).unwrap();
+            let expected = other.get_amp(i).unwrap();
+            assert!((amp - expected).norm() < EPSILON);
+        }
+    }
 
-    // This isn't a unitary
-    let mtr = ComplexMatrix2::new([[1., 2.], [0., 0.]], [[0., 0.], [1., 2.]]);
-    qureg.unitary(0, &mtr).unwrap_err();
-    qureg.unitary(1, &mtr).unwrap_err();
-    qureg.unitary(2, &mtr).unwrap_err();
-    qureg.unitary(-1, &mtr).unwrap_err();
+    qureg.apply_parametric(2, &rz, 0.).unwrap_err();
+    qureg.apply_parametric(-1, &rz, 0.).unwrap_err();
+}
+
+#[test]
+fn apply_parametric_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+
+    // not unitary for param != 1
+    let scale = ParametricGate::new(|x| {
+        ComplexMatrix2::new([[x, 0.], [0., x]], [[0., 0.], [0., 0.]])
+    });
+    qureg.apply_parametric(0, &scale, 1.).unwrap();
+    qureg.apply_parametric(0, &scale, 2.).unwrap_err();
 }
 
 #[test]
@@ -567,6 +2303,34 @@ fn rotate_x_01() {
     qureg.rotate_x(-1, theta).unwrap_err();
 }
 
+#[test]
+fn rotate_x_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg.rotate_x(0, Degrees(90.)).unwrap();
+    qureg.rotate_x(1, Radians(PI / 2.)).unwrap();
+    let prob0 = qureg.calc_prob_of_outcome(0, 1).unwrap();
+    let prob1 = qureg.calc_prob_of_outcome(1, 1).unwrap();
+    assert!((prob0 - 0.5).abs() < EPSILON);
+    assert!((prob1 - 0.5).abs() < EPSILON);
+
+    qureg.rotate_x(0, Degrees(-90.)).unwrap();
+    let prob0 = qureg.calc_prob_of_outcome(0, 1).unwrap();
+    assert!(prob0.abs() < EPSILON);
+
+    qureg.rotate_x(2, Degrees(90.)).unwrap_err();
+}
+
+#[test]
+fn radians_degrees_01() {
+    assert_eq!(Radians::from(1.), Radians(1.));
+    assert!((Radians::from(Degrees(90.)).0 - PI / 2.).abs() < EPSILON);
+    assert!((Degrees::from(Radians(PI)).0 - 180.).abs() < EPSILON);
+    assert!((Qreal::from(Radians(PI)) - PI).abs() < EPSILON);
+}
+
 #[test]
 fn rotate_y_01() {
     let env = &QuestEnv::new();
@@ -593,6 +2357,43 @@ fn rotate_z_01() {
     qureg.rotate_z(-1, theta).unwrap_err();
 }
 
+#[test]
+fn vector_01() {
+    const X: Vector = Vector::new(1., 0., 0.);
+    const Y: Vector = Vector::new(0., 1., 0.);
+    const Z: Vector = X.cross(&Y);
+    const XY: Qreal = X.dot(&Y);
+
+    assert_eq!(Z, Vector::new(0., 0., 1.));
+    assert_eq!(Y.cross(&X), -Z);
+    assert_eq!(Y.cross(&Z), X);
+    assert_eq!(XY, 0.);
+
+    let v = Vector::new(1., 2., 3.);
+    let w = Vector::new(-4., 5., 0.5);
+    assert_eq!(v + w, Vector::new(-3., 7., 3.5));
+    assert_eq!(v - w, Vector::new(5., -3., 2.5));
+    assert_eq!(v * 2., Vector::new(2., 4., 6.));
+    assert_eq!(2. * v, v * 2.);
+    assert_eq!(v.dot(&w), 7.5);
+    assert!(v.cross(&w).dot(&v).abs() < EPSILON);
+    assert!(v.cross(&w).dot(&w).abs() < EPSILON);
+}
+
+#[test]
+fn vector_02() {
+    let v = Vector::new(0., -3., 4.);
+    assert!((v.norm() - 5.).abs() < EPSILON);
+
+    let u = v.normalized().unwrap();
+    assert!((u.norm() - 1.).abs() < EPSILON);
+    assert!(u.x().abs() < EPSILON);
+    assert!((u.y() + 0.6).abs() < EPSILON);
+    assert!((u.z() - 0.8).abs() < EPSILON);
+
+    assert_eq!(Vector::new(0., 0., 0.).normalized(), None);
+}
+
 #[test]
 fn rotate_around_axis_01() {
     let env = &QuestEnv::new();
@@ -626,6 +2427,51 @@ fn rotate_around_axis_02() {
     qureg.rotate_around_axis(-1, angle, axis).unwrap_err();
 }
 
+#[test]
+fn rotate_around_axis_all_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    expected.init_debug_state();
+
+    // a rotation by 2 PI is -I on each qubit: only a global phase (-1)^3
+    let axis = &Vector::new(0., 0., 1.);
+    qureg.rotate_around_axis_all(2. * PI, axis).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let exp = expected.get_amp(i).unwrap();
+        assert!((amp + exp).norm() < EPSILON);
+    }
+
+    qureg.init_zero_state();
+    expected.init_zero_state();
+    let axis = &Vector::new(1., 1., 0.);
+    qureg.rotate_around_axis_all(0.3, axis).unwrap();
+    for qubit in 0..3 {
+        expected.rotate_around_axis(qubit, 0.3, axis).unwrap();
+    }
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let exp = expected.get_amp(i).unwrap();
+        assert!((amp - exp).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn rotate_around_axis_all_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+
+    let axis = &Vector::new(0., 0., 0.);
+    qureg.rotate_around_axis_all(0.5, axis).unwrap_err();
+    for i in 0..8 {
+        let amp = qureg.get_real_amp(i).unwrap();
+        assert!((amp - 1. / 8_f64.sqrt() as Qreal).abs() < EPSILON);
+    }
+}
+
 #[test]
 fn controlled_rotate_x_01() {
     let env = &QuestEnv::new();
@@ -875,6 +2721,53 @@ fn pauli_x_01() {
     qureg.pauli_x(-1).unwrap_err();
 }
 
+#[test]
+fn pauli_x_except_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+
+    qureg.init_zero_state();
+    qureg.pauli_x_except(&[1, 2]).unwrap();
+    assert!((qureg.get_prob_amp(0b1001).unwrap() - 1.).abs() < EPSILON);
+
+    qureg.init_zero_state();
+    qureg.pauli_x_except(&[]).unwrap();
+    assert!((qureg.get_prob_amp(0b1111).unwrap() - 1.).abs() < EPSILON);
+
+    qureg.init_zero_state();
+    qureg.pauli_x_except(&[0, 1, 2, 3, 3]).unwrap();
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+
+    // invalid indices are detected before any gate is applied
+    qureg.pauli_x_except(&[0, 4]).unwrap_err();
+    qureg.pauli_x_except(&[-1]).unwrap_err();
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn for_each_qubit_except_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_zero_state();
+
+    let mut visited = vec![];
+    qureg
+        .for_each_qubit_except(&[2, 0], |_, qubit| {
+            visited.push(qubit);
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(visited, [1, 3]);
+
+    // errors returned by the closure are propagated
+    let err = qureg
+        .for_each_qubit_except(&[], |qureg, qubit| {
+            qureg.controlled_not(qubit, qubit + 1)
+        })
+        .unwrap_err();
+    assert!(matches!(err, QuestError::InvalidQuESTInputError { .. }));
+}
+
 #[test]
 fn pauli_y_01() {
     let env = &QuestEnv::new();
@@ -901,6 +2794,55 @@ fn pauli_z_01() {
     qureg.pauli_z(-1).unwrap_err();
 }
 
+#[test]
+fn apply_pauli_string_01() {
+    use PauliOpType::*;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg
+        .apply_pauli_string(&[2, 0], &[PAULI_X, PAULI_X])
+        .unwrap();
+    let amp = qureg.get_real_amp(5).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg
+        .apply_pauli_string(&[0, 1, 2], &[PAULI_Z, PAULI_I, PAULI_Y])
+        .unwrap();
+    // Z|1> = -|1>, Y|1> = -i|0>
+    let amp = qureg.get_imag_amp(1).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg.apply_pauli_string(&[], &[]).unwrap();
+}
+
+#[test]
+fn apply_pauli_string_02() {
+    use PauliOpType::*;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    assert_eq!(
+        qureg.apply_pauli_string(&[0, 1], &[PAULI_X]),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg
+        .apply_pauli_string(&[0, 3], &[PAULI_X, PAULI_X])
+        .unwrap_err();
+    qureg.apply_pauli_string(&[-1], &[PAULI_X]).unwrap_err();
+    qureg
+        .apply_pauli_string(&[0, 0], &[PAULI_X, PAULI_X])
+        .unwrap_err();
+
+    // The state is unchanged
+    let amp = qureg.get_real_amp(0).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn hadamard_01() {
     let env = &QuestEnv::new();
@@ -930,6 +2872,43 @@ fn controlled_not_01() {
     qureg.controlled_not(2, -1).unwrap_err();
 }
 
+#[test]
+fn controlled_not_edges_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+
+    // Prepare GHZ state `|0000> + |1111>`
+    qureg
+        .controlled_not_edges(&[(0, 1), (1, 2), (2, 3)])
+        .unwrap();
+
+    let amp0 = qureg.get_real_amp(0).unwrap();
+    let amp15 = qureg.get_real_amp(15).unwrap();
+    assert!((amp0 - SQRT_2.recip()).abs() < EPSILON);
+    assert!((amp15 - SQRT_2.recip()).abs() < EPSILON);
+    for i in 1..15 {
+        assert!(qureg.get_prob_amp(i).unwrap().abs() < EPSILON);
+    }
+}
+
+#[test]
+fn controlled_not_edges_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+
+    qureg.controlled_not_edges(&[]).unwrap();
+    qureg.controlled_not_edges(&[(0, 1), (1, 1)]).unwrap_err();
+    qureg.controlled_not_edges(&[(0, 1), (-1, 2)]).unwrap_err();
+    qureg.controlled_not_edges(&[(0, 1), (0, 3)]).unwrap_err();
+
+    // no gate has been applied
+    let amp = qureg.get_real_amp(1).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn multi_qubit_not_01() {
     let env = &QuestEnv::new();
@@ -980,6 +2959,101 @@ fn multi_controlled_multi_qubit_not_01() {
         .unwrap_err();
 }
 
+#[test]
+fn multi_state_controlled_multi_qubit_not_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+
+    // the targets 2 and 3 are flipped iff qubit 0 is set and qubit 1 is not
+    for state in 0..16 {
+        qureg.init_classical_state(state).unwrap();
+        qureg
+            .multi_state_controlled_multi_qubit_not(&[0, 1], &[1, 0], &[2, 3])
+            .unwrap();
+        let expected = if state & 0b11 == 0b01 {
+            state ^ 0b1100
+        } else {
+            state
+        };
+        let amp = qureg.get_real_amp(expected).unwrap();
+        assert!((amp - 1.).abs() < EPSILON, "state: {state}");
+    }
+}
+
+#[test]
+fn multi_state_controlled_multi_qubit_not_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_classical_state(0b0010).unwrap();
+
+    assert_eq!(
+        qureg.multi_state_controlled_multi_qubit_not(&[0, 1], &[0], &[2]),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[], &[], &[2])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0], &[0], &[])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0, 1], &[0, 2], &[2])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0, 0], &[0, 1], &[2])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0, 1], &[0, 1], &[2, 2])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0, 1], &[0, 1], &[1, 2])
+        .unwrap_err();
+    qureg
+        .multi_state_controlled_multi_qubit_not(&[0, 4], &[0, 1], &[2])
+        .unwrap_err();
+
+    // the state is unchanged
+    let amp = qureg.get_real_amp(0b0010).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn toffoli_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    // truth table: the target qubit 2 is flipped iff both controls are set
+    for controls in 0..4 {
+        for target in 0..2 {
+            let state = controls | (target << 2);
+            qureg.init_classical_state(state).unwrap();
+            qureg.toffoli(0, 1, 2).unwrap();
+
+            let expected = if controls == 0b11 {
+                state ^ 0b100
+            } else {
+                state
+            };
+            let amp = qureg.get_real_amp(expected).unwrap();
+            assert!((amp - 1.).abs() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn toffoli_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg.toffoli(0, 0, 1).unwrap_err();
+    qureg.toffoli(0, 1, 1).unwrap_err();
+    qureg.toffoli(1, 0, 1).unwrap_err();
+    qureg.toffoli(-1, 0, 1).unwrap_err();
+    qureg.toffoli(0, 3, 1).unwrap_err();
+    qureg.toffoli(0, 1, 3).unwrap_err();
+}
+
 #[test]
 fn controlled_pauli_y_01() {
     let env = &QuestEnv::new();
@@ -1013,6 +3087,45 @@ fn calc_prob_of_outcome_01() {
     let _ = qureg.calc_prob_of_outcome(4, 0).unwrap_err();
 }
 
+#[test]
+fn qubit_outcome_probabilities_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(0b101).unwrap();
+    qureg.rotate_y(1, 1.2).unwrap();
+
+    for qubit in 0..3 {
+        let (p0, p1) = qureg.qubit_outcome_probabilities(qubit).unwrap();
+        assert!((p0 + p1 - 1.).abs() < EPSILON);
+        let prob = qureg.calc_prob_of_outcome(qubit, 1).unwrap();
+        assert!((p1 - prob).abs() < EPSILON);
+    }
+    let (p0, _) = qureg.qubit_outcome_probabilities(1).unwrap();
+    assert!((p0 - Qreal::cos(0.6).powi(2)).abs() < EPSILON);
+
+    // the state is not collapsed
+    let amp = qureg.get_real_amp(0b101).unwrap();
+    assert!((amp - Qreal::cos(0.6)).abs() < EPSILON);
+
+    qureg.qubit_outcome_probabilities(-1).unwrap_err();
+    qureg.qubit_outcome_probabilities(3).unwrap_err();
+}
+
+#[test]
+fn qubit_outcome_probabilities_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_classical_state(2).unwrap();
+    rho.mix_damping(1, 0.3).unwrap();
+
+    let (p0, p1) = rho.qubit_outcome_probabilities(1).unwrap();
+    assert!((p0 - 0.3).abs() < EPSILON);
+    assert!((p1 - 0.7).abs() < EPSILON);
+    let (p0, p1) = rho.qubit_outcome_probabilities(0).unwrap();
+    assert!((p0 - 1.).abs() < EPSILON);
+    assert!(p1.abs() < EPSILON);
+}
+
 #[test]
 fn calc_prob_of_all_outcomes_01() {
     let env = &QuestEnv::new();
@@ -1057,6 +3170,146 @@ fn calc_prob_of_all_outcomes_02() {
         .unwrap_err();
 }
 
+#[test]
+fn prob_of_all_outcomes_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+
+    let outcome_probs = qureg.prob_of_all_outcomes(&[0]).unwrap();
+    assert_eq!(outcome_probs.len(), 2);
+    assert!((outcome_probs[0] - 0.5).abs() < EPSILON);
+    assert!((outcome_probs[1] - 0.5).abs() < EPSILON);
+
+    let outcome_probs = qureg.prob_of_all_outcomes(&[2, 0, 1]).unwrap();
+    assert_eq!(outcome_probs.len(), 8);
+    assert!((outcome_probs[0] - 0.5).abs() < EPSILON);
+    assert!((outcome_probs[2] - 0.5).abs() < EPSILON);
+
+    qureg.prob_of_all_outcomes(&[-1]).unwrap_err();
+    qureg.prob_of_all_outcomes(&[3]).unwrap_err();
+    qureg.prob_of_all_outcomes(&[0, 0]).unwrap_err();
+}
+
+#[test]
+fn argmax_probability_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    for state in 0..8 {
+        qureg.init_classical_state(state).unwrap();
+        let (index, prob) = qureg.argmax_probability().unwrap();
+        assert_eq!(index, state);
+        assert!((prob - 1.).abs() < EPSILON);
+    }
+
+    // ties are broken by the smallest index
+    qureg.init_plus_state();
+    let (index, prob) = qureg.argmax_probability().unwrap();
+    assert_eq!(index, 0);
+    assert!((prob - 0.125).abs() < EPSILON);
+
+    qureg.init_debug_state();
+    let (index, _) = qureg.argmax_probability().unwrap();
+    assert_eq!(index, 7);
+}
+
+#[test]
+fn argmax_probability_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_classical_state(2).unwrap();
+    rho.rotate_y(0, 0.5).unwrap();
+    rho.mix_dephasing(1, 0.3).unwrap();
+
+    let (index, prob) = rho.argmax_probability().unwrap();
+    assert_eq!(index, 2);
+    let expected = rho.get_density_amp(2, 2).unwrap().re;
+    assert!((prob - expected).abs() < EPSILON);
+}
+
+#[test]
+fn argmax_probability_03() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    // NaN is skipped
+    qureg.init_zero_state();
+    qureg.set_amps(2, &[0.6, Qreal::NAN], &[0., 0.]).unwrap();
+    let (index, prob) = qureg.argmax_probability().unwrap();
+    assert_eq!(index, 0);
+    assert!((prob - 1.).abs() < EPSILON);
+
+    qureg.set_amps(0, &[Qreal::NAN; 4], &[0.; 4]).unwrap();
+    let (index, prob) = qureg.argmax_probability().unwrap();
+    assert_eq!(index, 0);
+    assert!(prob.is_nan());
+}
+
+#[test]
+fn marginal_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+    qureg.pauli_x(2).unwrap();
+
+    let dist = qureg.marginal(&[2, 0]).unwrap();
+    assert_eq!(dist.qubits(), &[2, 0]);
+    assert_eq!(dist.probs().len(), 4);
+    assert!((dist.prob(1).unwrap() - 0.5).abs() < EPSILON);
+    assert!((dist.prob(3).unwrap() - 0.5).abs() < EPSILON);
+    assert!(dist.prob(4).is_none());
+
+    let (outcome, prob) = dist.most_likely();
+    assert_eq!(outcome, 1);
+    assert!((prob - 0.5).abs() < EPSILON);
+    assert!((dist.entropy() - 1.).abs() < 4. * EPSILON);
+
+    let dist = qureg.marginal(&[2]).unwrap();
+    assert_eq!(dist.most_likely().0, 1);
+    assert!(dist.entropy().abs() < EPSILON);
+
+    qureg.marginal(&[3]).unwrap_err();
+    qureg.marginal(&[0, 0]).unwrap_err();
+}
+
+#[test]
+fn subsystem_prob_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+
+    let prob = qureg.subsystem_prob(&[0, 2]).unwrap();
+    assert!((prob - 1.).abs() < EPSILON);
+
+    // unnormalized state
+    qureg.apply_projector(1, 0).unwrap();
+    for qubits in [&[0][..], &[1], &[2, 0], &[0, 1, 2]] {
+        let prob = qureg.subsystem_prob(qubits).unwrap();
+        assert!((prob - 0.5).abs() < EPSILON);
+    }
+
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_plus_state();
+    rho.apply_projector(0, 1).unwrap();
+    let prob = rho.subsystem_prob(&[1]).unwrap();
+    assert!((prob - 0.5).abs() < EPSILON);
+}
+
+#[test]
+fn subsystem_prob_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    qureg.subsystem_prob(&[]).unwrap_err();
+    qureg.subsystem_prob(&[-1]).unwrap_err();
+    qureg.subsystem_prob(&[3]).unwrap_err();
+    qureg.subsystem_prob(&[1, 1]).unwrap_err();
+}
+
 #[test]
 fn collapse_to_outcome_01() {
     let env = &QuestEnv::new();
@@ -1103,6 +3356,183 @@ fn measure_with_stats_01() {
     let _ = qureg.measure_with_stats(3, prob).unwrap_err();
 }
 
+#[cfg(feature = "rand")]
+#[test]
+fn measure_with_rng_01() {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    let mut measure_all = |seed| {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        qureg.init_plus_state();
+        (0..3)
+            .map(|qubit| qureg.measure_with_rng(qubit, rng).unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    for seed in 0..10 {
+        let outcomes = measure_all(seed);
+        assert_eq!(outcomes, measure_all(seed));
+        assert!(outcomes.iter().all(|&x| x == 0 || x == 1));
+    }
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    qureg.measure_with_rng(3, rng).unwrap_err();
+    qureg.measure_with_rng(-1, rng).unwrap_err();
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn measure_with_rng_02() {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let rng = &mut StdRng::seed_from_u64(0);
+
+    // deterministic outcomes never collapse onto zero probability
+    for _ in 0..20 {
+        qureg.init_classical_state(2).unwrap();
+        assert_eq!(qureg.measure_with_rng(0, rng).unwrap(), 0);
+        assert_eq!(qureg.measure_with_rng(1, rng).unwrap(), 1);
+    }
+
+    // the state collapses consistently with the outcome
+    qureg.init_zero_state();
+    qureg.hadamard(0).and(qureg.controlled_not(0, 1)).unwrap();
+    let outcome = qureg.measure_with_rng(0, rng).unwrap();
+    let prob = qureg.calc_prob_of_outcome(1, outcome).unwrap();
+    assert!((prob - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn measure_all_with_probs_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    for _ in 0..10 {
+        // Prepare a GHZ state `|000> + |111>`
+        qureg.init_zero_state();
+        qureg.hadamard(0).unwrap();
+        qureg.controlled_not(0, 1).unwrap();
+        qureg.controlled_not(1, 2).unwrap();
+
+        let (outcomes, probs) = qureg.measure_all_with_probs().unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(probs.len(), 3);
+        assert!(outcomes == [0, 0, 0] || outcomes == [1, 1, 1]);
+        assert!((probs[0] - 0.5).abs() < EPSILON);
+        assert!((probs[1] - 1.).abs() < EPSILON);
+        assert!((probs[2] - 1.).abs() < EPSILON);
+
+        // the register has collapsed to the measured basis state
+        let index = if outcomes[0] == 0 { 0 } else { 7 };
+        assert!((qureg.get_prob_amp(index).unwrap() - 1.).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn measure_all_with_probs_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let (_, probs) = qureg.measure_all_with_probs().unwrap();
+    assert!((probs[0] - 0.5).abs() < EPSILON);
+    assert!((probs[1] - 0.5).abs() < EPSILON);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn to_ndarray1_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+
+    let amps = qureg.to_ndarray1().unwrap();
+    assert_eq!(amps.len(), 8);
+    for (i, amp) in amps.iter().enumerate() {
+        assert_eq!(*amp, qureg.get_amp(i as i64).unwrap());
+    }
+
+    let rho = &Qureg::try_new_density(3, env).unwrap();
+    rho.to_ndarray1().unwrap_err();
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn to_ndarray_01() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_debug_state();
+
+    let mtr = rho.to_ndarray().unwrap();
+    assert_eq!(mtr.dim(), (4, 4));
+    for ((row, col), amp) in mtr.indexed_iter() {
+        let expected = rho.get_density_amp(row as i64, col as i64).unwrap();
+        assert_eq!(*amp, expected);
+    }
+
+    let qureg = &Qureg::try_new(2, env).unwrap();
+    qureg.to_ndarray().unwrap_err();
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn timing_report_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    Qureg::reset_timing_report();
+    assert!(Qureg::timing_report().is_empty());
+
+    qureg.hadamard(0).unwrap();
+    qureg.toffoli(0, 1, 2).unwrap();
+    qureg.hadamard(4).unwrap_err();
+    qureg.measure(0).unwrap();
+
+    let report = Qureg::timing_report();
+    assert!(report.contains_key("hadamard"));
+    assert!(report.contains_key("multi_controlled_multi_qubit_not"));
+    assert!(!report.contains_key("toffoli"));
+    assert!(!report.contains_key("measure"));
+
+    Qureg::reset_timing_report();
+    assert!(Qureg::timing_report().is_empty());
+}
+
+#[test]
+fn reset_qubit_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    for _ in 0..10 {
+        // Prepare a Bell state `|00> + |11>`
+        qureg.init_zero_state();
+        qureg.hadamard(0).unwrap();
+        qureg.controlled_not(0, 1).unwrap();
+
+        qureg.reset_qubit(0).unwrap();
+        let prob = qureg.calc_prob_of_outcome(0, 0).unwrap();
+        assert!((prob - 1.).abs() < EPSILON);
+        assert_eq!(qureg.measure(0).unwrap(), 0);
+
+        // The other qubit collapsed to a classical state
+        let prob = qureg.calc_prob_of_outcome(1, 0).unwrap();
+        assert!(prob.abs() < EPSILON || (prob - 1.).abs() < EPSILON);
+    }
+
+    qureg.reset_qubit(-1).unwrap_err();
+    qureg.reset_qubit(2).unwrap_err();
+}
+
 #[test]
 fn calc_inner_product_01() {
     let env = &QuestEnv::new();
@@ -1169,6 +3599,32 @@ fn calc_density_inner_product_03() {
     let _ = calc_density_inner_product(qureg, other_qureg).unwrap_err();
 }
 
+#[test]
+fn calc_density_inner_product_04() {
+    let env = &QuestEnv::new();
+    let rho1 = &mut Qureg::try_new_density(1, env).unwrap();
+    let rho2 = &mut Qureg::try_new_density(1, env).unwrap();
+
+    // general (not normalized, not Hermitian) matrices
+    rho1.init_state_from_amps(&[1., 2., 3., 4.], &[0., 1., 0., -1.])
+        .unwrap();
+    rho2.init_state_from_amps(&[-1., 0., -2., 0.5], &[1., 0., 0., 2.])
+        .unwrap();
+
+    // the literal value of: Re Tr(rho1^dagger rho2)
+    let prod = calc_density_inner_product(rho1, rho2).unwrap();
+    assert!((prod + 7.).abs() < EPSILON);
+    let prod = calc_density_inner_product(rho2, rho1).unwrap();
+    assert!((prod + 7.).abs() < EPSILON);
+
+    // no normalization: <rho, 2 rho> = 2 <rho, rho>
+    rho1.init_classical_state(0).unwrap();
+    rho2.init_state_from_amps(&[2., 0., 0., 0.], &[0., 0., 0., 0.])
+        .unwrap();
+    let prod = calc_density_inner_product(rho1, rho2).unwrap();
+    assert!((prod - 2.).abs() < EPSILON);
+}
+
 #[test]
 fn get_quest_seeds_01() {
     let env = &QuestEnv::new();
@@ -1188,6 +3644,108 @@ fn get_quest_seeds_02() {
     assert_eq!(seed_array, seeds);
 }
 
+#[test]
+fn quest_env_new_seeded_01() {
+    let seed_array = &[4, 3, 2, 1];
+    let env1 = &QuestEnv::new_seeded(seed_array);
+    let env2 = &QuestEnv::new_seeded(seed_array);
+
+    assert_eq!(get_quest_seeds(env1), seed_array);
+    assert_eq!(get_quest_seeds(env2), seed_array);
+}
+
+#[test]
+#[should_panic(expected = "seeds must not be empty")]
+fn quest_env_new_seeded_02() {
+    let _ = QuestEnv::new_seeded(&[]);
+}
+
+#[test]
+fn quest_env_sequential_01() {
+    {
+        let env = &QuestEnv::new();
+        let qureg = &mut Qureg::try_new(2, env).unwrap();
+        qureg.init_zero_state();
+        qureg.pauli_x(0).unwrap();
+    }
+    {
+        let env = &QuestEnv::new();
+        let qureg = &mut Qureg::try_new(2, env).unwrap();
+        qureg.init_zero_state();
+        qureg.pauli_x(0).unwrap();
+        assert!((qureg.get_prob_amp(1).unwrap() - 1.).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn quest_env_concurrent_01() {
+    let env1 = QuestEnv::new();
+    let env2 = QuestEnv::new();
+    drop(env1);
+
+    let qureg = &mut Qureg::try_new(2, &env2).unwrap();
+    qureg.init_zero_state();
+    qureg.pauli_x(1).unwrap();
+    assert!((qureg.get_prob_amp(2).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn quest_env_num_threads_01() {
+    let env = &QuestEnv::new();
+    let num_threads = env.num_threads();
+
+    assert!(num_threads >= 1);
+    let env_str = env.get_environment_string().unwrap();
+    assert!(env_str.contains(&format!("threads={num_threads}")));
+}
+
+#[test]
+fn quest_env_make_qureg_01() {
+    let env = &QuestEnv::new();
+
+    // many short-lived registers in a loop
+    for num_qubits in 1..5 {
+        let qureg = &mut env.make_qureg(num_qubits).unwrap();
+        assert_eq!(qureg.num_qubits(), num_qubits);
+        assert_eq!(qureg.kind(), QuregKind::StateVector);
+        qureg.init_plus_state();
+        qureg.hadamard(0).unwrap();
+    }
+
+    env.make_qureg(0).unwrap_err();
+    env.make_qureg(-1).unwrap_err();
+}
+
+#[test]
+fn quest_env_make_density_qureg_01() {
+    let env = &QuestEnv::new();
+
+    for num_qubits in 1..4 {
+        let rho = &env.make_density_qureg(num_qubits).unwrap();
+        assert_eq!(rho.num_qubits(), num_qubits);
+        assert_eq!(rho.kind(), QuregKind::DensityMatrix);
+        assert!((rho.calc_total_prob() - 1.).abs() < EPSILON);
+    }
+
+    env.make_density_qureg(0).unwrap_err();
+}
+
+#[test]
+fn quest_env_set_reporting_01() {
+    let env = &QuestEnv::new();
+    assert!(env.is_reporting());
+
+    env.set_reporting(false);
+    assert!(!env.is_reporting());
+    env.report_quest_env();
+    let qureg = &Qureg::try_new(2, env).unwrap();
+    qureg.report_qureg_params();
+    qureg.report_state_to_screen(0);
+
+    env.set_reporting(true);
+    assert!(env.is_reporting());
+}
+
 #[test]
 fn start_recording_qasm_01() {
     let env = &QuestEnv::new();
@@ -1200,6 +3758,223 @@ fn start_recording_qasm_01() {
     qureg.print_recorded_qasm();
 }
 
+#[test]
+fn is_recording_qasm_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    assert!(!qureg.is_recording_qasm());
+
+    qureg.start_recording_qasm();
+    assert!(qureg.is_recording_qasm());
+    qureg.stop_recording_qasm();
+    assert!(!qureg.is_recording_qasm());
+}
+
+#[test]
+fn with_qasm_recording_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let outcome = qureg.with_qasm_recording(|qureg| {
+        assert!(qureg.is_recording_qasm());
+        qureg.pauli_x(0).unwrap();
+        qureg.measure(0).unwrap()
+    });
+    assert_eq!(outcome, 1);
+    assert!(!qureg.is_recording_qasm());
+}
+
+#[test]
+fn with_qasm_recording_02() {
+    use std::panic::{
+        catch_unwind,
+        AssertUnwindSafe,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        qureg.with_qasm_recording(|_| panic!("oops"));
+    }));
+    assert!(result.is_err());
+    assert!(!qureg.is_recording_qasm());
+}
+
+#[test]
+fn run_qasm_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_zero_state();
+
+    let src = r#"
+        OPENQASM 2.0;
+        include "qelib1.inc";
+        qreg a[1];
+        qreg b[2];
+        creg c[3];
+
+        // Prepare GHZ state
+        h a[0];
+        cx a[0], b[0];
+        CX b[0],b[1];
+        barrier a, b;
+        rz(-pi / 2) a[0]; rz(pi/2) a[0];
+    "#;
+    run_qasm(src, qureg).unwrap();
+
+    let amp0 = qureg.get_real_amp(0).unwrap();
+    let amp7 = qureg.get_real_amp(7).unwrap();
+    assert!((amp0 - SQRT_2.recip()).abs() < EPSILON);
+    assert!((amp7 - SQRT_2.recip()).abs() < EPSILON);
+
+    run_qasm("qreg q[3]; creg c[3]; measure q -> c;", qureg).unwrap();
+    let outcome = qureg.measure(0).unwrap();
+    assert_eq!(qureg.measure(1).unwrap(), outcome);
+    assert_eq!(qureg.measure(2).unwrap(), outcome);
+
+    run_qasm("qreg q[3]; x q; reset q[1];", qureg).unwrap();
+    let index = if outcome == 0 { 0b101 } else { 0b000 };
+    let amp = qureg.get_real_amp(index).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn run_qasm_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+
+    let invalid = [
+        "OPENQASM 3.0;",
+        "qreg q[4];",
+        "qreg q[2]; qreg r[2];",
+        "qreg q[2]; qreg q[1];",
+        "qreg q[0];",
+        "qreg a[1]; qreg b[2147483647];",
+        "qreg q[2]; x r[0];",
+        "qreg q[2]; x q[2];",
+        "qreg q[2]; x q[-1];",
+        "qreg q[2]; cx q[0],q[0];",
+        "qreg q[2]; cx q,q[0];",
+        "qreg q[2]; ccz q[0],q[1];",
+        "qreg q[2]; rx q[0];",
+        "qreg q[2]; x(pi) q[0];",
+        "qreg q[2]; rx(tau) q[0];",
+        "qreg q[2]; measure q[0];",
+        "qreg q[2]; measure q[0] -> c[0];",
+        "qreg q[2]; gate g a { x a; }",
+        "qreg q[2]; x q[0]",
+        "qreg q[2]; x q[0]; cx q[0],q[2];",
+    ];
+    for src in invalid {
+        assert!(
+            matches!(
+                run_qasm(src, qureg),
+                Err(QuestError::InvalidQuESTInputError { .. })
+            ),
+            "{src}"
+        );
+    }
+
+    // The register is left intact
+    let amp = qureg.get_real_amp(1).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn run_qasm_03() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+
+    // Nested parentheses in gate parameters
+    let src = "qreg q[2]; h q; rz(-(pi/2)) q[0]; rz((pi)/2) q[0]; h q;";
+    run_qasm(src, qureg).unwrap();
+    let amp = qureg.get_real_amp(0).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    // Gates recorded by QuEST: `U` and `u3` are the same gate
+    let src = "qreg q[2]; U(pi/2,0,pi) q[0]; u3(pi,0,pi) q[1];";
+    run_qasm(src, qureg).unwrap();
+    let amp = qureg.get_real_amp(2).unwrap();
+    assert!((amp - SQRT_2.recip()).abs() < EPSILON);
+    let amp = qureg.get_real_amp(3).unwrap();
+    assert!((amp - SQRT_2.recip()).abs() < EPSILON);
+
+    // u2(0, pi) is the Hadamard gate
+    run_qasm("qreg q[2]; u2(0,pi) q[0];", qureg).unwrap();
+    let amp = qureg.get_real_amp(2).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    run_qasm("qreg q[2]; rz(-(pi/2) q[0];", qureg).unwrap_err();
+    run_qasm("qreg q[2]; u3(pi,0) q[0];", qureg).unwrap_err();
+}
+
+#[test]
+fn probability_01() {
+    assert_eq!(*Probability::new(0.).unwrap(), 0.);
+    assert_eq!(*Probability::new(1.).unwrap(), 1.);
+    assert_eq!(Qreal::from(Probability::new(0.25).unwrap()), 0.25);
+    assert!(Probability::try_from(0.5).is_ok());
+
+    Probability::new(-0.1).unwrap_err();
+    Probability::new(1.1).unwrap_err();
+    Probability::new(Qreal::NAN).unwrap_err();
+    Probability::try_from(Qreal::INFINITY).unwrap_err();
+}
+
+#[test]
+fn probability_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    let other = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_plus_state();
+    other.init_plus_state();
+
+    let prob = Probability::new(0.3).unwrap();
+    qureg.mix_damping(0, prob).unwrap();
+    qureg
+        .mix_pauli(1, prob, 0.1, Probability::new(0.2).unwrap())
+        .unwrap();
+    other.mix_damping(0, 0.3).unwrap();
+    other.mix_pauli(1, 0.3, 0.1, 0.2).unwrap();
+
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = qureg.get_density_amp(row, col).unwrap();
+            let expected = other.get_density_amp(row, col).unwrap();
+            assert!((amp - expected).norm() < EPSILON);
+        }
+    }
+
+    // A valid probability may still be out of range for a given channel
+    qureg
+        .mix_dephasing(0, Probability::new(0.75).unwrap())
+        .unwrap_err();
+}
+
+#[test]
+fn probability_03() -> Result<(), QuestError> {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env)?;
+    let other = &mut Qureg::try_new_density(2, env)?;
+    qureg.init_plus_state();
+    other.init_plus_state();
+
+    qureg.mix_dephasing(0, Probability::new(0.25)?)?;
+    qureg.mix_two_qubit_dephasing(0, 1, Probability::new(0.5)?)?;
+    qureg.mix_depolarising(1, Probability::new(0.5)?)?;
+    qureg.mix_two_qubit_depolarising(0, 1, Probability::new(0.5)?)?;
+    qureg.mix_density_matrix(Probability::new(0.5)?, other)?;
+    other.mix_dephasing(0, 0.25)?;
+    other.mix_two_qubit_dephasing(0, 1, 0.5)?;
+
+    assert!((qureg.calc_total_prob() - 1.).abs() < EPSILON);
+    Ok(())
+}
+
 #[test]
 fn mix_dephasing_01() {
     let env = &QuestEnv::new();
@@ -1367,6 +4142,54 @@ fn mix_pauli_02() {
     qureg.mix_pauli(1, prob_x, prob_y, prob_z).unwrap_err();
 }
 
+#[test]
+fn mix_pauli_04() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let mut err_msg = |prob_x, prob_y, prob_z| match qureg
+        .mix_pauli(0, prob_x, prob_y, prob_z)
+        .unwrap_err()
+    {
+        QuestError::InvalidQuESTInputError {
+            err_msg,
+            err_func,
+        } => {
+            assert_eq!(err_func, "mix_pauli");
+            err_msg
+        }
+        _ => panic!("expected InvalidQuESTInputError"),
+    };
+
+    assert_eq!(
+        err_msg(-0.25, 0., 0.),
+        "The probability of a Pauli X error, prob_x = -0.25, must be in [0, \
+         1]."
+    );
+    assert_eq!(
+        err_msg(0., 1.5, 0.),
+        "The probability of a Pauli Y error, prob_y = 1.5, must be in [0, 1]."
+    );
+    assert_eq!(
+        err_msg(0., 0., Qreal::NAN),
+        "The probability of a Pauli Z error, prob_z = NaN, must be in [0, 1]."
+    );
+    assert_eq!(
+        err_msg(0.5, 0.25, 0.),
+        "The probability of a Pauli X error, prob_x = 0.5, must not exceed \
+         the probability of no error, 1 - prob_x - prob_y - prob_z = 0.25."
+    );
+    assert_eq!(
+        err_msg(0., 0.25, 0.5),
+        "The probability of a Pauli Z error, prob_z = 0.5, must not exceed \
+         the probability of no error, 1 - prob_x - prob_y - prob_z = 0.25."
+    );
+
+    qureg.mix_pauli(0, 0.25, 0.25, 0.25).unwrap();
+    qureg.mix_pauli(0, 0.5, 0., 0.).unwrap();
+}
+
 #[test]
 fn mix_pauli_03() {
     let env = &QuestEnv::new();
@@ -1444,16 +4267,82 @@ fn mix_density_matrix_03() {
 // }
 
 #[test]
-fn calc_purity_01() {
+fn calc_purity_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let _ = qureg.calc_purity().unwrap();
+
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+    let _ = qureg.calc_purity().unwrap_err();
+}
+
+#[test]
+fn is_normalized_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    qureg.init_plus_state();
+    assert!(qureg.is_normalized(EPSILON));
+    qureg.hadamard(1).unwrap();
+    assert!(qureg.is_normalized(EPSILON));
+
+    qureg.apply_projector(0, 1).unwrap();
+    assert!(!qureg.is_normalized(EPSILON));
+    assert!(qureg.is_normalized(0.6));
+
+    qureg.init_blank_state();
+    assert!(!qureg.is_normalized(EPSILON));
+}
+
+#[test]
+fn is_normalized_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+
+    rho.init_plus_state();
+    rho.mix_depolarising(0, 0.3).unwrap();
+    assert!(rho.is_normalized(EPSILON));
+
+    rho.set_density_amps(0, 0, &[2.], &[0.]).unwrap();
+    assert!(!rho.is_normalized(EPSILON));
+}
+
+#[test]
+fn l2_norm_sq_fast_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    qureg.init_plus_state();
+    assert!((qureg.l2_norm_sq_fast() - 1.).abs() < EPSILON);
+
+    // sum of |2n/10 + i(2n+1)/10|^2 over n = 0..8
+    qureg.init_debug_state();
+    let norm_sq = qureg.l2_norm_sq_fast();
+    assert!((norm_sq - 12.4).abs() < 100. * EPSILON);
+    assert!((norm_sq - qureg.calc_total_prob()).abs() < 100. * EPSILON);
+}
+
+#[test]
+fn l2_norm_sq_fast_02() {
     let env = &QuestEnv::new();
-    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
-    qureg.init_zero_state();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
 
-    let _ = qureg.calc_purity().unwrap();
+    rho.init_plus_state();
+    assert!((rho.l2_norm_sq_fast() - 1.).abs() < 10. * EPSILON);
 
-    let qureg = &mut Qureg::try_new(2, env).unwrap();
-    qureg.init_zero_state();
-    let _ = qureg.calc_purity().unwrap_err();
+    // not the trace, but the sum of squares of all elements
+    rho.init_debug_state();
+    let mut expected = 0.;
+    for row in 0..4 {
+        for col in 0..4 {
+            expected += rho.get_density_amp(row, col).unwrap().norm_sqr();
+        }
+    }
+    let norm_sq = rho.l2_norm_sq_fast();
+    assert!((norm_sq - expected).abs() < expected * 100. * EPSILON);
 }
 
 #[test]
@@ -1492,6 +4381,82 @@ fn calc_fidelity_03() {
     let _ = qureg.calc_fidelity(pure_state).unwrap_err();
 }
 
+#[test]
+fn fidelity_with_01() {
+    let env = &QuestEnv::new();
+    let psi = &mut Qureg::try_new(2, env).unwrap();
+    let phi = &mut Qureg::try_new(2, env).unwrap();
+    psi.init_plus_state();
+    phi.init_zero_state();
+    let rho_psi = &Qureg::density_from_pure(psi, env).unwrap();
+    let rho_phi = &Qureg::density_from_pure(phi, env).unwrap();
+
+    for (a, b) in [
+        (&*psi, &*phi),
+        (psi, rho_phi),
+        (rho_psi, phi),
+        (rho_psi, rho_phi),
+    ] {
+        let fidelity = a.fidelity_with(b).unwrap();
+        assert!((fidelity - 0.25).abs() < EPSILON);
+        let fidelity = b.fidelity_with(a).unwrap();
+        assert!((fidelity - 0.25).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn fidelity_with_02() {
+    let env = &QuestEnv::new();
+    let pure = &mut Qureg::try_new_density(2, env).unwrap();
+    let mixed = &mut Qureg::try_new_density(2, env).unwrap();
+    pure.init_plus_state();
+    mixed.init_plus_state();
+    mixed.mix_dephasing(0, 0.5).unwrap();
+
+    // Tr(pure mixed) = <+|mixed|+>
+    let fidelity = pure.fidelity_with(mixed).unwrap();
+    assert!((fidelity - 0.5).abs() < EPSILON);
+    let fidelity = mixed.fidelity_with(pure).unwrap();
+    assert!((fidelity - 0.5).abs() < EPSILON);
+
+    mixed.fidelity_with(mixed).unwrap_err();
+
+    let other = &Qureg::try_new_density(3, env).unwrap();
+    pure.fidelity_with(other).unwrap_err();
+}
+
+#[test]
+fn fidelity_loss_01() {
+    let env = &QuestEnv::new();
+    let reference = &mut Qureg::try_new(2, env).unwrap();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    reference.init_plus_state();
+
+    qureg.init_plus_state();
+    assert!(qureg.fidelity_loss(reference).unwrap().abs() < EPSILON);
+    qureg.init_zero_state();
+    let loss = qureg.fidelity_loss(reference).unwrap();
+    assert!((loss - 0.75).abs() < EPSILON);
+
+    rho.init_pure_state(reference).unwrap();
+    assert!(rho.fidelity_loss(reference).unwrap().abs() < EPSILON);
+    rho.mix_dephasing(0, 0.5).unwrap();
+    let loss = rho.fidelity_loss(reference).unwrap();
+    assert!((loss - 0.5).abs() < EPSILON);
+}
+
+#[test]
+fn fidelity_loss_02() {
+    let env = &QuestEnv::new();
+    let qureg = &Qureg::try_new(2, env).unwrap();
+    let reference = &Qureg::try_new_density(2, env).unwrap();
+    qureg.fidelity_loss(reference).unwrap_err();
+
+    let reference = &Qureg::try_new(3, env).unwrap();
+    qureg.fidelity_loss(reference).unwrap_err();
+}
+
 #[test]
 fn swap_gate_01() {
     let env = &QuestEnv::new();
@@ -1576,6 +4541,98 @@ fn sqrt_swap_gate_03() {
     qureg.sqrt_swap_gate(-4, -4).unwrap_err();
 }
 
+#[test]
+fn controlled_swap_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+
+    // |q2 q1 q0> = |011>
+    qureg.init_classical_state(0b011).unwrap();
+    qureg.controlled_swap(0, 1, 2).unwrap();
+    let amp = qureg.get_real_amp(0b101).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    // control qubit in |0> state: nothing happens
+    qureg.init_classical_state(0b010).unwrap();
+    qureg.controlled_swap(0, 1, 2).unwrap();
+    let amp = qureg.get_real_amp(0b010).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg.init_classical_state(0b110).unwrap();
+    qureg.controlled_swap(2, 0, 1).unwrap();
+    let amp = qureg.get_real_amp(0b101).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn controlled_swap_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(0b011).unwrap();
+
+    qureg.controlled_swap(0, 1, 1).unwrap_err();
+    qureg.controlled_swap(0, 0, 1).unwrap_err();
+    qureg.controlled_swap(1, 0, 1).unwrap_err();
+    qureg.controlled_swap(-1, 0, 1).unwrap_err();
+    qureg.controlled_swap(3, 0, 1).unwrap_err();
+    qureg.controlled_swap(0, 1, 3).unwrap_err();
+
+    // the state is left intact
+    let amp = qureg.get_real_amp(0b011).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn permute_qubits_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+
+    qureg.permute_qubits(&[1, 2, 0]).unwrap();
+    let amp = qureg.get_real_amp(2).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg.permute_qubits(&[0, 1, 2]).unwrap();
+    let amp = qureg.get_real_amp(2).unwrap();
+    assert!((amp - 1.).abs() < EPSILON);
+
+    qureg.permute_qubits(&[0, 1]).unwrap_err();
+    qureg.permute_qubits(&[0, 1, 2, 3]).unwrap_err();
+    qureg.permute_qubits(&[0, 1, 1]).unwrap_err();
+    qureg.permute_qubits(&[0, 1, 3]).unwrap_err();
+    qureg.permute_qubits(&[-1, 1, 2]).unwrap_err();
+}
+
+#[test]
+fn permute_qubits_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+
+    qureg.permute_qubits(&[1, 0]).unwrap();
+    for (row, col) in [(0, 0), (0, 2), (2, 0), (2, 2)] {
+        let amp = qureg.get_density_amp(row, col).unwrap();
+        assert!((amp.re - 0.5).abs() < EPSILON);
+    }
+    let amp = qureg.get_density_amp(1, 1).unwrap();
+    assert!(amp.norm() < EPSILON);
+}
+
+#[test]
+fn reverse_qubit_order_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+    qureg.hadamard(1).unwrap();
+
+    qureg.reverse_qubit_order().unwrap();
+    for i in [4, 6] {
+        let amp = qureg.get_real_amp(i).unwrap();
+        assert!((amp - SQRT_2.recip()).abs() < EPSILON);
+    }
+}
+
 #[test]
 fn multi_rotate_z_01() {
     let env = &QuestEnv::new();
@@ -1686,6 +4743,46 @@ fn multi_qubit_unitary_02() {
     qureg.multi_qubit_unitary(&[1, -1], u).unwrap_err();
 }
 
+#[test]
+fn multi_qubit_unitary_03() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    expected.init_debug_state();
+
+    // a permutation matrix with phases
+    let mut re = [[0.; 4]; 4];
+    let mut im = [[0.; 4]; 4];
+    re[0][3] = 1.;
+    im[1][1] = 1.;
+    re[2][2] = 1.;
+    im[3][0] = -1.;
+
+    let rows = re.iter().zip(&im).map(|(re, im)| {
+        re.iter()
+            .zip(im)
+            .map(|(&re, &im)| Qcomplex::new(re, im))
+            .collect::<Vec<_>>()
+    });
+    let heap = &ComplexMatrixN::from_rows(rows).unwrap();
+    let stack = StackComplexMatrixN::new(&re, &im).unwrap();
+
+    for targets in [[2, 0], [0, 1], [1, 2]] {
+        qureg
+            .multi_qubit_unitary(&targets, &stack.as_matrix())
+            .unwrap();
+        expected.multi_qubit_unitary(&targets, heap).unwrap();
+    }
+
+    for index in 0..8 {
+        assert_eq!(
+            qureg.get_amp(index).unwrap(),
+            expected.get_amp(index).unwrap()
+        );
+    }
+}
+
 #[test]
 fn controlled_multi_qubit_unitary_01() {
     let env = &QuestEnv::new();
@@ -1934,6 +5031,33 @@ fn apply_matrix2_01() {
     qureg.apply_matrix2(2, m).unwrap_err();
 }
 
+#[test]
+fn apply_matrix2_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(1, env).unwrap();
+    rho.init_zero_state();
+
+    // X|0><0| = |1><0|, not X|0><0|X = |1><1|
+    let m = &ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+    rho.apply_matrix2(0, m).unwrap();
+
+    let amp = rho.get_density_amp(1, 0).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+    assert!(amp.im.abs() < EPSILON);
+    for (row, col) in [(0, 0), (0, 1), (1, 1)] {
+        let amp = rho.get_density_amp(row, col).unwrap();
+        assert!(amp.re.abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+
+    // the matrix need not be unitary: scale the only nonzero entry
+    let m = &ComplexMatrix2::new([[2., 0.], [0., 3.]], [[0., 0.], [0., 1.]]);
+    rho.apply_matrix2(0, m).unwrap();
+    let amp = rho.get_density_amp(1, 0).unwrap();
+    assert!((amp.re - 3.).abs() < EPSILON);
+    assert!((amp.im - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn mix_kraus_map_01() {
     let env = &QuestEnv::new();
@@ -1977,6 +5101,39 @@ fn mix_kraus_map_03() {
     qureg.mix_kraus_map(0, &[m1, m1, m1, m1]).unwrap_err();
 }
 
+#[test]
+fn mix_kraus_map_04() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let m = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+
+    qureg.mix_kraus_map_owned(0, &[m]).unwrap();
+    qureg.mix_kraus_map_owned(1, &[m]).unwrap();
+
+    let amp = qureg.get_density_amp(3, 3).unwrap();
+    assert!((amp.re - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn mix_kraus_map_05() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+
+    let m = ComplexMatrix2::new([[0., 1.], [1., 0.]], [[0., 0.], [0., 0.]]);
+
+    assert_eq!(
+        qureg.mix_kraus_map(0, &[&m; 5]),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.mix_nontp_kraus_map(0, &[&m; 5]),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
 #[test]
 fn mix_two_qubit_kraus_map_01() {
     let env = &QuestEnv::new();
@@ -2060,6 +5217,96 @@ fn mix_two_qubit_kraus_map_03() {
     qureg.mix_two_qubit_kraus_map(0, 2, &[m, m, m]).unwrap_err();
 }
 
+#[test]
+fn mix_two_qubit_kraus_map_04() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(3, env).unwrap();
+    qureg.init_zero_state();
+
+    let m = &ComplexMatrix4::new(
+        [
+            [0., 0., 0., 1.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [1., 0., 0., 0.],
+        ],
+        [
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+        ],
+    );
+    assert_eq!(
+        qureg.mix_two_qubit_kraus_map(0, 1, &[m; 17]),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.mix_nontp_two_qubit_kraus_map(0, 1, &[m; 17]),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
+#[test]
+fn mix_two_qubit_kraus_map_05() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+
+    // Flip the least significant qubit of the operator's basis with
+    // probability 1/4, i.e. `K0 = 1/2 (I ⊗ X)`, `K1 = sqrt(3)/2 I`.
+    let zero = [[0.; 4]; 4];
+    let k0 = ComplexMatrix4::new(
+        [
+            [0., 0.5, 0., 0.],
+            [0.5, 0., 0., 0.],
+            [0., 0., 0., 0.5],
+            [0., 0., 0.5, 0.],
+        ],
+        zero,
+    );
+    let c = (0.75 as Qreal).sqrt();
+    let k1 = ComplexMatrix4::new(
+        [
+            [c, 0., 0., 0.],
+            [0., c, 0., 0.],
+            [0., 0., c, 0.],
+            [0., 0., 0., c],
+        ],
+        zero,
+    );
+
+    // The expected density matrices: `rho[i][j]`, for initial state |00>
+    // and the flipped qubit being qubit 0 or qubit 1
+    let mut rho_flip_0 = [[0.; 4]; 4];
+    rho_flip_0[0][0] = 0.75;
+    rho_flip_0[1][1] = 0.25;
+    let mut rho_flip_1 = [[0.; 4]; 4];
+    rho_flip_1[0][0] = 0.75;
+    rho_flip_1[2][2] = 0.25;
+
+    let cases = [
+        (0, 1, [&k0, &k1], rho_flip_0),
+        (0, 1, [&k1, &k0], rho_flip_0),
+        (1, 0, [&k0, &k1], rho_flip_1),
+        (1, 0, [&k1, &k0], rho_flip_1),
+    ];
+    for (target1, target2, ops, rho) in cases {
+        qureg.init_zero_state();
+        qureg
+            .mix_two_qubit_kraus_map(target1, target2, &ops)
+            .unwrap();
+
+        for (row, rho_row) in rho.iter().enumerate() {
+            for (col, expected) in rho_row.iter().enumerate() {
+                let amp =
+                    qureg.get_density_amp(row as i64, col as i64).unwrap();
+                assert!((amp.re - expected).abs() < 4. * EPSILON);
+                assert!(amp.im.abs() < EPSILON);
+            }
+        }
+    }
+}
+
 #[test]
 fn mix_multi_qubit_kraus_map_01() {
     let env = &QuestEnv::new();
@@ -2199,6 +5446,79 @@ fn calc_hilbert_schmidt_distance_03() {
     let _ = calc_hilbert_schmidt_distance(a, b).unwrap_err();
 }
 
+#[test]
+fn calc_hilbert_schmidt_distance_04() {
+    let env = &QuestEnv::new();
+    let a = &mut Qureg::try_new_density(1, env).unwrap();
+    let b = &mut Qureg::try_new_density(1, env).unwrap();
+
+    // general (not normalized, not Hermitian) matrices
+    a.init_state_from_amps(&[1., 2., 3., 4.], &[0., 1., 0., -1.])
+        .unwrap();
+    b.init_state_from_amps(&[-1., 0., -2., 0.5], &[1., 0., 0., 2.])
+        .unwrap();
+
+    // the literal Frobenius norm of the difference
+    let dist = calc_hilbert_schmidt_distance(a, b).unwrap();
+    assert!((dist - 7.5).abs() < EPSILON);
+
+    // no normalization: the distance between rho and 2 rho is not zero
+    a.init_classical_state(0).unwrap();
+    b.init_state_from_amps(&[2., 0., 0., 0.], &[0., 0., 0., 0.])
+        .unwrap();
+    let dist = calc_hilbert_schmidt_distance(a, b).unwrap();
+    assert!((dist - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn mix_multi_qubit_kraus_map_04() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(3, env).unwrap();
+    qureg.init_zero_state();
+    let mut m = ComplexMatrixN::try_new(1).unwrap();
+    init_complex_matrix_n(
+        &mut m,
+        &[&[0., 1.], &[1., 0.]],
+        &[&[0., 0.], &[0., 0.]],
+    )
+    .unwrap();
+
+    qureg.mix_multi_qubit_kraus_map(&[1], &[&m]).unwrap();
+
+    // The operator acts on one qubit only
+    let err = qureg.mix_multi_qubit_kraus_map(&[1, 2], &[&m]).unwrap_err();
+    assert!(matches!(
+        err,
+        QuestError::InvalidQuESTInputError { err_func, .. }
+            if err_func == "mix_multi_qubit_kraus_map"
+    ));
+    qureg
+        .mix_nontp_multi_qubit_kraus_map(&[1, 2], &[&m])
+        .unwrap_err();
+
+    qureg.mix_multi_qubit_kraus_map(&[3], &[&m]).unwrap_err();
+    qureg.mix_multi_qubit_kraus_map(&[-1], &[&m]).unwrap_err();
+}
+
+#[test]
+fn mix_multi_qubit_kraus_map_05() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(3, env).unwrap();
+    qureg.init_zero_state();
+    let m = &ComplexMatrixN::try_new(3).unwrap();
+
+    // For three targets, the limit is (2N)^2 = 36 operators, not 2^(2N) = 64
+    let ops = [m; 37];
+    assert_eq!(
+        qureg.mix_multi_qubit_kraus_map(&[0, 1, 2], &ops),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.mix_nontp_multi_qubit_kraus_map(&[0, 1, 2], &ops),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
 #[test]
 fn mix_nontp_kraus_map_01() {
     let env = &QuestEnv::new();
@@ -2375,6 +5695,40 @@ fn apply_matrix4_01() {
     qureg.apply_matrix4(3, -3, m).unwrap_err();
 }
 
+#[test]
+fn apply_matrix4_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(2, env).unwrap();
+    rho.init_zero_state();
+
+    // swap the amplitudes of |00> and |01>
+    let m = &ComplexMatrix4::new(
+        [
+            [0., 1., 0., 0.],
+            [1., 0., 0., 0.],
+            [0., 0., 1., 0.],
+            [0., 0., 0., 1.],
+        ],
+        [
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+        ],
+    );
+    rho.apply_matrix4(0, 1, m).unwrap();
+
+    // rho = |01><00|, by left-multiplication only
+    for row in 0..4 {
+        for col in 0..4 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let expected = if (row, col) == (1, 0) { 1. } else { 0. };
+            assert!((amp.re - expected).abs() < EPSILON);
+            assert!(amp.im.abs() < EPSILON);
+        }
+    }
+}
+
 #[test]
 fn apply_matrix_n_01() {
     let env = &QuestEnv::new();
@@ -2468,6 +5822,69 @@ fn apply_multi_controlled_matrix_n_01() {
         .unwrap_err();
 }
 
+#[test]
+fn apply_multi_controlled_matrix_n_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    qureg.init_zero_state();
+
+    let u = &mut ComplexMatrixN::try_new(2).unwrap();
+    let zero_row = &[0., 0., 0., 0.];
+    init_complex_matrix_n(
+        u,
+        &[
+            &[0., 0., 0., 1.],
+            &[0., 1., 0., 0.],
+            &[0., 0., 1., 0.],
+            &[1., 0., 0., 0.],
+        ],
+        &[zero_row, zero_row, zero_row, zero_row],
+    )
+    .unwrap();
+
+    let err_with = |err_msg: &str| QuestError::InvalidQuESTInputError {
+        err_msg:  err_msg.to_owned(),
+        err_func: "apply_multi_controlled_matrix_n".to_owned(),
+    };
+
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 2], &[2, 3], u)
+            .unwrap_err(),
+        err_with("Control and target qubits must be disjoint.")
+    );
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 0], &[2, 3], u)
+            .unwrap_err(),
+        err_with("The control qubits should be unique.")
+    );
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 1], &[3, 3], u)
+            .unwrap_err(),
+        err_with("The target qubits must be unique.")
+    );
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 4], &[2, 3], u)
+            .unwrap_err(),
+        err_with("Invalid control qubit. Must be >=0 and <numQubits.")
+    );
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 1], &[2, -1], u)
+            .unwrap_err(),
+        err_with("Invalid target qubit. Must be >=0 and <numQubits.")
+    );
+    assert_eq!(
+        qureg
+            .apply_multi_controlled_matrix_n(&[0, 1], &[2], u)
+            .unwrap_err(),
+        err_with("The matrix size does not match the number of target qubits.")
+    );
+}
+
 #[test]
 fn apply_qft_01() {
     let env = &QuestEnv::new();
@@ -2492,6 +5909,70 @@ fn apply_qft_02() {
     qureg.apply_qft(&[4, 0]).unwrap_err();
 }
 
+#[test]
+fn apply_inverse_qft_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(4, env).unwrap();
+    let expected = &mut Qureg::try_new(4, env).unwrap();
+    expected.init_debug_state();
+
+    for qubits in [&[0][..], &[1, 0], &[3, 0, 2], &[0, 1, 2, 3], &[2, 3, 1, 0]]
+    {
+        qureg.init_debug_state();
+        qureg.apply_qft(qubits).unwrap();
+        qureg.apply_inverse_qft(qubits).unwrap();
+        for i in 0..16 {
+            let amp = qureg.get_amp(i).unwrap();
+            let exp = expected.get_amp(i).unwrap();
+            assert!((amp - exp).norm() < 10. * EPSILON);
+        }
+
+        // and the other way round
+        qureg.apply_inverse_qft(qubits).unwrap();
+        qureg.apply_qft(qubits).unwrap();
+        for i in 0..16 {
+            let amp = qureg.get_amp(i).unwrap();
+            let exp = expected.get_amp(i).unwrap();
+            assert!((amp - exp).norm() < 10. * EPSILON);
+        }
+    }
+
+    qureg.init_debug_state();
+    qureg.apply_full_qft();
+    qureg.apply_full_inverse_qft();
+    for i in 0..16 {
+        let amp = qureg.get_amp(i).unwrap();
+        let exp = expected.get_amp(i).unwrap();
+        assert!((amp - exp).norm() < 10. * EPSILON);
+    }
+}
+
+#[test]
+fn apply_inverse_qft_02() {
+    let env = &QuestEnv::new();
+    let rho = &mut Qureg::try_new_density(3, env).unwrap();
+    let expected = &mut Qureg::try_new_density(3, env).unwrap();
+    expected.init_classical_state(6).unwrap();
+    expected.hadamard(0).unwrap();
+    expected.mix_dephasing(2, 0.2).unwrap();
+    rho.clone_qureg(expected).unwrap();
+
+    rho.apply_qft(&[0, 2, 1]).unwrap();
+    rho.apply_inverse_qft(&[0, 2, 1]).unwrap();
+    for row in 0..8 {
+        for col in 0..8 {
+            let amp = rho.get_density_amp(row, col).unwrap();
+            let exp = expected.get_density_amp(row, col).unwrap();
+            assert!((amp - exp).norm() < 10. * EPSILON);
+        }
+    }
+
+    rho.apply_inverse_qft(&[]).unwrap_err();
+    rho.apply_inverse_qft(&[0, 0]).unwrap_err();
+    rho.apply_inverse_qft(&[-1, 0]).unwrap_err();
+    rho.apply_inverse_qft(&[3, 0]).unwrap_err();
+}
+
 #[test]
 fn apply_projector_01() {
     let env = &QuestEnv::new();
@@ -2517,6 +5998,52 @@ fn apply_projector_02() {
     qureg.apply_projector(-1, 0).unwrap_err();
 }
 
+#[test]
+fn apply_projector_bits_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    // project onto |10>, i.e. qubit 0 in |0> and qubit 1 in |1>
+    qureg.apply_projector_bits(&[0, 1], &[0, 1]).unwrap();
+    for index in 0..4 {
+        let amp = qureg.get_amp(index).unwrap();
+        let expected = if index == 2 { 0.5 } else { 0. };
+        assert!((amp.re - expected).abs() < EPSILON);
+        assert!(amp.im.abs() < EPSILON);
+    }
+
+    // the order of the pairs does not matter
+    qureg.init_plus_state();
+    qureg.apply_projector_bits(&[1, 0], &[1, 0]).unwrap();
+    assert!((qureg.get_real_amp(2).unwrap() - 0.5).abs() < EPSILON);
+    assert!((qureg.calc_total_prob() - 0.25).abs() < EPSILON);
+
+    // empty pattern is the identity
+    qureg.init_plus_state();
+    qureg.apply_projector_bits(&[], &[]).unwrap();
+    assert!((qureg.calc_total_prob() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn apply_projector_bits_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    assert_eq!(
+        qureg.apply_projector_bits(&[0, 1], &[0]),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg.apply_projector_bits(&[0, 0], &[0, 0]).unwrap_err();
+    qureg.apply_projector_bits(&[0, 2], &[0, 0]).unwrap_err();
+    qureg.apply_projector_bits(&[-1], &[0]).unwrap_err();
+    qureg.apply_projector_bits(&[0, 1], &[0, 2]).unwrap_err();
+
+    // the state is not modified on failure
+    assert!((qureg.calc_total_prob() - 1.).abs() < EPSILON);
+}
+
 #[test]
 fn multi_rotate_pauli_01() {
     use PauliOpType::PAULI_X;
@@ -2562,6 +6089,70 @@ fn multi_rotate_pauli_01() {
         .unwrap_err();
 }
 
+#[test]
+fn apply_pauli_exponential_01() {
+    use PauliOpType::{
+        PAULI_X,
+        PAULI_Y,
+        PAULI_Z,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    let expected = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    expected.init_debug_state();
+
+    let targets = &[2, 0, 1];
+    let paulis = &[PAULI_X, PAULI_Y, PAULI_Z];
+    qureg.apply_pauli_exponential(targets, paulis, 0.3).unwrap();
+    expected.multi_rotate_pauli(targets, paulis, 0.6).unwrap();
+    qureg
+        .apply_pauli_exponential(targets, paulis, Degrees(45.))
+        .unwrap();
+    expected
+        .multi_rotate_pauli(targets, paulis, Degrees(90.))
+        .unwrap();
+
+    for index in 0..8 {
+        let amp = qureg.get_amp(index).unwrap();
+        let other = expected.get_amp(index).unwrap();
+        assert!((amp - other).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn apply_pauli_exponential_02() {
+    use PauliOpType::{
+        PAULI_X,
+        PAULI_Z,
+    };
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+
+    // exp(-i pi/4 Z) = diag(exp(-i pi/4), exp(i pi/4))
+    qureg.init_plus_state();
+    qureg
+        .apply_pauli_exponential(&[0], &[PAULI_Z], PI / 4.)
+        .unwrap();
+    let amp = qureg.get_amp(1).unwrap();
+    assert!((amp.arg() - PI / 4.).abs() < EPSILON);
+    let amp = qureg.get_amp(0).unwrap();
+    assert!((amp.arg() + PI / 4.).abs() < EPSILON);
+
+    assert_eq!(
+        qureg.apply_pauli_exponential(&[0, 1], &[PAULI_X], 0.1),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg
+        .apply_pauli_exponential(&[0, 0], &[PAULI_X, PAULI_X], 0.1)
+        .unwrap_err();
+    qureg
+        .apply_pauli_exponential(&[0, 2], &[PAULI_X, PAULI_X], 0.1)
+        .unwrap_err();
+}
+
 #[test]
 fn apply_phase_func_01() {
     let env = &QuestEnv::new();
@@ -3242,6 +6833,151 @@ fn calc_expec_pauli_prod_01() {
 //         .unwrap_err();
 // }
 
+#[test]
+fn expec_pauli_string_01() {
+    use PauliOpType::*;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_plus_state();
+
+    let expec_val = qureg.expec_pauli_string(&[0, 1], &[PAULI_X, PAULI_X]);
+    assert!((expec_val.unwrap() - 1.).abs() < EPSILON);
+    let expec_val = qureg.expec_pauli_string(&[1], &[PAULI_Z]).unwrap();
+    assert!(expec_val.abs() < EPSILON);
+
+    assert_eq!(
+        qureg.expec_pauli_string(&[0, 1], &[PAULI_X]),
+        Err(QuestError::ArrayLengthError)
+    );
+    qureg.expec_pauli_string(&[2], &[PAULI_X]).unwrap_err();
+    qureg
+        .expec_pauli_string(&[0, 0], &[PAULI_X, PAULI_X])
+        .unwrap_err();
+}
+
+#[test]
+fn expec_pauli_string_02() {
+    use PauliOpType::*;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_classical_state(1).unwrap();
+
+    let expec_val = qureg.expec_pauli_string(&[0], &[PAULI_Z]).unwrap();
+    assert!((expec_val + 1.).abs() < EPSILON);
+    let expec_val = qureg.expec_pauli_string(&[1], &[PAULI_Z]).unwrap();
+    assert!((expec_val - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn expec_pauli_prod_complex_01() {
+    use PauliOpType::*;
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+
+    for paulis in [
+        [PAULI_X, PAULI_Y, PAULI_Z],
+        [PAULI_I, PAULI_Y, PAULI_Y],
+        [PAULI_Z, PAULI_X, PAULI_I],
+    ] {
+        let expec_val =
+            qureg.expec_pauli_prod_complex(&[0, 1, 2], &paulis).unwrap();
+        let expected = qureg.expec_pauli_string(&[0, 1, 2], &paulis).unwrap();
+        assert!((expec_val.re - expected).abs() < EPSILON);
+        assert!(expec_val.im.abs() < EPSILON);
+    }
+
+    qureg
+        .expec_pauli_prod_complex(&[0, 1], &[PAULI_X])
+        .unwrap_err();
+    qureg
+        .expec_pauli_prod_complex(&[0, 0], &[PAULI_X, PAULI_Z])
+        .unwrap_err();
+    qureg
+        .expec_pauli_prod_complex(&[3], &[PAULI_X])
+        .unwrap_err();
+}
+
+#[test]
+fn expec_pauli_prod_complex_02() {
+    use PauliOpType::*;
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+
+    // physical state: the imaginary part vanishes
+    qureg.init_plus_state();
+    qureg.rotate_y(1, 0.3).unwrap();
+    let expec_val = qureg
+        .expec_pauli_prod_complex(&[0, 1], &[PAULI_X, PAULI_Z])
+        .unwrap();
+    let expected = qureg
+        .expec_pauli_string(&[0, 1], &[PAULI_X, PAULI_Z])
+        .unwrap();
+    assert!((expec_val.re - expected).abs() < EPSILON);
+    assert!(expec_val.im.abs() < EPSILON);
+
+    // rho = [[0.5, 0], [0.5i, 0.5]] on qubit 0
+    let rho = &mut Qureg::try_new_density(1, env).unwrap();
+    rho.init_state_from_amps(&[0.5, 0., 0., 0.5], &[0., 0.5, 0., 0.])
+        .unwrap();
+    let expec_val = rho.expec_pauli_prod_complex(&[0], &[PAULI_X]).unwrap();
+    assert!(expec_val.re.abs() < EPSILON);
+    assert!((expec_val.im - 0.5).abs() < EPSILON);
+    let expec_val = rho.expec_pauli_prod_complex(&[0], &[PAULI_Y]).unwrap();
+    assert!((expec_val.re - 0.5).abs() < EPSILON);
+    assert!(expec_val.im.abs() < EPSILON);
+    let expec_val = rho.expec_pauli_prod_complex(&[0], &[PAULI_Z]).unwrap();
+    assert!(expec_val.norm() < EPSILON);
+}
+
+#[test]
+fn bloch_vector_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+    qureg.s_gate(0).unwrap();
+    qureg.pauli_x(1).unwrap();
+
+    let v = qureg.bloch_vector(0).unwrap();
+    assert!(v.x().abs() < EPSILON);
+    assert!((v.y() - 1.).abs() < EPSILON);
+    assert!(v.z().abs() < EPSILON);
+
+    let v = qureg.bloch_vector(1).unwrap();
+    assert!(v.x().abs() < EPSILON);
+    assert!(v.y().abs() < EPSILON);
+    assert!((v.z() + 1.).abs() < EPSILON);
+
+    qureg.bloch_vector(2).unwrap_err();
+    qureg.bloch_vector(-1).unwrap_err();
+}
+
+#[test]
+fn bloch_vector_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.init_zero_state();
+    qureg.hadamard(0).unwrap();
+    qureg.controlled_not(0, 1).unwrap();
+
+    // The reduced state of a Bell pair is maximally mixed
+    for qubit in 0..2 {
+        let v = qureg.bloch_vector(qubit).unwrap();
+        assert!(v.x().abs() < EPSILON);
+        assert!(v.y().abs() < EPSILON);
+        assert!(v.z().abs() < EPSILON);
+    }
+
+    qureg.init_plus_state();
+    let v = qureg.bloch_vector(1).unwrap();
+    assert!((v.x() - 1.).abs() < EPSILON);
+    assert!(v.y().abs() < EPSILON);
+    assert!(v.z().abs() < EPSILON);
+}
+
 #[test]
 fn calc_expec_pauli_sum_01() {
     use PauliOpType::{
@@ -3261,6 +6997,40 @@ fn calc_expec_pauli_sum_01() {
         .unwrap();
 }
 
+#[test]
+fn calc_expec_pauli_sum_03() {
+    use PauliOpType::{
+        PAULI_X,
+        PAULI_Z,
+    };
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    qureg.init_zero_state();
+    let workspace = &mut Qureg::try_new(2, env).unwrap();
+
+    let term_coeffs = &[0.5, 0.5];
+    assert_eq!(
+        qureg.calc_expec_pauli_sum(
+            &[PAULI_X, PAULI_Z, PAULI_Z],
+            term_coeffs,
+            workspace
+        ),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.calc_expec_pauli_sum(&[], term_coeffs, workspace),
+        Err(QuestError::ArrayLengthError)
+    );
+    assert_eq!(
+        qureg.calc_expec_pauli_sum(
+            &[PAULI_X, PAULI_Z, PAULI_Z, PAULI_X, PAULI_X],
+            term_coeffs,
+            workspace
+        ),
+        Err(QuestError::ArrayLengthError)
+    );
+}
+
 // #[test]
 // fn calc_expec_pauli_sum_02() {
 //     use PauliOpType::{
@@ -3634,6 +7404,51 @@ fn apply_pauli_hamil_01() {
     apply_pauli_hamil(in_qureg, hamil, out_qureg).unwrap();
 }
 
+#[test]
+fn apply_pauli_hamil_to_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_debug_state();
+    let out = &mut Qureg::try_new(3, env).unwrap();
+    let out_free = &mut Qureg::try_new(3, env).unwrap();
+
+    let hamil = PauliHamilBuilder::new(3)
+        .add_term(0.3, "XYZ")
+        .add_term(-0.7, "ZIX")
+        .build()
+        .unwrap();
+
+    qureg.apply_pauli_hamil_to(&hamil, out).unwrap();
+    apply_pauli_hamil(qureg, &hamil, out_free).unwrap();
+    for i in 0..8 {
+        let amp = out.get_amp(i).unwrap();
+        let expected = out_free.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+}
+
+#[test]
+fn apply_pauli_hamil_to_02() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(2, env).unwrap();
+    let hamil = PauliHamilBuilder::new(2)
+        .add_term(1., "XX")
+        .build()
+        .unwrap();
+
+    let out = &mut Qureg::try_new_density(2, env).unwrap();
+    qureg.apply_pauli_hamil_to(&hamil, out).unwrap_err();
+    let out = &mut Qureg::try_new(3, env).unwrap();
+    qureg.apply_pauli_hamil_to(&hamil, out).unwrap_err();
+
+    let out = &mut Qureg::try_new(2, env).unwrap();
+    let hamil = PauliHamilBuilder::new(3)
+        .add_term(1., "XXX")
+        .build()
+        .unwrap();
+    qureg.apply_pauli_hamil_to(&hamil, out).unwrap_err();
+}
+
 #[test]
 fn apply_trotter_circuit_01() {
     use PauliOpType::PAULI_X;
@@ -3656,6 +7471,79 @@ fn apply_trotter_circuit_01() {
     qureg.apply_trotter_circuit(hamil, 0., 1, -1).unwrap_err();
 }
 
+#[test]
+fn apply_trotter_circuit_02() {
+    use PauliOpType::PAULI_X;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_zero_state();
+
+    let hamil = &mut PauliHamil::try_new(1, 1).unwrap();
+    init_pauli_hamil(hamil, &[1.], &[PAULI_X]).unwrap();
+
+    qureg.apply_trotter_circuit(hamil, 0., 4, 1).unwrap();
+    qureg.apply_trotter_circuit(hamil, 0., 6, 3).unwrap();
+
+    qureg.apply_trotter_circuit(hamil, 0., 0, 1).unwrap_err();
+    qureg.apply_trotter_circuit(hamil, 0., 3, 1).unwrap_err();
+    qureg.apply_trotter_circuit(hamil, 0., -2, 1).unwrap_err();
+    qureg.apply_trotter_circuit(hamil, 0., 2, 0).unwrap_err();
+}
+
+#[test]
+fn evolve_01() {
+    use PauliOpType::PAULI_X;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_zero_state();
+
+    let hamil = &mut PauliHamil::try_new(1, 1).unwrap();
+    init_pauli_hamil(hamil, &[1.], &[PAULI_X]).unwrap();
+
+    qureg
+        .evolve(hamil, PI / 2., TrotterConfig::default())
+        .unwrap();
+    assert!((qureg.get_prob_amp(1).unwrap() - 1.).abs() < EPSILON);
+
+    let config = TrotterConfig {
+        order: 2, reps: 3
+    };
+    qureg.evolve(hamil, PI / 2., config).unwrap();
+    assert!((qureg.get_prob_amp(0).unwrap() - 1.).abs() < EPSILON);
+}
+
+#[test]
+fn evolve_02() {
+    use PauliOpType::PAULI_X;
+
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(1, env).unwrap();
+    qureg.init_zero_state();
+
+    let hamil = &mut PauliHamil::try_new(1, 1).unwrap();
+    init_pauli_hamil(hamil, &[1.], &[PAULI_X]).unwrap();
+
+    let config = TrotterConfig {
+        order: 3, reps: 1
+    };
+    qureg.evolve(hamil, 0., config).unwrap_err();
+    let config = TrotterConfig {
+        order: 0, reps: 1
+    };
+    qureg.evolve(hamil, 0., config).unwrap_err();
+    let config = TrotterConfig {
+        order: 1, reps: 0
+    };
+    qureg.evolve(hamil, 0., config).unwrap_err();
+    let config = TrotterConfig {
+        order: u32::MAX,
+        reps:  1,
+    };
+    qureg.evolve(hamil, 0., config).unwrap_err();
+}
+
 #[test]
 fn set_weighted_qureg_01() {
     let env = &QuestEnv::new();
@@ -3695,6 +7583,53 @@ fn set_weighted_qureg_04() {
     set_weighted_qureg(fac1, qureg1, fac2, qureg2, fac_out, out).unwrap_err();
 }
 
+#[test]
+fn tensor_01() {
+    let env = &QuestEnv::new();
+    let a = &mut Qureg::try_new(1, env).unwrap();
+    let b = &mut Qureg::try_new(1, env).unwrap();
+    a.init_plus_state();
+    b.init_plus_state();
+
+    let qureg = tensor(a, b, env).unwrap();
+    assert_eq!(qureg.num_qubits(), 2);
+    assert!(!qureg.is_density_matrix());
+    for i in 0..4 {
+        let amp = qureg.get_real_amp(i).unwrap();
+        assert!((amp - 0.5).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn tensor_02() {
+    let env = &QuestEnv::new();
+    let a = &mut Qureg::try_new_density(1, env).unwrap();
+    let b = &mut Qureg::try_new_density(2, env).unwrap();
+    a.init_classical_state(1).unwrap();
+    b.init_plus_state();
+
+    let qureg = tensor(a, b, env).unwrap();
+    assert_eq!(qureg.num_qubits(), 3);
+    assert!(qureg.is_density_matrix());
+    for row in 0..8 {
+        for col in 0..8 {
+            let amp = qureg.get_density_amp(row, col).unwrap();
+            let expected = if row >= 4 && col >= 4 { 0.25 } else { 0. };
+            assert!((amp.re - expected).abs() < EPSILON);
+        }
+    }
+}
+
+#[test]
+fn tensor_03() {
+    let env = &QuestEnv::new();
+    let a = &Qureg::try_new(1, env).unwrap();
+    let b = &Qureg::try_new_density(1, env).unwrap();
+
+    tensor(a, b, env).unwrap_err();
+    tensor(b, a, env).unwrap_err();
+}
+
 #[test]
 fn multi_controlled_multi_rotate_z_01() {
     let env = &QuestEnv::new();
@@ -3793,6 +7728,87 @@ fn multi_controlled_multi_rotate_pauli_01() {
         .unwrap_err();
 }
 
+#[test]
+fn multi_controlled_rotate_x_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+    other.init_plus_state();
+
+    qureg.multi_controlled_rotate_x(&[1], 0, 0.3).unwrap();
+    other.controlled_rotate_x(1, 0, 0.3).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    qureg.multi_controlled_rotate_x(&[1, 2], 0, 0.3).unwrap();
+    qureg
+        .multi_controlled_rotate_x(&[1, 1], 0, 0.3)
+        .unwrap_err();
+    qureg
+        .multi_controlled_rotate_x(&[1, 0], 0, 0.3)
+        .unwrap_err();
+    qureg.multi_controlled_rotate_x(&[3], 0, 0.3).unwrap_err();
+    qureg.multi_controlled_rotate_x(&[1], -1, 0.3).unwrap_err();
+}
+
+#[test]
+fn multi_controlled_rotate_y_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+    other.init_plus_state();
+
+    qureg.multi_controlled_rotate_y(&[1], 0, 0.3).unwrap();
+    other.controlled_rotate_y(1, 0, 0.3).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    qureg.multi_controlled_rotate_y(&[1, 2], 0, 0.3).unwrap();
+    qureg
+        .multi_controlled_rotate_y(&[1, 1], 0, 0.3)
+        .unwrap_err();
+    qureg
+        .multi_controlled_rotate_y(&[1, 0], 0, 0.3)
+        .unwrap_err();
+    qureg.multi_controlled_rotate_y(&[3], 0, 0.3).unwrap_err();
+    qureg.multi_controlled_rotate_y(&[1], -1, 0.3).unwrap_err();
+}
+
+#[test]
+fn multi_controlled_rotate_z_01() {
+    let env = &QuestEnv::new();
+    let qureg = &mut Qureg::try_new(3, env).unwrap();
+    qureg.init_plus_state();
+    let other = &mut Qureg::try_new(3, env).unwrap();
+    other.init_plus_state();
+
+    qureg.multi_controlled_rotate_z(&[1], 0, 0.3).unwrap();
+    other.controlled_rotate_z(1, 0, 0.3).unwrap();
+    for i in 0..8 {
+        let amp = qureg.get_amp(i).unwrap();
+        let expected = other.get_amp(i).unwrap();
+        assert!((amp - expected).norm() < EPSILON);
+    }
+
+    qureg.multi_controlled_rotate_z(&[1, 2], 0, 0.3).unwrap();
+    qureg
+        .multi_controlled_rotate_z(&[1, 1], 0, 0.3)
+        .unwrap_err();
+    qureg
+        .multi_controlled_rotate_z(&[1, 0], 0, 0.3)
+        .unwrap_err();
+    qureg.multi_controlled_rotate_z(&[3], 0, 0.3).unwrap_err();
+    qureg.multi_controlled_rotate_z(&[1], -1, 0.3).unwrap_err();
+}
+
 #[test]
 fn check_array_length_init_state_from_amps() {
     let env = &QuestEnv::new();